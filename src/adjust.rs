@@ -0,0 +1,1037 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    column_name_to_number, column_number_to_name, coordinates_to_cell_name,
+    coordinates_to_range_ref, get_sheet_table_paths, move_comments, range_ref_to_coordinates,
+    split_cell_name, xml_table, CTAutoFilter, CTSortState, Cell, ExcelizeError, Spreadsheet, Table,
+    Worksheet, XMLWorksheet, TOTAL_COLUMNS, TOTAL_ROWS,
+};
+
+/// AdjustDirection specifies whether the rows or the columns of a worksheet
+/// are being inserted or removed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdjustDirection {
+    Columns,
+    Rows,
+}
+
+pub trait Adjust {
+    /// adjust_helper provides a function to adjust rows and columns dimensions,
+    /// hyperlinks, merged cells, auto filter, data validations, conditional
+    /// formats, protected ranges, comments, tables, defined names and formulas
+    /// when inserting or deleting rows or columns. The offset is the number of
+    /// inserted rows or columns, or -1 when the row or column at num is
+    /// removed.
+    fn adjust_helper(
+        &mut self,
+        sheet: &str,
+        dir: AdjustDirection,
+        num: u32,
+        offset: i32,
+    ) -> Result<(), ExcelizeError>;
+}
+
+impl Adjust for Spreadsheet {
+    fn adjust_helper(
+        &mut self,
+        sheet: &str,
+        dir: AdjustDirection,
+        num: u32,
+        offset: i32,
+    ) -> Result<(), ExcelizeError> {
+        let ws = self.get_worksheet_mut(sheet)?;
+        match dir {
+            AdjustDirection::Rows => adjust_row_dimensions(ws, num, offset)?,
+            AdjustDirection::Columns => {
+                adjust_col_dimensions(ws, num, offset)?;
+                adjust_cols(ws, num, offset);
+            }
+        }
+        adjust_dimension(ws, dir, num, offset);
+        adjust_merge_cells(ws, dir, num, offset);
+        adjust_auto_filter(ws, dir, num, offset);
+        if let Some(ref mut sort_state) = ws.sort_state {
+            if !adjust_sort_state(sort_state, dir, num, offset) {
+                ws.sort_state = None;
+            }
+        }
+        adjust_hyperlinks(ws, dir, num, offset);
+        adjust_sqref_ranges(ws, dir, num, offset);
+        adjust_comments(self, sheet, dir, num, offset)?;
+        adjust_tables(self, sheet, dir, num, offset)?;
+        for (name, ws) in self.worksheets.iter_mut() {
+            adjust_formulas(ws, sheet, is_same_sheet(name, sheet), dir, num, offset);
+        }
+        if let Some(ref mut wb) = self.workbook {
+            if let Some(ref mut defined_names) = wb.defined_names {
+                for defined_name in defined_names.defined_name.iter_mut() {
+                    defined_name.data =
+                        adjust_formula(&defined_name.data, sheet, false, dir, num, offset);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// is_same_sheet compares worksheet names case-insensitively, the same as the
+// spreadsheet application does.
+fn is_same_sheet(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+// adjust_span provides a function to adjust the one-dimensional span
+// [start, end] of row or column numbers. Returns None if the whole span was
+// removed.
+fn adjust_span(start: u32, end: u32, num: u32, offset: i32, max: u32) -> Option<(u32, u32)> {
+    let (mut start, mut end) = (start, end);
+    if offset > 0 {
+        if start >= num {
+            start += offset as u32;
+        }
+        if end >= num {
+            end += offset as u32;
+        }
+        if start > max {
+            return None;
+        }
+        return Some((start, end.min(max)));
+    }
+    if start == num && end == num {
+        return None;
+    }
+    if start > num {
+        start -= 1;
+    }
+    if end >= num {
+        end -= 1;
+    }
+    Some((start, end))
+}
+
+// adjust_range_ref provides a function to adjust the range reference of cells
+// by the given direction. Returns None if the range is invalid or the whole
+// range was removed.
+fn adjust_range_ref(range: &str, dir: AdjustDirection, num: u32, offset: i32) -> Option<String> {
+    let mut coordinates = match range_ref_to_coordinates(range) {
+        Ok(c) => c,
+        Err(_) => return None,
+    };
+    let (i, max) = match dir {
+        AdjustDirection::Columns => (0, TOTAL_COLUMNS),
+        AdjustDirection::Rows => (1, TOTAL_ROWS),
+    };
+    let (start, end) = adjust_span(coordinates[i], coordinates[i + 2], num, offset, max)?;
+    coordinates[i] = start;
+    coordinates[i + 2] = end;
+    coordinates_to_range_ref(&coordinates).ok()
+}
+
+// adjust_row_dimensions provides a function to update row numbers and cell
+// references in the sheet data.
+fn adjust_row_dimensions(
+    ws: &mut XMLWorksheet,
+    num: u32,
+    offset: i32,
+) -> Result<(), ExcelizeError> {
+    let rows = match ws.sheet_data.row {
+        Some(ref mut rows) => rows,
+        None => return Ok(()),
+    };
+    for row in rows.iter() {
+        for c in &row.c {
+            split_cell_name(&c.r)?;
+        }
+    }
+    if offset > 0 {
+        let last = rows.iter().filter_map(|r| r.r).max().unwrap_or(0);
+        if last >= num && last + offset as u32 > TOTAL_ROWS {
+            return Err(ExcelizeError::CommonError(String::from(
+                "row number exceeds maximum limit",
+            )));
+        }
+    } else {
+        rows.retain(|r| r.r != Some(num));
+    }
+    for row in rows.iter_mut() {
+        let r = match row.r {
+            Some(r) if r >= num => (r as i64 + offset as i64) as u32,
+            _ => continue,
+        };
+        row.r = Some(r);
+        for c in row.c.iter_mut() {
+            let (col, _) = split_cell_name(&c.r)?;
+            c.r = format!("{}{}", col, r);
+        }
+    }
+    Ok(())
+}
+
+// adjust_col_dimensions provides a function to update cell references and row
+// spans in the sheet data when inserting or removing columns.
+fn adjust_col_dimensions(
+    ws: &mut XMLWorksheet,
+    num: u32,
+    offset: i32,
+) -> Result<(), ExcelizeError> {
+    let rows = match ws.sheet_data.row {
+        Some(ref mut rows) => rows,
+        None => return Ok(()),
+    };
+    for row in rows.iter() {
+        for c in &row.c {
+            let (col, _) = split_cell_name(&c.r)?;
+            let col = column_name_to_number(&col)?;
+            if offset > 0 && col >= num && col + offset as u32 > TOTAL_COLUMNS {
+                return Err(ExcelizeError::CommonError(String::from(
+                    "column number exceeds maximum limit",
+                )));
+            }
+        }
+    }
+    for row in rows.iter_mut() {
+        let mut cells = Vec::with_capacity(row.c.len());
+        for mut c in row.c.drain(..) {
+            let (col_name, r) = split_cell_name(&c.r)?;
+            let col = column_name_to_number(&col_name)?;
+            if offset < 0 && col == num {
+                continue;
+            }
+            if col >= num {
+                let col = (col as i64 + offset as i64) as u32;
+                c.r = format!("{}{}", column_number_to_name(col)?, r);
+            }
+            cells.push(c);
+        }
+        row.c = cells;
+        if let Some(ref spans) = row.spans {
+            row.spans = spans.split_once(':').and_then(|(start, end)| {
+                let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+                let (start, end) = adjust_span(start, end, num, offset, TOTAL_COLUMNS)?;
+                Some(format!("{}:{}", start, end))
+            });
+        }
+    }
+    Ok(())
+}
+
+// adjust_cols provides a function to update the column width and formatting
+// spans, the inserted columns inherit the format of the column on the left.
+fn adjust_cols(ws: &mut XMLWorksheet, num: u32, offset: i32) {
+    if let Some(ref mut cols) = ws.cols {
+        cols.col.retain_mut(|col| {
+            if offset > 0 {
+                if col.min >= num {
+                    col.min = (col.min + offset as u32).min(TOTAL_COLUMNS);
+                }
+                if col.max >= num || col.max + 1 == num {
+                    col.max = (col.max + offset as u32).min(TOTAL_COLUMNS);
+                }
+                return true;
+            }
+            match adjust_span(col.min, col.max, num, offset, TOTAL_COLUMNS) {
+                Some((min, max)) => {
+                    col.min = min;
+                    col.max = max;
+                    true
+                }
+                None => false,
+            }
+        });
+        if cols.col.is_empty() {
+            ws.cols = None;
+        }
+    }
+}
+
+// adjust_dimension provides a function to update the used range of the
+// worksheet.
+fn adjust_dimension(ws: &mut XMLWorksheet, dir: AdjustDirection, num: u32, offset: i32) {
+    if let Some(ref mut dimension) = ws.dimension {
+        if let Some(ref_attr) = adjust_range_ref(&dimension.ref_attr, dir, num, offset) {
+            dimension.ref_attr = ref_attr;
+        }
+    }
+}
+
+// adjust_merge_cells provides a function to update merged cells, the merged
+// cells which were removed or reduced to a single cell are deleted.
+fn adjust_merge_cells(ws: &mut XMLWorksheet, dir: AdjustDirection, num: u32, offset: i32) {
+    if let Some(ref mut merge_cells) = ws.merge_cells {
+        merge_cells.merge_cell.retain_mut(|merge_cell| {
+            match adjust_range_ref(&merge_cell.ref_attr, dir, num, offset) {
+                Some(ref_attr) if ref_attr.contains(':') => {
+                    merge_cell.ref_attr = ref_attr;
+                    true
+                }
+                _ => false,
+            }
+        });
+        if merge_cells.merge_cell.is_empty() {
+            ws.merge_cells = None;
+        } else {
+            merge_cells.count = Some(merge_cells.merge_cell.len() as u32);
+        }
+    }
+}
+
+// adjust_auto_filter provides a function to update the auto filter range and
+// the column IDs of its filter columns. The auto filter will be deleted when
+// its header row is removed.
+fn adjust_auto_filter(ws: &mut XMLWorksheet, dir: AdjustDirection, num: u32, offset: i32) {
+    let auto_filter = match ws.auto_filter {
+        Some(ref mut auto_filter) => auto_filter,
+        None => return,
+    };
+    let coordinates = match auto_filter.ref_attr {
+        Some(ref r) => match range_ref_to_coordinates(r) {
+            Ok(c) => c,
+            Err(_) => return,
+        },
+        None => return,
+    };
+    let removed = match dir {
+        AdjustDirection::Rows => offset < 0 && coordinates[1] == num,
+        AdjustDirection::Columns => offset < 0 && coordinates[0] == num && coordinates[2] == num,
+    };
+    if removed {
+        ws.auto_filter = None;
+        if dir == AdjustDirection::Rows {
+            if let Some(ref mut rows) = ws.sheet_data.row {
+                for row in rows.iter_mut() {
+                    if let Some(r) = row.r {
+                        if r > coordinates[1] && r <= coordinates[3] {
+                            row.hidden = None;
+                        }
+                    }
+                }
+            }
+        }
+        return;
+    }
+    adjust_filter_range(auto_filter, &coordinates, dir, num, offset);
+}
+
+// adjust_filter_range provides a function to update the range of the auto
+// filter by given coordinates of the range, the column IDs of its filter
+// columns and its sort state.
+fn adjust_filter_range(
+    auto_filter: &mut CTAutoFilter,
+    coordinates: &[u32; 4],
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+) {
+    if dir == AdjustDirection::Columns && num <= coordinates[2] {
+        auto_filter.filter_column.retain_mut(|filter_column| {
+            let col = coordinates[0] + filter_column.col_id;
+            if offset < 0 {
+                if col == num {
+                    return false;
+                }
+                if col > num && num >= coordinates[0] {
+                    filter_column.col_id -= 1;
+                }
+            } else if col >= num && num > coordinates[0] {
+                filter_column.col_id += offset as u32;
+            }
+            true
+        });
+    }
+    if let Some(ref r) = auto_filter.ref_attr {
+        auto_filter.ref_attr = adjust_range_ref(r, dir, num, offset);
+    }
+    if let Some(ref mut sort_state) = auto_filter.sort_state {
+        if !adjust_sort_state(sort_state, dir, num, offset) {
+            auto_filter.sort_state = None;
+        }
+    }
+}
+
+// adjust_sort_state provides a function to update the references of the sort
+// state and its sort conditions. Returns false if the sorted range was
+// removed.
+fn adjust_sort_state(
+    sort_state: &mut CTSortState,
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+) -> bool {
+    match adjust_range_ref(&sort_state.ref_attr, dir, num, offset) {
+        Some(ref_attr) => sort_state.ref_attr = ref_attr,
+        None => return false,
+    }
+    sort_state.sort_condition.retain_mut(|condition| {
+        match adjust_range_ref(&condition.ref_attr, dir, num, offset) {
+            Some(ref_attr) => {
+                condition.ref_attr = ref_attr;
+                true
+            }
+            None => false,
+        }
+    });
+    true
+}
+
+// adjust_hyperlinks provides a function to update hyperlinks, the hyperlinks
+// of the removed cells are deleted.
+fn adjust_hyperlinks(ws: &mut XMLWorksheet, dir: AdjustDirection, num: u32, offset: i32) {
+    if let Some(ref mut hyperlinks) = ws.hyperlinks {
+        hyperlinks.hyperlink.retain_mut(|hyperlink| {
+            match adjust_range_ref(&hyperlink.ref_attr, dir, num, offset) {
+                Some(ref_attr) => {
+                    hyperlink.ref_attr = ref_attr;
+                    true
+                }
+                None => false,
+            }
+        });
+        if hyperlinks.hyperlink.is_empty() {
+            ws.hyperlinks = None;
+        }
+    }
+}
+
+// adjust_cell provides a function to adjust the column and row number of a
+// cell by the given direction. Returns None if the cell was removed.
+fn adjust_cell(
+    col: u32,
+    row: u32,
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+) -> Option<(u32, u32)> {
+    match dir {
+        AdjustDirection::Columns => {
+            let (col, _) = adjust_span(col, col, num, offset, TOTAL_COLUMNS)?;
+            Some((col, row))
+        }
+        AdjustDirection::Rows => {
+            let (row, _) = adjust_span(row, row, num, offset, TOTAL_ROWS)?;
+            Some((col, row))
+        }
+    }
+}
+
+// adjust_comments provides a function to update the cells of the comments, the
+// anchors of their shapes in the VML drawing and the cells of the threaded
+// comments, the comments and the threads of the removed cells are deleted.
+fn adjust_comments(
+    spreadsheet: &mut Spreadsheet,
+    sheet: &str,
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+) -> Result<(), ExcelizeError> {
+    move_comments(spreadsheet, sheet, |col, row| {
+        adjust_cell(col, row, dir, num, offset)
+    })
+}
+
+// adjust_tables provides a function to update the tables of the worksheet,
+// the tables which header row or all of the data rows or columns were removed
+// are deleted, and the names of the inserted columns are written into the
+// header cells.
+fn adjust_tables(
+    spreadsheet: &mut Spreadsheet,
+    sheet: &str,
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+) -> Result<(), ExcelizeError> {
+    let sheet_path = spreadsheet.get_sheet_xml_path(sheet)?;
+    let (mut deleted, mut headers) = (Vec::new(), Vec::new());
+    for path in get_sheet_table_paths(spreadsheet, &sheet_path) {
+        if let Some(table) = spreadsheet.tables.get_mut(&path) {
+            if !adjust_table(table, dir, num, offset, &mut headers) {
+                deleted.push(table.display_name.clone());
+            }
+        }
+    }
+    for name in deleted {
+        spreadsheet.delete_table(&name)?;
+    }
+    for (cell, header) in headers {
+        spreadsheet.set_cell_str(sheet, &cell, &header)?;
+    }
+    Ok(())
+}
+
+// adjust_table provides a function to update the range, the columns and the
+// auto filter of the table, the columns inserted in the table will be named
+// as ColumnN, and their header cells and names are appended to the headers.
+// Returns false if the table should be deleted.
+fn adjust_table(
+    table: &mut xml_table::XMLTable,
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+    headers: &mut Vec<(String, String)>,
+) -> bool {
+    let coordinates = match range_ref_to_coordinates(&table.ref_attr) {
+        Ok(c) => c,
+        Err(_) => return true,
+    };
+    let new_coordinates = match adjust_range_ref(&table.ref_attr, dir, num, offset)
+        .and_then(|r| range_ref_to_coordinates(&r).ok())
+    {
+        Some(c) => c,
+        None => return false,
+    };
+    let header_rows = u32::from(table.header_row_count != Some(0));
+    if dir == AdjustDirection::Rows && offset < 0 {
+        if header_rows > 0 && num == coordinates[1] {
+            return false;
+        }
+        if table.totals_row_count.unwrap_or(0) > 0 && num == coordinates[3] {
+            table.totals_row_count = None;
+            table.totals_row_shown = Some(false);
+            for column in table.table_columns.table_column.iter_mut() {
+                column.totals_row_function = None;
+                column.totals_row_label = None;
+            }
+        }
+    }
+    let totals_rows = table.totals_row_count.unwrap_or(0);
+    if new_coordinates[3] - new_coordinates[1] < header_rows + totals_rows {
+        return false;
+    }
+    if dir == AdjustDirection::Columns && num >= coordinates[0] && num <= coordinates[2] {
+        let columns = &mut table.table_columns.table_column;
+        let index = (num - coordinates[0]) as usize;
+        if offset < 0 {
+            if index < columns.len() {
+                columns.remove(index);
+            }
+        } else if index > 0 {
+            let id = columns.iter().map(|c| c.id).max().unwrap_or(0);
+            let count = (new_coordinates[2] - new_coordinates[0] + 1) as usize;
+            let mut n = 1;
+            for i in 0..count.saturating_sub(columns.len()) {
+                while columns
+                    .iter()
+                    .any(|c| c.name.eq_ignore_ascii_case(&format!("Column{}", n)))
+                {
+                    n += 1;
+                }
+                columns.insert(
+                    index + i,
+                    xml_table::CTTableColumn {
+                        id: id + i as u32 + 1,
+                        name: format!("Column{}", n),
+                        ..Default::default()
+                    },
+                );
+                if header_rows > 0 {
+                    let col = new_coordinates[0] + (index + i) as u32;
+                    if let Ok(cell) = coordinates_to_cell_name(col, new_coordinates[1]) {
+                        headers.push((cell, format!("Column{}", n)));
+                    }
+                }
+            }
+        }
+        table.table_columns.count = Some(columns.len() as u32);
+    }
+    if let Some(ref mut auto_filter) = table.auto_filter {
+        if let Some(filter_coordinates) = auto_filter
+            .ref_attr
+            .as_deref()
+            .and_then(|r| range_ref_to_coordinates(r).ok())
+        {
+            adjust_filter_range(auto_filter, &filter_coordinates, dir, num, offset);
+        }
+    }
+    if let Ok(ref_attr) = coordinates_to_range_ref(&new_coordinates) {
+        table.ref_attr = ref_attr;
+    }
+    true
+}
+
+// adjust_sqref provides a function to update the space separated sequence of
+// references, returns None if all of the references were removed.
+fn adjust_sqref(sqref: &str, dir: AdjustDirection, num: u32, offset: i32) -> Option<String> {
+    let refs: Vec<String> = sqref
+        .split_whitespace()
+        .filter_map(|r| adjust_range_ref(r, dir, num, offset))
+        .collect();
+    if refs.is_empty() {
+        return None;
+    }
+    Some(refs.join(" "))
+}
+
+// adjust_sqref_ranges provides a function to update the ranges of the data
+// validations, conditional formats and protected ranges, the rules and ranges
+// which no longer apply to any cells are deleted.
+fn adjust_sqref_ranges(ws: &mut XMLWorksheet, dir: AdjustDirection, num: u32, offset: i32) {
+    if let Some(ref mut data_validations) = ws.data_validations {
+        data_validations.data_validation.retain_mut(|dv| {
+            match adjust_sqref(&dv.sqref, dir, num, offset) {
+                Some(sqref) => {
+                    dv.sqref = sqref;
+                    true
+                }
+                None => false,
+            }
+        });
+        if data_validations.data_validation.is_empty() {
+            ws.data_validations = None;
+        } else {
+            data_validations.count = Some(data_validations.data_validation.len() as u32);
+        }
+    }
+    if let Some(ref mut conditional_formatting) = ws.conditional_formatting {
+        conditional_formatting.retain_mut(|cf| match adjust_sqref(&cf.sqref, dir, num, offset) {
+            Some(sqref) => {
+                cf.sqref = sqref;
+                true
+            }
+            None => false,
+        });
+        if conditional_formatting.is_empty() {
+            ws.conditional_formatting = None;
+        }
+    }
+    if let Some(ref mut protected_ranges) = ws.protected_ranges {
+        protected_ranges.protected_range.retain_mut(|range| {
+            match adjust_sqref(&range.sqref, dir, num, offset) {
+                Some(sqref) => {
+                    range.sqref = sqref;
+                    true
+                }
+                None => false,
+            }
+        });
+        if protected_ranges.protected_range.is_empty() {
+            ws.protected_ranges = None;
+        }
+    }
+}
+
+// adjust_formulas provides a function to update the references in the cell
+// formulas, data validations and conditional formats of the worksheet. The
+// local flag indicates whether the worksheet is the one being adjusted, in
+// which case the unqualified references and the shared formula ranges are
+// updated as well.
+fn adjust_formulas(
+    ws: &mut XMLWorksheet,
+    sheet: &str,
+    local: bool,
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+) {
+    if let Some(ref mut rows) = ws.sheet_data.row {
+        for row in rows.iter_mut() {
+            for c in row.c.iter_mut() {
+                if let Some(ref mut f) = c.f {
+                    if let Some(ref content) = f.content {
+                        f.content = Some(adjust_formula(content, sheet, local, dir, num, offset));
+                    }
+                    if local {
+                        if let Some(ref r) = f.ref_attr {
+                            if let Some(r) = adjust_range_ref(r, dir, num, offset) {
+                                f.ref_attr = Some(r);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if let Some(ref mut data_validations) = ws.data_validations {
+        for dv in data_validations.data_validation.iter_mut() {
+            for formula in [&mut dv.formula1, &mut dv.formula2] {
+                if let Some(ref content) = formula {
+                    *formula = Some(adjust_formula(content, sheet, local, dir, num, offset));
+                }
+            }
+        }
+    }
+    if let Some(ref mut conditional_formatting) = ws.conditional_formatting {
+        for cf in conditional_formatting.iter_mut() {
+            for rule in cf.cf_rule.iter_mut() {
+                if let Some(ref mut formulas) = rule.formula {
+                    for formula in formulas.iter_mut() {
+                        *formula = adjust_formula(formula, sheet, local, dir, num, offset);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// RefPart represents one side of a cell reference in a formula, which could
+/// be a cell, a whole column or a whole row, with its absolute markers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RefPart {
+    Cell(u32, bool, u32, bool),
+    Col(u32, bool),
+    Row(u32, bool),
+}
+
+impl RefPart {
+    // parse provides a function to parse the part of reference, returns None
+    // if the text is not a valid cell, column or row reference.
+    fn parse(text: &str) -> Option<RefPart> {
+        let bytes = text.as_bytes();
+        let mut i = 0;
+        let abs_col = bytes.first() == Some(&b'$');
+        if abs_col {
+            i += 1;
+        }
+        let col_start = i;
+        while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+            i += 1;
+        }
+        let col_name = &text[col_start..i];
+        let abs_row = bytes.get(i) == Some(&b'$');
+        if abs_row {
+            i += 1;
+        }
+        let row_name = &text[i..];
+        if !row_name.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let col = match col_name.is_empty() {
+            true => None,
+            false => Some(column_name_to_number(col_name).ok()?),
+        };
+        let row = match row_name.is_empty() {
+            true => None,
+            false => match row_name.parse::<u32>() {
+                Ok(r) if r >= 1 && r <= TOTAL_ROWS => Some(r),
+                _ => return None,
+            },
+        };
+        match (col, row) {
+            (Some(col), Some(row)) => Some(RefPart::Cell(col, abs_col, row, abs_row)),
+            (Some(col), None) if !abs_row => Some(RefPart::Col(col, abs_col)),
+            (None, Some(row)) => Some(RefPart::Row(row, abs_col || abs_row)),
+            _ => None,
+        }
+    }
+
+    // value returns the row or column number of the part in the given
+    // direction, returns None if the part is not affected by the direction.
+    fn value(&self, dir: AdjustDirection) -> Option<u32> {
+        match (*self, dir) {
+            (RefPart::Cell(col, _, _, _), AdjustDirection::Columns) => Some(col),
+            (RefPart::Cell(_, _, row, _), AdjustDirection::Rows) => Some(row),
+            (RefPart::Col(col, _), AdjustDirection::Columns) => Some(col),
+            (RefPart::Row(row, _), AdjustDirection::Rows) => Some(row),
+            _ => None,
+        }
+    }
+
+    // with_value returns a copy of the part with the row or column number in
+    // the given direction replaced.
+    fn with_value(&self, dir: AdjustDirection, v: u32) -> RefPart {
+        match (*self, dir) {
+            (RefPart::Cell(_, a, row, b), AdjustDirection::Columns) => RefPart::Cell(v, a, row, b),
+            (RefPart::Cell(col, a, _, b), AdjustDirection::Rows) => RefPart::Cell(col, a, v, b),
+            (RefPart::Col(_, a), AdjustDirection::Columns) => RefPart::Col(v, a),
+            (RefPart::Row(_, a), AdjustDirection::Rows) => RefPart::Row(v, a),
+            (part, _) => part,
+        }
+    }
+
+    // offset returns a copy of the part with the relative column and row
+    // moved by the given numbers, returns None if it's out of the worksheet.
+    fn offset(&self, cols: i64, rows: i64) -> Option<RefPart> {
+        let shift = |v: u32, absolute: bool, delta: i64, max: u32| match absolute {
+            true => Some(v),
+            false => {
+                let v = v as i64 + delta;
+                (v >= 1 && v <= max as i64).then_some(v as u32)
+            }
+        };
+        Some(match *self {
+            RefPart::Cell(col, a, row, b) => RefPart::Cell(
+                shift(col, a, cols, TOTAL_COLUMNS)?,
+                a,
+                shift(row, b, rows, TOTAL_ROWS)?,
+                b,
+            ),
+            RefPart::Col(col, a) => RefPart::Col(shift(col, a, cols, TOTAL_COLUMNS)?, a),
+            RefPart::Row(row, a) => RefPart::Row(shift(row, a, rows, TOTAL_ROWS)?, a),
+        })
+    }
+
+    fn to_ref(self) -> String {
+        let abs = |b: bool| if b { "$" } else { "" };
+        let col_name = |col: u32| column_number_to_name(col).unwrap_or_default();
+        match self {
+            RefPart::Cell(col, a, row, b) => {
+                format!("{}{}{}{}", abs(a), col_name(col), abs(b), row)
+            }
+            RefPart::Col(col, a) => format!("{}{}", abs(a), col_name(col)),
+            RefPart::Row(row, a) => format!("{}{}", abs(a), row),
+        }
+    }
+}
+
+// adjust_reference provides a function to adjust a cell or range reference
+// without the sheet name. Returns None if the text is not a reference, and
+// "#REF!" if the referenced cells were removed.
+fn adjust_reference(text: &str, dir: AdjustDirection, num: u32, offset: i32) -> Option<String> {
+    let max = match dir {
+        AdjustDirection::Columns => TOTAL_COLUMNS,
+        AdjustDirection::Rows => TOTAL_ROWS,
+    };
+    let (first, last) = match text.split_once(':') {
+        Some((a, b)) => {
+            let (a, b) = (RefPart::parse(a)?, RefPart::parse(b)?);
+            if std::mem::discriminant(&a) != std::mem::discriminant(&b) {
+                return None;
+            }
+            (a, Some(b))
+        }
+        None => match RefPart::parse(text)? {
+            part @ RefPart::Cell(..) => (part, None),
+            _ => return None,
+        },
+    };
+    let last_part = last.unwrap_or(first);
+    let (start, end) = match (first.value(dir), last_part.value(dir)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Some(String::from(text)),
+    };
+    let swapped = start > end;
+    let (lo, hi) = if swapped { (end, start) } else { (start, end) };
+    let (lo, hi) = match adjust_span(lo, hi, num, offset, max) {
+        Some(span) => span,
+        None => return Some(String::from("#REF!")),
+    };
+    let (start, end) = if swapped { (hi, lo) } else { (lo, hi) };
+    let first = first.with_value(dir, start);
+    Some(match last {
+        Some(last) => format!("{}:{}", first.to_ref(), last.with_value(dir, end).to_ref()),
+        None => first.to_ref(),
+    })
+}
+
+// is_formula_token_char checks if the character could be a part of the
+// reference, name or number token in a formula.
+fn is_formula_token_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '$' | ':' | '!' | '\\')
+}
+
+// adjust_formula provides a function to update the references in the formula
+// which refer to the given sheet. The local flag indicates whether the formula
+// belongs to the given sheet, in which case the references without a sheet
+// name are updated as well.
+pub(crate) fn adjust_formula(
+    formula: &str,
+    sheet: &str,
+    local: bool,
+    dir: AdjustDirection,
+    num: u32,
+    offset: i32,
+) -> String {
+    map_formula_references(formula, |name, token| match name {
+        Some(name) if is_same_sheet(name, sheet) => adjust_reference(token, dir, num, offset),
+        None if local => adjust_reference(token, dir, num, offset),
+        _ => None,
+    })
+}
+
+// offset_formula provides a function to move the relative references in the
+// formula by the given number of columns and rows, the same as copying the
+// formula to another cell. The references moved out of the worksheet will be
+// replaced with "#REF!".
+pub(crate) fn offset_formula(formula: &str, cols: i64, rows: i64) -> String {
+    map_formula_references(formula, |_, token| offset_reference(token, cols, rows))
+}
+
+// offset_reference provides a function to move the relative parts of a cell
+// or range reference without the sheet name. Returns None if the text is not
+// a reference.
+fn offset_reference(text: &str, cols: i64, rows: i64) -> Option<String> {
+    let parts = match text.split_once(':') {
+        Some((a, b)) => {
+            let (a, b) = (RefPart::parse(a)?, RefPart::parse(b)?);
+            if std::mem::discriminant(&a) != std::mem::discriminant(&b) {
+                return None;
+            }
+            vec![a, b]
+        }
+        None => match RefPart::parse(text)? {
+            part @ RefPart::Cell(..) => vec![part],
+            _ => return None,
+        },
+    };
+    let mut refs = Vec::with_capacity(parts.len());
+    for part in parts {
+        match part.offset(cols, rows) {
+            Some(part) => refs.push(part.to_ref()),
+            None => return Some(String::from("#REF!")),
+        }
+    }
+    Some(refs.join(":"))
+}
+
+// map_formula_references provides a function to replace the cell and range
+// references in the formula. The callback receives the sheet name of the
+// reference if it has one and the reference without the sheet name, and
+// returns the replacement, or None to keep the reference unchanged.
+fn map_formula_references<F>(formula: &str, mut f: F) -> String
+where
+    F: FnMut(Option<&str>, &str) -> Option<String>,
+{
+    let chars: Vec<char> = formula.chars().collect();
+    let mut result = String::with_capacity(formula.len());
+    let mut i = 0;
+    // The token after the external workbook index or the table name in the
+    // brackets can't be a reference of the given sheet.
+    let mut skip_next = false;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '"' {
+                    if chars.get(i + 1) == Some(&'"') {
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            result.extend(&chars[start..i]);
+            continue;
+        }
+        if c == '[' {
+            let start = i;
+            let mut depth = 0;
+            while i < chars.len() {
+                match chars[i] {
+                    '[' => depth += 1,
+                    ']' => depth -= 1,
+                    _ => {}
+                }
+                i += 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            result.extend(&chars[start..i]);
+            skip_next = true;
+            continue;
+        }
+        if c == '\'' {
+            let start = i;
+            let mut name = String::new();
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' {
+                    if chars.get(i + 1) == Some(&'\'') {
+                        name.push('\'');
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                name.push(chars[i]);
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            if chars.get(i) != Some(&'!') {
+                result.extend(&chars[start..i]);
+                continue;
+            }
+            i += 1;
+            let token_start = i;
+            while i < chars.len() && is_formula_token_char(chars[i]) {
+                i += 1;
+            }
+            result.extend(&chars[start..token_start]);
+            let token: String = chars[token_start..i].iter().collect();
+            match skip_next {
+                true => result.push_str(&token),
+                false => match f(Some(&name), &token) {
+                    Some(r) => result.push_str(&r),
+                    None => result.push_str(&token),
+                },
+            }
+            skip_next = false;
+            continue;
+        }
+        if is_formula_token_char(c) {
+            let start = i;
+            while i < chars.len() && is_formula_token_char(chars[i]) {
+                i += 1;
+            }
+            let token: String = chars[start..i].iter().collect();
+            let is_function = chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&'(');
+            if skip_next || is_function || chars.get(i) == Some(&'[') {
+                skip_next = false;
+                result.push_str(&token);
+                continue;
+            }
+            match token.split_once('!') {
+                Some((name, r)) => match f(Some(name), r) {
+                    Some(r) => {
+                        result.push_str(name);
+                        result.push('!');
+                        result.push_str(&r);
+                    }
+                    None => result.push_str(&token),
+                },
+                None => match f(None, &token) {
+                    Some(r) => result.push_str(&r),
+                    None => result.push_str(&token),
+                },
+            }
+            continue;
+        }
+        skip_next = false;
+        result.push(c);
+        i += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust_formula() {
+        let dir = AdjustDirection::Rows;
+        assert_eq!(
+            adjust_formula("SUM(A1:A10)+$B$5*'Sheet1'!C7", "Sheet1", true, dir, 5, 2),
+            "SUM(A1:A12)+$B$7*'Sheet1'!C9"
+        );
+        assert_eq!(
+            adjust_formula("Sheet2!A5+A5&\"A5\"", "Sheet2", false, dir, 5, -1),
+            "Sheet2!#REF!+A5&\"A5\""
+        );
+        assert_eq!(
+            adjust_formula("SUM(C:C)+LOG10(B3)+Table1[Col1]", "Sheet1", true, dir, 1, 1),
+            "SUM(C:C)+LOG10(B4)+Table1[Col1]"
+        );
+        let dir = AdjustDirection::Columns;
+        assert_eq!(
+            adjust_formula("SUM(A1:D1)+SUM(3:3)", "Sheet1", true, dir, 2, -1),
+            "SUM(A1:C1)+SUM(3:3)"
+        );
+    }
+
+    #[test]
+    fn test_offset_formula() {
+        assert_eq!(
+            offset_formula("SUM(A1:B2)*$C$3+Sheet2!D$4+'My Sheet'!$E5", 1, 2),
+            "SUM(B3:C4)*$C$3+Sheet2!E$4+'My Sheet'!$E7"
+        );
+        assert_eq!(
+            offset_formula("A1+SUM(2:3)&\"A1\"", 0, -1),
+            "#REF!+SUM(1:2)&\"A1\""
+        );
+    }
+}
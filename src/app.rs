@@ -3,7 +3,11 @@
 // the LICENSE file.
 
 use crate::{
-    xml_content_types, xml_rels, xml_sst, xml_workbook, xml_worksheet, ExcelizeError, Rels,
+    get_persons_path, get_pivot_cache_paths, get_pivot_cache_records_path, get_rels_path,
+    get_sheet_comments_path, get_sheet_pivot_table_paths, get_sheet_table_paths,
+    get_sheet_threaded_comments_path, xml_comments, xml_content_types, xml_pivot_cache,
+    xml_pivot_table, xml_rels, xml_sst, xml_styles, xml_table, xml_threaded_comments, xml_workbook,
+    xml_worksheet, Comments, ExcelizeError, PivotTables, Rels, Styles, Table, ThreadedComments,
     Workbook, Worksheet, SST,
 };
 use std::{collections::HashMap, fs, io};
@@ -15,7 +19,15 @@ pub struct Spreadsheet {
     pub workbook: Option<xml_workbook::XMLWorkbook>,
     pub worksheets: HashMap<String, xml_worksheet::XMLWorksheet>,
     pub sst: Option<xml_sst::CTSST>,
+    pub styles: Option<xml_styles::XMLStyleSheet>,
     pub rels: HashMap<String, xml_rels::XMLRelationships>,
+    pub comments: HashMap<String, xml_comments::XMLComments>,
+    pub threaded_comments: HashMap<String, xml_threaded_comments::XMLThreadedComments>,
+    pub persons: Option<xml_threaded_comments::XMLPersonList>,
+    pub tables: HashMap<String, xml_table::XMLTable>,
+    pub pivot_cache_definitions: HashMap<String, xml_pivot_cache::XMLPivotCacheDefinition>,
+    pub pivot_cache_records: HashMap<String, xml_pivot_cache::XMLPivotCacheRecords>,
+    pub pivot_tables: HashMap<String, xml_pivot_table::XMLPivotTableDefinition>,
 }
 
 impl Spreadsheet {
@@ -26,7 +38,15 @@ impl Spreadsheet {
             workbook: None,
             worksheets: HashMap::new(),
             sst: None,
+            styles: None,
             rels: HashMap::new(),
+            comments: HashMap::new(),
+            threaded_comments: HashMap::new(),
+            persons: None,
+            tables: HashMap::new(),
+            pivot_cache_definitions: HashMap::new(),
+            pivot_cache_records: HashMap::new(),
+            pivot_tables: HashMap::new(),
         };
         match fs::File::open(&path) {
             Ok(file) => {
@@ -49,7 +69,7 @@ impl Spreadsheet {
                         Some(path) => path.to_owned(),
                         None => continue,
                     };
-                    if !(&*file.name()).ends_with('/') {
+                    if !file.name().ends_with('/') {
                         let mut data = vec![];
                         match io::copy(&mut file, &mut data) {
                             Ok(_) => (),
@@ -75,7 +95,7 @@ impl Spreadsheet {
                     Ok(_) => {}
                     Err(e) => return Err(e),
                 }
-                match spreadsheet.rels_reader(&"xl/_rels/workbook.xml.rels") {
+                match spreadsheet.rels_reader("xl/_rels/workbook.xml.rels") {
                     Ok(_) => {}
                     Err(e) => return Err(e),
                 }
@@ -83,7 +103,37 @@ impl Spreadsheet {
                     Ok(_) => {}
                     Err(e) => return Err(e),
                 }
-                if spreadsheet.get_sst().is_ok() {}
+                let sheets: Vec<String> = spreadsheet.worksheets.keys().cloned().collect();
+                for sheet in sheets {
+                    if let Ok(path) = spreadsheet.get_sheet_xml_path(&sheet) {
+                        spreadsheet.rels_reader(&get_rels_path(&path)).ok();
+                        if let Some(path) = get_sheet_comments_path(&spreadsheet, &path) {
+                            spreadsheet.comments_reader(&path).ok();
+                        }
+                        if let Some(path) = get_sheet_threaded_comments_path(&spreadsheet, &path) {
+                            spreadsheet.threaded_comments_reader(&path).ok();
+                        }
+                        for path in get_sheet_table_paths(&spreadsheet, &path) {
+                            spreadsheet.tables_reader(&path).ok();
+                        }
+                        for path in get_sheet_pivot_table_paths(&spreadsheet, &path) {
+                            spreadsheet.rels_reader(&get_rels_path(&path)).ok();
+                            spreadsheet.pivot_tables_reader(&path).ok();
+                        }
+                    }
+                }
+                for path in get_pivot_cache_paths(&spreadsheet) {
+                    spreadsheet.rels_reader(&get_rels_path(&path)).ok();
+                    spreadsheet.pivot_cache_definitions_reader(&path).ok();
+                    if let Some(path) = get_pivot_cache_records_path(&spreadsheet, &path) {
+                        spreadsheet.pivot_cache_records_reader(&path).ok();
+                    }
+                }
+                if let Some(path) = get_persons_path(&spreadsheet) {
+                    spreadsheet.persons_reader(&path).ok();
+                }
+                spreadsheet.get_sst().ok();
+                spreadsheet.get_styles().ok();
                 Ok(spreadsheet)
             }
             Err(e) => Err(ExcelizeError::CommonError(e.to_string())),
@@ -102,7 +152,7 @@ mod tests {
         match wb {
             Ok(ws) => match ws.get_cell_value("Sheet1", 22, 1) {
                 Ok(c) => {
-                    let cell = String::from(c);
+                    let cell = c;
                     println!("the value of cell A2 is: {}", cell);
                     assert_eq!(cell, "GitHub");
                 }
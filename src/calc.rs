@@ -0,0 +1,1263 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    cell_name_to_coordinates, column_name_to_number, get_row, month_serial, serial_to_datetime,
+    today_serial, wildcard_match, Cell, ExcelizeError, Spreadsheet, Worksheet, XMLWorksheet,
+    MAX_DATE_SERIAL, TOTAL_COLUMNS, TOTAL_ROWS,
+};
+use std::cmp::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static FORMULA_ERRORS: [&str; 7] = [
+    "#DIV/0!", "#N/A", "#NAME?", "#NULL!", "#NUM!", "#REF!", "#VALUE!",
+];
+static MAX_NAME_DEPTH: usize = 16;
+
+// FormulaValue defined the value of the formula evaluated by the calculation
+// engine, the range reference is evaluated to an array of the cell values.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FormulaValue {
+    Blank,
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Error(String),
+    Array(Vec<Vec<FormulaValue>>),
+}
+
+impl FormulaValue {
+    // first provides a function to get the top-left value of the array, or
+    // the value itself for the scalar value.
+    fn first(self) -> FormulaValue {
+        match self {
+            FormulaValue::Array(rows) => rows
+                .into_iter()
+                .next()
+                .and_then(|row| row.into_iter().next())
+                .unwrap_or(FormulaValue::Blank),
+            value => value,
+        }
+    }
+
+    // values provides a function to get the flatten values of the array, or
+    // the value itself for the scalar value.
+    pub(crate) fn values(self) -> Vec<FormulaValue> {
+        match self {
+            FormulaValue::Array(rows) => rows.into_iter().flatten().collect(),
+            value => vec![value],
+        }
+    }
+
+    // to_number provides a function to convert the value to number, the error
+    // will be returned if the value can't be converted.
+    pub(crate) fn to_number(&self) -> Result<f64, String> {
+        match self {
+            FormulaValue::Blank => Ok(0.0),
+            FormulaValue::Number(n) => Ok(*n),
+            FormulaValue::Bool(b) => Ok(f64::from(u8::from(*b))),
+            FormulaValue::Text(s) => match s.trim().parse::<f64>() {
+                Ok(n) if n.is_finite() => Ok(n),
+                _ => Err(String::from("#VALUE!")),
+            },
+            FormulaValue::Error(e) => Err(e.clone()),
+            FormulaValue::Array(_) => self.clone().first().to_number(),
+        }
+    }
+
+    // to_text provides a function to convert the value to text, the numbers
+    // are formatted with up to 15 significant digits.
+    pub(crate) fn to_text(&self) -> Result<String, String> {
+        match self {
+            FormulaValue::Blank => Ok(String::new()),
+            FormulaValue::Number(n) => Ok(number_to_text(*n)),
+            FormulaValue::Bool(b) => Ok(String::from(if *b { "TRUE" } else { "FALSE" })),
+            FormulaValue::Text(s) => Ok(s.clone()),
+            FormulaValue::Error(e) => Err(e.clone()),
+            FormulaValue::Array(_) => self.clone().first().to_text(),
+        }
+    }
+
+    // to_bool provides a function to convert the value to boolean.
+    pub(crate) fn to_bool(&self) -> Result<bool, String> {
+        match self {
+            FormulaValue::Blank => Ok(false),
+            FormulaValue::Number(n) => Ok(*n != 0.0),
+            FormulaValue::Bool(b) => Ok(*b),
+            FormulaValue::Text(s) => match s.to_uppercase().as_str() {
+                "TRUE" => Ok(true),
+                "FALSE" => Ok(false),
+                _ => Err(String::from("#VALUE!")),
+            },
+            FormulaValue::Error(e) => Err(e.clone()),
+            FormulaValue::Array(_) => self.clone().first().to_bool(),
+        }
+    }
+}
+
+// Token defined the lexical token of the formula.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Error(String),
+    Ref(Option<String>, [u32; 4]),
+    Name(String),
+    Func(String),
+    Op(String),
+    LParen,
+    RParen,
+    Comma,
+}
+
+// Expr defined the node of the parsed formula expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Empty,
+    Value(FormulaValue),
+    Ref(Option<String>, [u32; 4]),
+    Name(String),
+    Negate(Box<Expr>),
+    Percent(Box<Expr>),
+    Binary(String, Box<Expr>, Box<Expr>),
+    Func(String, Vec<Expr>),
+}
+
+// calc_formula provides a function to evaluate the formula on the worksheet
+// by given worksheet name. The cell references, ranges, defined names,
+// operators and the common logical, information, math, text, date and
+// statistical functions are supported.
+pub(crate) fn calc_formula(
+    spreadsheet: &Spreadsheet,
+    sheet: &str,
+    formula: &str,
+) -> Result<FormulaValue, ExcelizeError> {
+    let expr = parse_formula(formula)?;
+    let ctx = Context {
+        spreadsheet,
+        sheet: String::from(sheet),
+        depth: 0,
+    };
+    Ok(ctx.eval(&expr).unwrap_or_else(FormulaValue::Error))
+}
+
+// parse_formula provides a function to parse the formula to the expression.
+fn parse_formula(formula: &str) -> Result<Expr, ExcelizeError> {
+    let tokens = tokenize(formula)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.comparison()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(invalid_formula(formula));
+    }
+    Ok(expr)
+}
+
+// parse_reference provides a function to parse the cell, range, column range
+// or row range reference with the optional worksheet name, such as
+// 'Sheet 1'!$A$1:$B$2. Returns None if the text is not a single reference.
+pub(crate) fn parse_reference(text: &str) -> Option<(Option<String>, [u32; 4])> {
+    match tokenize(text).ok()?.as_slice() {
+        [Token::Ref(sheet, area)] => Some((sheet.clone(), *area)),
+        _ => None,
+    }
+}
+
+fn invalid_formula(formula: &str) -> ExcelizeError {
+    ExcelizeError::CommonError(format!("invalid formula {:?}", formula))
+}
+
+// tokenize provides a function to split the formula into the tokens.
+fn tokenize(formula: &str) -> Result<Vec<Token>, ExcelizeError> {
+    let text = formula.trim();
+    let chars: Vec<char> = text.strip_prefix('=').unwrap_or(text).chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '\r' | '\n' => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '+' | '-' | '*' | '/' | '^' | '&' | '%' | '=' => {
+                tokens.push(Token::Op(c.to_string()));
+                i += 1;
+            }
+            '<' | '>' => {
+                let op = match chars.get(i + 1) {
+                    Some('=') => format!("{}=", c),
+                    Some('>') if c == '<' => String::from("<>"),
+                    _ => c.to_string(),
+                };
+                i += op.len();
+                tokens.push(Token::Op(op));
+            }
+            '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('"') if chars.get(i + 1) == Some(&'"') => {
+                            s.push('"');
+                            i += 2;
+                        }
+                        Some('"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(c) => {
+                            s.push(*c);
+                            i += 1;
+                        }
+                        None => return Err(invalid_formula(formula)),
+                    }
+                }
+                tokens.push(Token::Text(s));
+            }
+            '#' => {
+                let rest: String = chars[i..].iter().collect::<String>().to_uppercase();
+                match FORMULA_ERRORS.iter().find(|e| rest.starts_with(*e)) {
+                    Some(e) => {
+                        tokens.push(Token::Error(String::from(*e)));
+                        i += e.len();
+                    }
+                    None => return Err(invalid_formula(formula)),
+                }
+            }
+            '\'' => {
+                let mut sheet = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('\'') if chars.get(i + 1) == Some(&'\'') => {
+                            sheet.push('\'');
+                            i += 2;
+                        }
+                        Some('\'') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(c) => {
+                            sheet.push(*c);
+                            i += 1;
+                        }
+                        None => return Err(invalid_formula(formula)),
+                    }
+                }
+                if chars.get(i) != Some(&'!') {
+                    return Err(invalid_formula(formula));
+                }
+                i += 1;
+                let area = read_area(&chars, &mut i).ok_or_else(|| invalid_formula(formula))?;
+                tokens.push(Token::Ref(Some(sheet), area));
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                if chars.get(i) == Some(&':') && !chars[start..i].contains(&'.') {
+                    i = start;
+                    let area = read_area(&chars, &mut i).ok_or_else(|| invalid_formula(formula))?;
+                    tokens.push(Token::Ref(None, area));
+                    continue;
+                }
+                if matches!(chars.get(i), Some('e') | Some('E')) {
+                    let mut j = i + 1;
+                    if matches!(chars.get(j), Some('+') | Some('-')) {
+                        j += 1;
+                    }
+                    if chars.get(j).map_or(false, char::is_ascii_digit) {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let number: String = chars[start..i].iter().collect();
+                match number.parse::<f64>() {
+                    Ok(n) if n.is_finite() => tokens.push(Token::Number(n)),
+                    _ => return Err(invalid_formula(formula)),
+                }
+            }
+            c if is_name_char(c) => {
+                let start = i;
+                let word = read_word(&chars, &mut i);
+                match chars.get(i) {
+                    Some('!') => {
+                        i += 1;
+                        let area =
+                            read_area(&chars, &mut i).ok_or_else(|| invalid_formula(formula))?;
+                        tokens.push(Token::Ref(Some(word), area));
+                    }
+                    Some('(') => {
+                        let name = word.to_uppercase();
+                        let name = name.strip_prefix("_XLFN.").unwrap_or(&name);
+                        tokens.push(Token::Func(String::from(name)));
+                    }
+                    _ => {
+                        i = start;
+                        match read_area(&chars, &mut i) {
+                            Some(area) => tokens.push(Token::Ref(None, area)),
+                            None => {
+                                i = start + word.chars().count();
+                                tokens.push(match word.to_uppercase().as_str() {
+                                    "TRUE" => Token::Bool(true),
+                                    "FALSE" => Token::Bool(false),
+                                    _ => Token::Name(word),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            _ => return Err(invalid_formula(formula)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '$' || c == '\\'
+}
+
+fn read_word(chars: &[char], i: &mut usize) -> String {
+    let start = *i;
+    while *i < chars.len() && is_name_char(chars[*i]) {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect()
+}
+
+// read_area provides a function to read the cell, range, column range or row
+// range reference at the position, the position will be moved to the end of
+// the reference if it's valid.
+fn read_area(chars: &[char], i: &mut usize) -> Option<[u32; 4]> {
+    let start = *i;
+    let first = read_word(chars, i);
+    let area = match chars.get(*i) {
+        Some(':') => {
+            *i += 1;
+            let last = read_word(chars, i);
+            match (parse_ref_part(&first)?, parse_ref_part(&last)?) {
+                ((Some(x1), Some(y1)), (Some(x2), Some(y2))) => Some([x1, y1, x2, y2]),
+                ((Some(x1), None), (Some(x2), None)) => Some([x1, 1, x2, TOTAL_ROWS]),
+                ((None, Some(y1)), (None, Some(y2))) => Some([1, y1, TOTAL_COLUMNS, y2]),
+                _ => None,
+            }
+        }
+        _ => match parse_ref_part(&first)? {
+            (Some(x), Some(y)) => Some([x, y, x, y]),
+            _ => None,
+        },
+    };
+    match area {
+        Some(a) => Some([
+            a[0].min(a[2]),
+            a[1].min(a[3]),
+            a[0].max(a[2]),
+            a[1].max(a[3]),
+        ]),
+        None => {
+            *i = start;
+            None
+        }
+    }
+}
+
+// parse_ref_part provides a function to parse the part of the reference to
+// the column and row number, such as "$A$1", "A" or "1".
+fn parse_ref_part(part: &str) -> Option<(Option<u32>, Option<u32>)> {
+    let part = part.replace('$', "");
+    if part.is_empty() {
+        return None;
+    }
+    if part.chars().all(|c| c.is_ascii_alphabetic()) {
+        return column_name_to_number(&part)
+            .ok()
+            .filter(|c| *c <= TOTAL_COLUMNS)
+            .map(|c| (Some(c), None));
+    }
+    if part.chars().all(|c| c.is_ascii_digit()) {
+        return part
+            .parse::<u32>()
+            .ok()
+            .filter(|r| (1..=TOTAL_ROWS).contains(r))
+            .map(|r| (None, Some(r)));
+    }
+    cell_name_to_coordinates(&part)
+        .ok()
+        .map(|(c, r)| (Some(c), Some(r)))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next_op(&mut self, ops: &[&str]) -> Option<String> {
+        match self.peek() {
+            Some(Token::Op(op)) if ops.contains(&op.as_str()) => {
+                let op = op.clone();
+                self.pos += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn binary<F>(&mut self, ops: &[&str], mut operand: F) -> Result<Expr, ExcelizeError>
+    where
+        F: FnMut(&mut Self) -> Result<Expr, ExcelizeError>,
+    {
+        let mut left = operand(self)?;
+        while let Some(op) = self.next_op(ops) {
+            let right = operand(self)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, ExcelizeError> {
+        self.binary(&["=", "<>", "<", ">", "<=", ">="], Self::concat)
+    }
+
+    fn concat(&mut self) -> Result<Expr, ExcelizeError> {
+        self.binary(&["&"], Self::additive)
+    }
+
+    fn additive(&mut self) -> Result<Expr, ExcelizeError> {
+        self.binary(&["+", "-"], Self::term)
+    }
+
+    fn term(&mut self) -> Result<Expr, ExcelizeError> {
+        self.binary(&["*", "/"], Self::power)
+    }
+
+    fn power(&mut self) -> Result<Expr, ExcelizeError> {
+        self.binary(&["^"], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExcelizeError> {
+        match self.next_op(&["+", "-"]) {
+            Some(op) if op == "-" => Ok(Expr::Negate(Box::new(self.unary()?))),
+            Some(_) => self.unary(),
+            None => {
+                let mut expr = self.primary()?;
+                while self.next_op(&["%"]).is_some() {
+                    expr = Expr::Percent(Box::new(expr));
+                }
+                Ok(expr)
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExcelizeError> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        Ok(match token {
+            Some(Token::Number(n)) => Expr::Value(FormulaValue::Number(n)),
+            Some(Token::Text(s)) => Expr::Value(FormulaValue::Text(s)),
+            Some(Token::Bool(b)) => Expr::Value(FormulaValue::Bool(b)),
+            Some(Token::Error(e)) => Expr::Value(FormulaValue::Error(e)),
+            Some(Token::Ref(sheet, area)) => Expr::Ref(sheet, area),
+            Some(Token::Name(name)) => Expr::Name(name),
+            Some(Token::LParen) => {
+                let expr = self.comparison()?;
+                self.expect(Token::RParen)?;
+                expr
+            }
+            Some(Token::Func(name)) => {
+                self.expect(Token::LParen)?;
+                let mut args = Vec::new();
+                if self.peek() == Some(&Token::RParen) {
+                    self.pos += 1;
+                    return Ok(Expr::Func(name, args));
+                }
+                loop {
+                    match self.peek() {
+                        Some(Token::Comma) | Some(Token::RParen) => args.push(Expr::Empty),
+                        _ => args.push(self.comparison()?),
+                    }
+                    match self.peek() {
+                        Some(Token::Comma) => self.pos += 1,
+                        _ => break,
+                    }
+                }
+                self.expect(Token::RParen)?;
+                Expr::Func(name, args)
+            }
+            _ => {
+                return Err(ExcelizeError::CommonError(String::from(
+                    "invalid formula syntax",
+                )))
+            }
+        })
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), ExcelizeError> {
+        if self.peek() != Some(&token) {
+            return Err(ExcelizeError::CommonError(String::from(
+                "invalid formula syntax",
+            )));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+}
+
+struct Context<'a> {
+    spreadsheet: &'a Spreadsheet,
+    sheet: String,
+    depth: usize,
+}
+
+impl<'a> Context<'a> {
+    fn eval(&self, expr: &Expr) -> Result<FormulaValue, String> {
+        match expr {
+            Expr::Empty => Ok(FormulaValue::Blank),
+            Expr::Value(v) => Ok(v.clone()),
+            Expr::Ref(sheet, area) => {
+                let sheet = sheet.as_deref().unwrap_or(&self.sheet);
+                let ws = self
+                    .spreadsheet
+                    .get_worksheet(sheet)
+                    .map_err(|_| String::from("#REF!"))?;
+                match area[0] == area[2] && area[1] == area[3] {
+                    true => Ok(get_cell_formula_value(
+                        self.spreadsheet,
+                        ws,
+                        area[0],
+                        area[1],
+                    )),
+                    false => Ok(get_range_formula_value(self.spreadsheet, ws, area)),
+                }
+            }
+            Expr::Name(name) => self.eval_defined_name(name),
+            Expr::Negate(e) => Ok(FormulaValue::Number(-self.scalar(e)?.to_number()?)),
+            Expr::Percent(e) => Ok(FormulaValue::Number(self.scalar(e)?.to_number()? / 100.0)),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (self.scalar(left)?, self.scalar(right)?);
+                if let FormulaValue::Error(e) = left {
+                    return Err(e);
+                }
+                if let FormulaValue::Error(e) = right {
+                    return Err(e);
+                }
+                self.eval_binary(op, &left, &right)
+            }
+            Expr::Func(name, args) => self.call(name, args),
+        }
+    }
+
+    fn scalar(&self, expr: &Expr) -> Result<FormulaValue, String> {
+        Ok(self.eval(expr)?.first())
+    }
+
+    fn eval_defined_name(&self, name: &str) -> Result<FormulaValue, String> {
+        if self.depth >= MAX_NAME_DEPTH {
+            return Err(String::from("#NAME?"));
+        }
+        let index = self.spreadsheet.get_sheet_index(&self.sheet).ok();
+        let defined_names = self
+            .spreadsheet
+            .workbook
+            .as_ref()
+            .and_then(|wb| wb.defined_names.as_ref())
+            .map(|names| names.defined_name.as_slice())
+            .unwrap_or_default();
+        let defined_name = defined_names
+            .iter()
+            .filter(|n| n.name.eq_ignore_ascii_case(name))
+            .find(|n| n.local_sheet_id.is_some() && n.local_sheet_id.map(|i| i as usize) == index)
+            .or_else(|| {
+                defined_names
+                    .iter()
+                    .find(|n| n.name.eq_ignore_ascii_case(name) && n.local_sheet_id.is_none())
+            })
+            .ok_or_else(|| String::from("#NAME?"))?;
+        let expr = parse_formula(&defined_name.data).map_err(|_| String::from("#NAME?"))?;
+        let ctx = Context {
+            spreadsheet: self.spreadsheet,
+            sheet: self.sheet.clone(),
+            depth: self.depth + 1,
+        };
+        ctx.eval(&expr)
+    }
+
+    fn eval_binary(
+        &self,
+        op: &str,
+        left: &FormulaValue,
+        right: &FormulaValue,
+    ) -> Result<FormulaValue, String> {
+        let number = |f: fn(f64, f64) -> f64| -> Result<FormulaValue, String> {
+            let n = f(left.to_number()?, right.to_number()?);
+            match n.is_finite() {
+                true => Ok(FormulaValue::Number(n)),
+                false => Err(String::from("#NUM!")),
+            }
+        };
+        let ordering = || compare_formula_values(left, right);
+        Ok(match op {
+            "+" => number(|a, b| a + b)?,
+            "-" => number(|a, b| a - b)?,
+            "*" => number(|a, b| a * b)?,
+            "/" => {
+                if right.to_number()? == 0.0 {
+                    return Err(String::from("#DIV/0!"));
+                }
+                number(|a, b| a / b)?
+            }
+            "^" => number(f64::powf)?,
+            "&" => FormulaValue::Text(format!("{}{}", left.to_text()?, right.to_text()?)),
+            "=" => FormulaValue::Bool(ordering() == Ordering::Equal),
+            "<>" => FormulaValue::Bool(ordering() != Ordering::Equal),
+            "<" => FormulaValue::Bool(ordering() == Ordering::Less),
+            ">" => FormulaValue::Bool(ordering() == Ordering::Greater),
+            "<=" => FormulaValue::Bool(ordering() != Ordering::Greater),
+            ">=" => FormulaValue::Bool(ordering() != Ordering::Less),
+            _ => return Err(String::from("#VALUE!")),
+        })
+    }
+
+    // numbers provides a function to get the numbers of the arguments for the
+    // statistical functions, the text, booleans and blank cells in the ranges
+    // are ignored.
+    fn numbers(&self, args: &[Expr]) -> Result<Vec<f64>, String> {
+        let mut numbers = Vec::new();
+        for arg in args {
+            match self.eval(arg)? {
+                FormulaValue::Array(rows) => {
+                    for value in rows.into_iter().flatten() {
+                        match value {
+                            FormulaValue::Number(n) => numbers.push(n),
+                            FormulaValue::Error(e) => return Err(e),
+                            _ => {}
+                        }
+                    }
+                }
+                value => numbers.push(value.to_number()?),
+            }
+        }
+        Ok(numbers)
+    }
+
+    // booleans provides a function to get the booleans of the arguments for
+    // the logical functions, the text and blank cells in the ranges are
+    // ignored.
+    fn booleans(&self, args: &[Expr]) -> Result<Vec<bool>, String> {
+        let mut booleans = Vec::new();
+        for arg in args {
+            match self.eval(arg)? {
+                FormulaValue::Array(rows) => {
+                    for value in rows.into_iter().flatten() {
+                        match value {
+                            FormulaValue::Number(_) | FormulaValue::Bool(_) => {
+                                booleans.push(value.to_bool()?)
+                            }
+                            FormulaValue::Error(e) => return Err(e),
+                            _ => {}
+                        }
+                    }
+                }
+                value => booleans.push(value.to_bool()?),
+            }
+        }
+        match booleans.is_empty() {
+            true => Err(String::from("#VALUE!")),
+            false => Ok(booleans),
+        }
+    }
+
+    fn arg(&self, args: &[Expr], i: usize) -> Result<FormulaValue, String> {
+        match args.get(i) {
+            Some(arg) => self.scalar(arg),
+            None => Err(String::from("#VALUE!")),
+        }
+    }
+
+    fn number_arg(&self, args: &[Expr], i: usize, default: Option<f64>) -> Result<f64, String> {
+        match (args.get(i), default) {
+            (None, Some(n)) | (Some(Expr::Empty), Some(n)) => Ok(n),
+            _ => self.arg(args, i)?.to_number(),
+        }
+    }
+
+    fn text_arg(&self, args: &[Expr], i: usize) -> Result<String, String> {
+        self.arg(args, i)?.to_text()
+    }
+
+    fn check_args(args: &[Expr], min: usize, max: usize) -> Result<(), String> {
+        match (min..=max).contains(&args.len()) {
+            true => Ok(()),
+            false => Err(String::from("#VALUE!")),
+        }
+    }
+
+    fn call(&self, name: &str, args: &[Expr]) -> Result<FormulaValue, String> {
+        let number = |n: f64| match n.is_finite() {
+            true => Ok(FormulaValue::Number(n)),
+            false => Err(String::from("#NUM!")),
+        };
+        match name {
+            "ABS" => {
+                Self::check_args(args, 1, 1)?;
+                number(self.number_arg(args, 0, None)?.abs())
+            }
+            "AND" => Ok(FormulaValue::Bool(self.booleans(args)?.iter().all(|b| *b))),
+            "AVERAGE" => {
+                let numbers = self.numbers(args)?;
+                if numbers.is_empty() {
+                    return Err(String::from("#DIV/0!"));
+                }
+                number(numbers.iter().sum::<f64>() / numbers.len() as f64)
+            }
+            "CONCATENATE" | "CONCAT" => {
+                let mut s = String::new();
+                for arg in args {
+                    for value in self.eval(arg)?.values() {
+                        s.push_str(&value.to_text()?);
+                    }
+                }
+                Ok(FormulaValue::Text(s))
+            }
+            "COUNT" => {
+                let mut count = 0;
+                for arg in args {
+                    count += match self.eval(arg)? {
+                        FormulaValue::Array(rows) => rows
+                            .iter()
+                            .flatten()
+                            .filter(|v| matches!(v, FormulaValue::Number(_)))
+                            .count(),
+                        value => usize::from(value.to_number().is_ok()),
+                    };
+                }
+                Ok(FormulaValue::Number(count as f64))
+            }
+            "COUNTA" => {
+                let mut count = 0;
+                for arg in args {
+                    count += self
+                        .eval(arg)?
+                        .values()
+                        .iter()
+                        .filter(|v| **v != FormulaValue::Blank)
+                        .count();
+                }
+                Ok(FormulaValue::Number(count as f64))
+            }
+            "COUNTBLANK" => {
+                Self::check_args(args, 1, 1)?;
+                let count = self
+                    .eval(&args[0])?
+                    .values()
+                    .iter()
+                    .filter(|v| match v {
+                        FormulaValue::Blank => true,
+                        FormulaValue::Text(s) => s.is_empty(),
+                        _ => false,
+                    })
+                    .count();
+                Ok(FormulaValue::Number(count as f64))
+            }
+            "COUNTIF" => {
+                Self::check_args(args, 2, 2)?;
+                let criteria = self.arg(args, 1)?;
+                let count = self
+                    .eval(&args[0])?
+                    .values()
+                    .iter()
+                    .filter(|v| match_criteria(v, &criteria))
+                    .count();
+                Ok(FormulaValue::Number(count as f64))
+            }
+            "DATE" => {
+                Self::check_args(args, 3, 3)?;
+                let mut year = self.number_arg(args, 0, None)?.trunc() as i64;
+                if (0..1900).contains(&year) {
+                    year += 1900;
+                }
+                let month = self.number_arg(args, 1, None)?.trunc() as i64;
+                let day = self.number_arg(args, 2, None)?.trunc();
+                // the months out of range are carried to the year, which should
+                // be in the range from 0 to 9999 as well
+                let months = (0..=9999)
+                    .contains(&year)
+                    .then(|| month.checked_add(year * 12))
+                    .flatten();
+                if !months.map_or(false, |months| months > 0 && months <= 120000) {
+                    return Err(String::from("#NUM!"));
+                }
+                let serial = month_serial(year, month) + day - 1.0;
+                match (0.0..=MAX_DATE_SERIAL).contains(&serial) {
+                    true => Ok(FormulaValue::Number(serial)),
+                    false => Err(String::from("#NUM!")),
+                }
+            }
+            "DAY" | "MONTH" | "YEAR" => {
+                Self::check_args(args, 1, 1)?;
+                let datetime = serial_to_datetime(self.number_arg(args, 0, None)?)
+                    .ok_or_else(|| String::from("#NUM!"))?;
+                Ok(FormulaValue::Number(match name {
+                    "YEAR" => datetime[0],
+                    "MONTH" => datetime[1],
+                    _ => datetime[2],
+                } as f64))
+            }
+            "EXACT" => {
+                Self::check_args(args, 2, 2)?;
+                Ok(FormulaValue::Bool(
+                    self.text_arg(args, 0)? == self.text_arg(args, 1)?,
+                ))
+            }
+            "FALSE" => Ok(FormulaValue::Bool(false)),
+            "FIND" | "SEARCH" => {
+                Self::check_args(args, 2, 3)?;
+                let (mut find, mut within) = (self.text_arg(args, 0)?, self.text_arg(args, 1)?);
+                if name == "SEARCH" {
+                    find = find.to_lowercase();
+                    within = within.to_lowercase();
+                }
+                let start = self.number_arg(args, 2, Some(1.0))?;
+                let chars: Vec<char> = within.chars().collect();
+                if !start.is_finite() || start < 1.0 || start.trunc() > (chars.len() + 1) as f64 {
+                    return Err(String::from("#VALUE!"));
+                }
+                let start = start.trunc() as usize;
+                let rest: String = chars[start - 1..].iter().collect();
+                match rest.find(&find) {
+                    Some(i) => Ok(FormulaValue::Number(
+                        (start + rest[..i].chars().count()) as f64,
+                    )),
+                    None => Err(String::from("#VALUE!")),
+                }
+            }
+            "IF" => {
+                Self::check_args(args, 2, 3)?;
+                match self.arg(args, 0)?.to_bool()? {
+                    true => self.eval(&args[1]),
+                    false => match args.get(2) {
+                        Some(arg) => self.eval(arg),
+                        None => Ok(FormulaValue::Bool(false)),
+                    },
+                }
+            }
+            "IFERROR" => {
+                Self::check_args(args, 2, 2)?;
+                match self.scalar(&args[0]) {
+                    Ok(FormulaValue::Error(_)) | Err(_) => self.eval(&args[1]),
+                    value => value,
+                }
+            }
+            "INT" => {
+                Self::check_args(args, 1, 1)?;
+                number(self.number_arg(args, 0, None)?.floor())
+            }
+            "ISBLANK" | "ISERROR" | "ISLOGICAL" | "ISNUMBER" | "ISTEXT" => {
+                Self::check_args(args, 1, 1)?;
+                let value = match self.scalar(&args[0]) {
+                    Ok(value) => value,
+                    Err(e) => FormulaValue::Error(e),
+                };
+                Ok(FormulaValue::Bool(match name {
+                    "ISBLANK" => value == FormulaValue::Blank,
+                    "ISERROR" => matches!(value, FormulaValue::Error(_)),
+                    "ISLOGICAL" => matches!(value, FormulaValue::Bool(_)),
+                    "ISNUMBER" => matches!(value, FormulaValue::Number(_)),
+                    _ => matches!(value, FormulaValue::Text(_)),
+                }))
+            }
+            "LEFT" | "RIGHT" => {
+                Self::check_args(args, 1, 2)?;
+                let chars: Vec<char> = self.text_arg(args, 0)?.chars().collect();
+                let n = self.number_arg(args, 1, Some(1.0))?;
+                if !n.is_finite() || n < 0.0 {
+                    return Err(String::from("#VALUE!"));
+                }
+                let n = (n.trunc() as usize).min(chars.len());
+                Ok(FormulaValue::Text(match name {
+                    "LEFT" => chars[..n].iter().collect(),
+                    _ => chars[chars.len() - n..].iter().collect(),
+                }))
+            }
+            "LEN" => {
+                Self::check_args(args, 1, 1)?;
+                Ok(FormulaValue::Number(
+                    self.text_arg(args, 0)?.chars().count() as f64,
+                ))
+            }
+            "LOWER" => {
+                Self::check_args(args, 1, 1)?;
+                Ok(FormulaValue::Text(self.text_arg(args, 0)?.to_lowercase()))
+            }
+            "MAX" => {
+                let numbers = self.numbers(args)?;
+                Ok(FormulaValue::Number(
+                    numbers.into_iter().reduce(f64::max).unwrap_or(0.0),
+                ))
+            }
+            "MID" => {
+                Self::check_args(args, 3, 3)?;
+                let chars: Vec<char> = self.text_arg(args, 0)?.chars().collect();
+                let (start, n) = (
+                    self.number_arg(args, 1, None)?,
+                    self.number_arg(args, 2, None)?,
+                );
+                if !start.is_finite() || !n.is_finite() || start < 1.0 || n < 0.0 {
+                    return Err(String::from("#VALUE!"));
+                }
+                Ok(FormulaValue::Text(
+                    chars
+                        .iter()
+                        .skip(start.trunc() as usize - 1)
+                        .take(n.trunc() as usize)
+                        .collect(),
+                ))
+            }
+            "MIN" => {
+                let numbers = self.numbers(args)?;
+                Ok(FormulaValue::Number(
+                    numbers.into_iter().reduce(f64::min).unwrap_or(0.0),
+                ))
+            }
+            "MOD" => {
+                Self::check_args(args, 2, 2)?;
+                let (n, d) = (
+                    self.number_arg(args, 0, None)?,
+                    self.number_arg(args, 1, None)?,
+                );
+                if d == 0.0 {
+                    return Err(String::from("#DIV/0!"));
+                }
+                number(n - d * (n / d).floor())
+            }
+            "NOT" => {
+                Self::check_args(args, 1, 1)?;
+                Ok(FormulaValue::Bool(!self.arg(args, 0)?.to_bool()?))
+            }
+            "NOW" => {
+                let secs = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0.0, |d| d.as_secs_f64());
+                Ok(FormulaValue::Number(secs / 86400.0 + 25569.0))
+            }
+            "OR" => Ok(FormulaValue::Bool(self.booleans(args)?.iter().any(|b| *b))),
+            "ROUND" => {
+                Self::check_args(args, 1, 2)?;
+                let n = self.number_arg(args, 0, None)?;
+                let digits = 10f64.powi(self.number_arg(args, 1, Some(0.0))?.trunc() as i32);
+                number((n * digits).round() / digits)
+            }
+            "SQRT" => {
+                Self::check_args(args, 1, 1)?;
+                let n = self.number_arg(args, 0, None)?;
+                if n < 0.0 {
+                    return Err(String::from("#NUM!"));
+                }
+                number(n.sqrt())
+            }
+            "SUM" => number(self.numbers(args)?.iter().sum()),
+            "SUMIF" => {
+                Self::check_args(args, 2, 3)?;
+                let range = self.eval(&args[0])?.values();
+                let criteria = self.arg(args, 1)?;
+                let sum_range = match args.get(2) {
+                    Some(arg) => self.eval(arg)?.values(),
+                    None => range.clone(),
+                };
+                let mut sum = 0.0;
+                for (value, n) in range.iter().zip(sum_range) {
+                    if match_criteria(value, &criteria) {
+                        if let FormulaValue::Number(n) = n {
+                            sum += n;
+                        }
+                    }
+                }
+                number(sum)
+            }
+            "TODAY" => Ok(FormulaValue::Number(today_serial())),
+            "TRIM" => {
+                Self::check_args(args, 1, 1)?;
+                Ok(FormulaValue::Text(
+                    self.text_arg(args, 0)?
+                        .split(' ')
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<&str>>()
+                        .join(" "),
+                ))
+            }
+            "TRUE" => Ok(FormulaValue::Bool(true)),
+            "UPPER" => {
+                Self::check_args(args, 1, 1)?;
+                Ok(FormulaValue::Text(self.text_arg(args, 0)?.to_uppercase()))
+            }
+            "VALUE" => {
+                Self::check_args(args, 1, 1)?;
+                match self.arg(args, 0)? {
+                    FormulaValue::Bool(_) => Err(String::from("#VALUE!")),
+                    value => Ok(FormulaValue::Number(value.to_number()?)),
+                }
+            }
+            "WEEKDAY" => {
+                Self::check_args(args, 1, 2)?;
+                let serial = self.number_arg(args, 0, None)?.floor();
+                if serial < 0.0 {
+                    return Err(String::from("#NUM!"));
+                }
+                let sunday = (serial as i64 + 6).rem_euclid(7);
+                Ok(FormulaValue::Number(
+                    match self.number_arg(args, 1, Some(1.0))? as i64 {
+                        1 => sunday + 1,
+                        2 => (sunday + 6).rem_euclid(7) + 1,
+                        3 => (sunday + 6).rem_euclid(7),
+                        _ => return Err(String::from("#NUM!")),
+                    } as f64,
+                ))
+            }
+            _ => Err(String::from("#NAME?")),
+        }
+    }
+}
+
+// get_cell_formula_value provides a function to get the typed value of the
+// cell by given column and row number.
+pub(crate) fn get_cell_formula_value(
+    spreadsheet: &Spreadsheet,
+    ws: &XMLWorksheet,
+    col: u32,
+    row: u32,
+) -> FormulaValue {
+    let cell = get_row(ws, row).and_then(|r| {
+        r.c.iter()
+            .find(|c| cell_name_to_coordinates(&c.r).map_or(false, |(x, _)| x == col))
+    });
+    let cell = match cell {
+        Some(cell) => cell,
+        None => return FormulaValue::Blank,
+    };
+    let value = spreadsheet.get_value_from(cell);
+    match cell.t.as_deref() {
+        Some("s") | Some("str") | Some("inlineStr") => FormulaValue::Text(value),
+        _ if value.is_empty() => FormulaValue::Blank,
+        Some("b") => FormulaValue::Bool(value == "1"),
+        Some("e") => FormulaValue::Error(value),
+        _ => match value.parse::<f64>() {
+            Ok(n) if n.is_finite() => FormulaValue::Number(n),
+            _ => FormulaValue::Text(value),
+        },
+    }
+}
+
+// get_range_formula_value provides a function to get the array of the cell
+// values of the range, the range is clipped to the used area of the
+// worksheet.
+fn get_range_formula_value(
+    spreadsheet: &Spreadsheet,
+    ws: &XMLWorksheet,
+    area: &[u32; 4],
+) -> FormulaValue {
+    let mut cells = Vec::new();
+    let (mut max_col, mut max_row) = (0, 0);
+    for r in ws.sheet_data.row.iter().flatten() {
+        for c in &r.c {
+            if let Ok((col, row)) = cell_name_to_coordinates(&c.r) {
+                max_col = max_col.max(col);
+                max_row = max_row.max(row);
+                if (area[0]..=area[2]).contains(&col) && (area[1]..=area[3]).contains(&row) {
+                    cells.push((col, row));
+                }
+            }
+        }
+    }
+    let (x2, y2) = (area[2].min(max_col), area[3].min(max_row));
+    if x2 < area[0] || y2 < area[1] {
+        return FormulaValue::Array(Vec::new());
+    }
+    let mut rows =
+        vec![vec![FormulaValue::Blank; (x2 - area[0] + 1) as usize]; (y2 - area[1] + 1) as usize];
+    for (col, row) in cells {
+        rows[(row - area[1]) as usize][(col - area[0]) as usize] =
+            get_cell_formula_value(spreadsheet, ws, col, row);
+    }
+    FormulaValue::Array(rows)
+}
+
+// compare_formula_values provides a function to compare the values as the
+// comparison operators, the numbers are less than the text and the text are
+// less than the booleans, the text are compared case-insensitively.
+pub(crate) fn compare_formula_values(a: &FormulaValue, b: &FormulaValue) -> Ordering {
+    let blank = |other: &FormulaValue| match other {
+        FormulaValue::Text(_) => FormulaValue::Text(String::new()),
+        FormulaValue::Bool(_) => FormulaValue::Bool(false),
+        _ => FormulaValue::Number(0.0),
+    };
+    let a = match a {
+        FormulaValue::Blank => blank(b),
+        a => a.clone(),
+    };
+    let b = match b {
+        FormulaValue::Blank => blank(&a),
+        b => b.clone(),
+    };
+    let rank = |v: &FormulaValue| match v {
+        FormulaValue::Number(_) => 0,
+        FormulaValue::Text(_) => 1,
+        FormulaValue::Bool(_) => 2,
+        _ => 3,
+    };
+    match (&a, &b) {
+        (FormulaValue::Number(x), FormulaValue::Number(y)) => {
+            x.partial_cmp(y).unwrap_or(Ordering::Equal)
+        }
+        (FormulaValue::Text(x), FormulaValue::Text(y)) => x.to_lowercase().cmp(&y.to_lowercase()),
+        (FormulaValue::Bool(x), FormulaValue::Bool(y)) => x.cmp(y),
+        _ => rank(&a).cmp(&rank(&b)),
+    }
+}
+
+// match_criteria provides a function to check if the value matches the
+// criteria of the COUNTIF and SUMIF functions, such as 5, ">=10", "<>" and
+// "app*".
+fn match_criteria(value: &FormulaValue, criteria: &FormulaValue) -> bool {
+    let text = match criteria {
+        FormulaValue::Text(text) => text,
+        FormulaValue::Blank => return false,
+        criteria => {
+            return compare_formula_values(value, criteria) == Ordering::Equal
+                && std::mem::discriminant(value) == std::mem::discriminant(criteria)
+        }
+    };
+    let (op, operand) = ["<=", ">=", "<>", "<", ">", "="]
+        .iter()
+        .find_map(|op| text.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("=", text.as_str()));
+    if let Ok(n) = operand.trim().parse::<f64>() {
+        let ordering = match value {
+            FormulaValue::Number(v) => v.partial_cmp(&n),
+            FormulaValue::Text(s) if op == "=" || op == "<>" => {
+                s.trim().parse::<f64>().ok().and_then(|v| v.partial_cmp(&n))
+            }
+            _ => None,
+        };
+        return match ordering {
+            Some(ordering) => match op {
+                "=" => ordering == Ordering::Equal,
+                "<>" => ordering != Ordering::Equal,
+                "<" => ordering == Ordering::Less,
+                ">" => ordering == Ordering::Greater,
+                "<=" => ordering != Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            },
+            None => op == "<>",
+        };
+    }
+    let value = match value {
+        FormulaValue::Blank => String::new(),
+        FormulaValue::Text(s) => s.clone(),
+        FormulaValue::Bool(b) => String::from(if *b { "TRUE" } else { "FALSE" }),
+        _ => return op == "<>",
+    };
+    let matched = || wildcard_match(&operand.to_lowercase(), &value.to_lowercase());
+    match op {
+        "=" => matched(),
+        "<>" => !matched(),
+        _ => {
+            let ordering = value.to_lowercase().cmp(&operand.to_lowercase());
+            !value.is_empty()
+                && match op {
+                    "<" => ordering == Ordering::Less,
+                    ">" => ordering == Ordering::Greater,
+                    "<=" => ordering != Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                }
+        }
+    }
+}
+
+// number_to_text provides a function to format the number with up to 15
+// significant digits as the spreadsheet application does.
+pub(crate) fn number_to_text(n: f64) -> String {
+    let rounded: f64 = format!("{:.14e}", n).parse().unwrap_or(n);
+    format!("{}", rounded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calc_formula() {
+        let wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let calc = |formula: &str| calc_formula(&wb, "Sheet1", formula).unwrap();
+        assert_eq!(calc("=1+2*3^2-4/2"), FormulaValue::Number(17.0));
+        assert_eq!(calc("-2^2&\"x\""), FormulaValue::Text(String::from("4x")));
+        assert_eq!(calc("50%*(1+1)"), FormulaValue::Number(1.0));
+        assert_eq!(calc("0.1+0.2=0.3"), FormulaValue::Bool(false));
+        assert_eq!(calc("\"a\"<\"B\""), FormulaValue::Bool(true));
+        assert_eq!(calc("1/0"), FormulaValue::Error(String::from("#DIV/0!")));
+        assert_eq!(
+            calc("IFERROR(1/0,\"err\")"),
+            FormulaValue::Text(String::from("err"))
+        );
+        assert_eq!(
+            calc("AND(TRUE,OR(FALSE,1),NOT(0))"),
+            FormulaValue::Bool(true)
+        );
+        assert_eq!(
+            calc("IF(LEN(\"abc\")=3,UPPER(MID(\"excelize\",1,5)),\"no\")"),
+            FormulaValue::Text(String::from("EXCEL"))
+        );
+        assert_eq!(calc("DATE(2024,14,1)"), FormulaValue::Number(45689.0));
+        assert_eq!(calc("YEAR(45689)"), FormulaValue::Number(2025.0));
+        assert_eq!(calc("DATE(9999,12,31)"), FormulaValue::Number(2958465.0));
+        let num_error = FormulaValue::Error(String::from("#NUM!"));
+        assert_eq!(calc("DATE(10000,1,1)"), num_error);
+        assert_eq!(calc("DATE(9999,12,32)"), num_error);
+        assert_eq!(calc("DATE(1E+18,1,1)"), num_error);
+        assert_eq!(calc("DATE(2024,1E+300,1)"), num_error);
+        assert_eq!(calc("DATE(2024,1,1E+300)"), num_error);
+        assert_eq!(calc("YEAR(1E+300)"), num_error);
+        assert_eq!(calc("DAY(2958466)"), num_error);
+        assert_eq!(calc("WEEKDAY(45689)"), FormulaValue::Number(7.0));
+        // the texts of the non-finite numbers are not converted to numbers
+        let value_error = FormulaValue::Error(String::from("#VALUE!"));
+        assert_eq!(calc("MID(\"abc\",\"nan\",1)"), value_error);
+        assert_eq!(calc("MID(\"abc\",1,\"inf\")"), value_error);
+        assert_eq!(calc("LEFT(\"abc\",\"infinity\")"), value_error);
+        assert_eq!(calc("FIND(\"b\",\"abc\",\"NaN\")"), value_error);
+        assert_eq!(calc("VALUE(\"-inf\")"), value_error);
+        assert_eq!(
+            calc("MID(\"abc\",1E+300,1)"),
+            FormulaValue::Text(String::new())
+        );
+        assert_eq!(calc("ISBLANK(Z100)"), FormulaValue::Bool(true));
+        assert_eq!(calc("ISTEXT(A22)"), FormulaValue::Bool(true));
+        assert_eq!(calc("COUNTIF(A:A,\"git*\")"), FormulaValue::Number(1.0));
+        assert_eq!(calc("FOO(1)"), FormulaValue::Error(String::from("#NAME?")));
+        assert_eq!(
+            calc("Sheet3!A1"),
+            FormulaValue::Error(String::from("#REF!"))
+        );
+        assert!(calc_formula(&wb, "Sheet1", "SUM(1,").is_err());
+        assert!(calc_formula(&wb, "Sheet1", "MID(\"abc\",1E+400,1)").is_err());
+        assert_eq!(number_to_text(0.1 + 0.2), "0.3");
+    }
+}
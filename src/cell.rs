@@ -2,22 +2,78 @@
 // this source code is governed by a BSD-style license that can be found in
 // the LICENSE file.
 
-use crate::{column_number_to_name, CTCell, ExcelizeError, Spreadsheet};
+use crate::{
+    cell_name_to_coordinates, column_number_to_name, coordinates_to_cell_name, get_rels_path,
+    prepare_row, range_ref_to_coordinates, set_shared_string, CTCell, CTHyperlink, CTHyperlinks,
+    ExcelizeError, Rels, Spreadsheet, Worksheet, SOURCE_RELATIONSHIP_HYPERLINK, TOTAL_CELL_CHARS,
+    TOTAL_SHEET_HYPERLINKS,
+};
+
+/// Hyperlink directly maps the settings of a cell hyperlink. The link type is
+/// "External" for the link to the external resource such as URL, and
+/// "Location" for the link to the location in the workbook, such as
+/// Sheet1!A1 or a defined name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hyperlink {
+    pub link_type: String,
+    pub link: String,
+    pub tooltip: Option<String>,
+    pub display: Option<String>,
+}
+
 pub trait Cell {
     /// GetCellValue provides a function to get formatted value from cell by given
     /// worksheet name and axis in spreadsheet.
     fn get_cell_value(&self, sheet: &str, row: u32, col: u32) -> Result<String, ExcelizeError>;
     fn get_value_from(&self, cell: &CTCell) -> String;
+    /// set_cell_hyperlink provides a function to set the cell hyperlink by
+    /// given worksheet name, cell reference, link, link type, tooltip and
+    /// display text. The link type should be "External" for the URL, which is
+    /// stored in the worksheet relationships, or "Location" for the location
+    /// in the workbook. The existing hyperlink of the cell will be replaced,
+    /// when the cell is in the range of a hyperlink, the link of the whole
+    /// range will be replaced. The maximum number of hyperlinks in a worksheet
+    /// is 65529. For
+    /// example, add a link to the external URL for the cell A3 on Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.set_cell_hyperlink(
+    ///     "Sheet1",
+    ///     "A3",
+    ///     "https://github.com/xuri/excelize",
+    ///     "External",
+    ///     Some("excelize"),
+    ///     None,
+    /// )
+    /// ```
+    fn set_cell_hyperlink(
+        &mut self,
+        sheet: &str,
+        cell: &str,
+        link: &str,
+        link_type: &str,
+        tooltip: Option<&str>,
+        display: Option<&str>,
+    ) -> Result<(), ExcelizeError>;
+    /// set_cell_str provides a function to set the string value of the cell
+    /// by given worksheet name, cell reference and value. The value is stored
+    /// in the shared strings table, and the characters over the limit 32767
+    /// will be truncated. The formula of the cell will be removed.
+    fn set_cell_str(&mut self, sheet: &str, cell: &str, value: &str) -> Result<(), ExcelizeError>;
+    /// get_cell_hyperlink provides a function to get the hyperlink of the
+    /// cell by given worksheet name and cell reference, returns None if the
+    /// cell doesn't have a hyperlink.
+    fn get_cell_hyperlink(
+        &self,
+        sheet: &str,
+        cell: &str,
+    ) -> Result<Option<Hyperlink>, ExcelizeError>;
 }
 
 impl Cell for Spreadsheet {
     fn get_cell_value(&self, sheet: &str, row: u32, col: u32) -> Result<String, ExcelizeError> {
         let empty = String::from("");
-        let column_title;
-        match column_number_to_name(col) {
-            Ok(c) => column_title = c,
-            Err(e) => return Err(e),
-        }
+        let column_title = column_number_to_name(col)?;
         let worksheet = self.worksheets.get_key_value(sheet);
         match worksheet {
             Some(ws) => match &ws.1.sheet_data.row {
@@ -27,8 +83,8 @@ impl Cell for Spreadsheet {
                             Some(rn) => {
                                 if rn == row {
                                     for c in &r.c {
-                                        if c.r == format!("{}{}", column_title, row.to_string()) {
-                                            return Ok(self.get_value_from(&c));
+                                        if c.r == format!("{}{}", column_title, row) {
+                                            return Ok(self.get_value_from(c));
                                         }
                                     }
                                 }
@@ -100,4 +156,288 @@ impl Cell for Spreadsheet {
             },
         }
     }
+
+    fn set_cell_hyperlink(
+        &mut self,
+        sheet: &str,
+        cell: &str,
+        link: &str,
+        link_type: &str,
+        tooltip: Option<&str>,
+        display: Option<&str>,
+    ) -> Result<(), ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        let cell = coordinates_to_cell_name(col, row)?;
+        if link_type != "External" && link_type != "Location" {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid link type {}",
+                link_type
+            )));
+        }
+        let rels_path = get_rels_path(&self.get_sheet_xml_path(sheet)?);
+        let ws = self.get_worksheet(sheet)?;
+        let hyperlinks = ws.hyperlinks.as_ref().map_or(&[][..], |h| &h.hyperlink[..]);
+        let idx = hyperlinks
+            .iter()
+            .position(|h| h.ref_attr == cell)
+            .or_else(|| {
+                hyperlinks
+                    .iter()
+                    .position(|h| hyperlink_contains(&h.ref_attr, col, row))
+            });
+        let (cell, rid) = match idx {
+            Some(idx) => (
+                hyperlinks[idx].ref_attr.clone(),
+                hyperlinks[idx].rid.clone(),
+            ),
+            None if hyperlinks.len() >= TOTAL_SHEET_HYPERLINKS => {
+                return Err(ExcelizeError::CommonError(String::from(
+                    "over maximum limit hyperlinks in a worksheet",
+                )))
+            }
+            None => (cell, None),
+        };
+        if let Some(rid) = rid {
+            self.delete_rels(&rels_path, &rid);
+        }
+        let mut hyperlink = CTHyperlink {
+            ref_attr: cell,
+            rid: None,
+            location: None,
+            tooltip: tooltip.map(String::from),
+            display: display.map(String::from),
+        };
+        match link_type {
+            "External" => {
+                hyperlink.rid = Some(self.add_rels(
+                    &rels_path,
+                    SOURCE_RELATIONSHIP_HYPERLINK,
+                    link,
+                    Some("External"),
+                ))
+            }
+            _ => hyperlink.location = Some(String::from(link)),
+        }
+        let hyperlinks = &mut self
+            .get_worksheet_mut(sheet)?
+            .hyperlinks
+            .get_or_insert_with(|| CTHyperlinks {
+                hyperlink: Vec::new(),
+            })
+            .hyperlink;
+        match idx {
+            Some(idx) => hyperlinks[idx] = hyperlink,
+            None => hyperlinks.push(hyperlink),
+        }
+        Ok(())
+    }
+
+    fn set_cell_str(&mut self, sheet: &str, cell: &str, value: &str) -> Result<(), ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        let cell = coordinates_to_cell_name(col, row)?;
+        self.get_worksheet(sheet)?;
+        let value: String = value.chars().take(TOTAL_CELL_CHARS).collect();
+        let index = set_shared_string(self, &value);
+        let cells = &mut prepare_row(self.get_worksheet_mut(sheet)?, row).c;
+        let i = match cells
+            .iter()
+            .position(|c| cell_name_to_coordinates(&c.r).map_or(false, |(x, _)| x >= col))
+        {
+            Some(i) if cells[i].r == cell => i,
+            position => {
+                let i = position.unwrap_or(cells.len());
+                cells.insert(
+                    i,
+                    CTCell {
+                        r: cell,
+                        s: None,
+                        t: None,
+                        f: None,
+                        v: None,
+                    },
+                );
+                i
+            }
+        };
+        cells[i].t = Some(String::from("s"));
+        cells[i].f = None;
+        cells[i].v = Some(index.to_string());
+        Ok(())
+    }
+
+    fn get_cell_hyperlink(
+        &self,
+        sheet: &str,
+        cell: &str,
+    ) -> Result<Option<Hyperlink>, ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        let ws = self.get_worksheet(sheet)?;
+        for hyperlink in ws.hyperlinks.iter().flat_map(|h| h.hyperlink.iter()) {
+            if !hyperlink_contains(&hyperlink.ref_attr, col, row) {
+                continue;
+            }
+            let (link_type, link) = match hyperlink.rid {
+                Some(ref rid) => {
+                    let rels_path = get_rels_path(&self.get_sheet_xml_path(sheet)?);
+                    let target = self
+                        .rels
+                        .get(&rels_path)
+                        .and_then(|rels| rels.relationship.iter().find(|rel| &rel.id == rid))
+                        .map(|rel| rel.target.clone())
+                        .unwrap_or_default();
+                    ("External", target)
+                }
+                None => ("Location", hyperlink.location.clone().unwrap_or_default()),
+            };
+            return Ok(Some(Hyperlink {
+                link_type: String::from(link_type),
+                link,
+                tooltip: hyperlink.tooltip.clone(),
+                display: hyperlink.display.clone(),
+            }));
+        }
+        Ok(None)
+    }
+}
+
+// hyperlink_contains provides a function to check if the cell is in the
+// reference of the hyperlink, the unparsable reference contains no cell.
+fn hyperlink_contains(ref_attr: &str, col: u32, row: u32) -> bool {
+    match range_ref_to_coordinates(ref_attr) {
+        Ok(coordinates) => {
+            (coordinates[0]..=coordinates[2]).contains(&col)
+                && (coordinates[1]..=coordinates[3]).contains(&row)
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_cell_str() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert!(wb.set_cell_str("Sheet3", "A1", "x").is_err());
+        assert!(wb.set_cell_str("Sheet1", "A0", "x").is_err());
+        wb.set_cell_str("Sheet1", "c200", "Excelize").unwrap();
+        wb.set_cell_str("Sheet1", "B200", "Excelize").unwrap();
+        assert_eq!(wb.get_cell_value("Sheet1", 200, 3).unwrap(), "Excelize");
+        let ws = wb.get_worksheet("Sheet1").unwrap();
+        let row = ws.sheet_data.row.as_ref().unwrap().last().unwrap();
+        let cells: Vec<&str> = row.c.iter().map(|c| c.r.as_str()).collect();
+        assert_eq!(cells, vec!["B200", "C200"]);
+        assert_eq!(row.c[0].v, row.c[1].v);
+
+        // the shared strings part is created if it doesn't exist
+        let rels = wb.rels.get_mut("xl/_rels/workbook.xml.rels").unwrap();
+        rels.relationship
+            .retain(|rel| rel.target != "sharedStrings.xml");
+        wb.sst = None;
+        wb.file.remove("xl/sharedStrings.xml");
+        wb.set_cell_str("Sheet1", "B200", "Hello").unwrap();
+        wb.set_cell_str("Sheet1", "C200", "World").unwrap();
+        assert_eq!(wb.get_cell_value("Sheet1", 200, 2).unwrap(), "Hello");
+        assert_eq!(wb.sst.as_ref().unwrap().si.len(), 2);
+        let rels = &wb.rels["xl/_rels/workbook.xml.rels"];
+        let targets = rels
+            .relationship
+            .iter()
+            .filter(|rel| rel.target == "sharedStrings.xml");
+        assert_eq!(targets.count(), 1);
+    }
+
+    #[test]
+    fn test_cell_hyperlink() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let hyperlink = wb.get_cell_hyperlink("Sheet1", "A22").unwrap().unwrap();
+        assert_eq!(hyperlink.link_type, "External");
+        assert_eq!(
+            hyperlink.link,
+            "https://github.com/360EntSecGroup-Skylar/excelize"
+        );
+        assert_eq!(wb.get_cell_hyperlink("Sheet1", "A1").unwrap(), None);
+        assert!(wb
+            .set_cell_hyperlink("Sheet1", "A1", "Sheet2!A1", "Internal", None, None)
+            .is_err());
+
+        wb.set_cell_hyperlink(
+            "Sheet1",
+            "A22",
+            "https://github.com/xuri/excelize",
+            "External",
+            Some("excelize"),
+            None,
+        )
+        .unwrap();
+        wb.set_cell_hyperlink(
+            "Sheet1",
+            "b3",
+            "Sheet2!A1",
+            "Location",
+            None,
+            Some("Sheet2"),
+        )
+        .unwrap();
+        let hyperlink = wb.get_cell_hyperlink("Sheet1", "A22").unwrap().unwrap();
+        assert_eq!(hyperlink.link, "https://github.com/xuri/excelize");
+        assert_eq!(hyperlink.tooltip.as_deref(), Some("excelize"));
+        let rels = &wb.rels["xl/worksheets/_rels/sheet1.xml.rels"];
+        let rel = rels
+            .relationship
+            .iter()
+            .find(|rel| rel.rel_type == SOURCE_RELATIONSHIP_HYPERLINK);
+        assert_eq!(rel.unwrap().id, "rId7");
+        assert_eq!(rel.unwrap().target_mode.as_deref(), Some("External"));
+        assert_eq!(
+            wb.get_cell_hyperlink("Sheet1", "B3").unwrap(),
+            Some(Hyperlink {
+                link_type: String::from("Location"),
+                link: String::from("Sheet2!A1"),
+                tooltip: None,
+                display: Some(String::from("Sheet2")),
+            })
+        );
+        assert_eq!(
+            wb.get_worksheet("Sheet1")
+                .unwrap()
+                .hyperlinks
+                .as_ref()
+                .unwrap()
+                .hyperlink
+                .len(),
+            2
+        );
+
+        // the hyperlink of the range is replaced by the cell in the range
+        let ws = wb.get_worksheet_mut("Sheet1").unwrap();
+        let hyperlinks = &mut ws.hyperlinks.as_mut().unwrap().hyperlink;
+        hyperlinks.insert(
+            0,
+            CTHyperlink {
+                ref_attr: String::from("invalid"),
+                location: Some(String::from("Sheet2!A1")),
+                ..Default::default()
+            },
+        );
+        hyperlinks.push(CTHyperlink {
+            ref_attr: String::from("D1:E5"),
+            location: Some(String::from("Sheet2!A1")),
+            ..Default::default()
+        });
+        assert_eq!(
+            wb.get_cell_hyperlink("Sheet1", "E4").unwrap().unwrap().link,
+            "Sheet2!A1"
+        );
+        wb.set_cell_hyperlink("Sheet1", "E4", "Sheet2!B2", "Location", None, None)
+            .unwrap();
+        assert_eq!(
+            wb.get_cell_hyperlink("Sheet1", "D1").unwrap().unwrap().link,
+            "Sheet2!B2"
+        );
+        let hyperlinks = &wb.get_worksheet("Sheet1").unwrap().hyperlinks;
+        assert_eq!(hyperlinks.as_ref().unwrap().hyperlink.len(), 4);
+        assert_eq!(hyperlinks.as_ref().unwrap().hyperlink[3].ref_attr, "D1:E5");
+    }
 }
@@ -0,0 +1,992 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    add_content_type_override, calc::parse_reference, cell_name_to_coordinates,
+    column_number_to_name, get_relative_target, get_rels_path, next_part_index, CTAxDataSource,
+    CTAxis, CTChart, CTChartGroup, CTChartLines, CTChartNumFmt, CTChartRelId, CTDLbls, CTDrawing,
+    CTDrawingMarker, CTGraphicalObject, CTGraphicalObjectData, CTGraphicalObjectFrame,
+    CTGraphicalObjectFrameNonVisual, CTLayout, CTLegend, CTLineProperties, CTMarker,
+    CTNoFillProperties, CTNonVisualDrawingProps, CTNumDataSource, CTNumRef, CTPlotArea,
+    CTRegularTextRun, CTScaling, CTSer, CTShapeProperties, CTStrRef, CTTextBody, CTTextListStyle,
+    CTTextParagraph, CTTitle, CTTwoCellAnchor, CTTx, Col, ExcelizeError, Rels, Rows, STXBool,
+    STXCoordinate, STXDouble, STXInt, STXText, STXstring, Spreadsheet, Worksheet, XMLChartSpace,
+    XMLWsDr, SOURCE_RELATIONSHIP_DRAWING, TOTAL_COLUMNS, TOTAL_ROWS,
+};
+use quick_xml::events::Event;
+use quick_xml::se::to_string;
+use quick_xml::{Reader, Writer};
+use serde::Serialize;
+
+pub(crate) static SOURCE_RELATIONSHIP_CHART: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart";
+static CONTENT_TYPE_CHART: &str =
+    "application/vnd.openxmlformats-officedocument.drawingml.chart+xml";
+static CONTENT_TYPE_DRAWING: &str = "application/vnd.openxmlformats-officedocument.drawing+xml";
+static NAMESPACE_CHART: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
+static NAMESPACE_DRAWING_MAIN: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+static NAMESPACE_RELATIONSHIPS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+static NAMESPACE_SPREADSHEET_DRAWING: &str =
+    "http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing";
+// CHART_TYPES defined the supported chart types, the DrawingML element of the
+// chart group, the bar direction and the grouping of the chart.
+static CHART_TYPES: [(&str, &str, &str, &str); 12] = [
+    ("col", "barChart", "col", "clustered"),
+    ("colStacked", "barChart", "col", "stacked"),
+    ("colPercentStacked", "barChart", "col", "percentStacked"),
+    ("bar", "barChart", "bar", "clustered"),
+    ("barStacked", "barChart", "bar", "stacked"),
+    ("barPercentStacked", "barChart", "bar", "percentStacked"),
+    ("line", "lineChart", "", "standard"),
+    ("pie", "pieChart", "", ""),
+    ("scatter", "scatterChart", "", ""),
+    ("area", "areaChart", "", "standard"),
+    ("areaStacked", "areaChart", "", "stacked"),
+    ("areaPercentStacked", "areaChart", "", "percentStacked"),
+];
+static LEGEND_POSITIONS: [(&str, &str); 6] = [
+    ("", "r"),
+    ("right", "r"),
+    ("top", "t"),
+    ("bottom", "b"),
+    ("left", "l"),
+    ("top_right", "tr"),
+];
+static PRIMARY_AXIS_IDS: [u32; 2] = [100000000, 100000001];
+static SECONDARY_AXIS_IDS: [u32; 2] = [100000002, 100000003];
+static DEFAULT_CHART_WIDTH: u32 = 480;
+static DEFAULT_CHART_HEIGHT: u32 = 290;
+static EMU_PER_PIXEL: u32 = 9525;
+static XML_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
+
+/// Chart directly maps the settings of the chart. The type is one of area,
+/// areaStacked, areaPercentStacked, bar, barStacked, barPercentStacked, col,
+/// colStacked, colPercentStacked, line, pie and scatter. The size of the
+/// chart is in pixels, defaults to 480 x 290.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Chart {
+    pub type_attr: String,
+    pub series: Vec<ChartSeries>,
+    pub title: String,
+    pub legend: ChartLegend,
+    pub x_axis: ChartAxis,
+    pub y_axis: ChartAxis,
+    pub plot_area: ChartPlotArea,
+    pub size: ChartSize,
+}
+
+/// ChartSeries directly maps the settings of the chart series. The name is
+/// the reference of the series name cell such as Sheet1!$A$2, or the text of
+/// the name. The categories and the values are the references of the ranges,
+/// such as Sheet1!$B$1:$D$1 and Sheet1!$B$2:$D$2, the categories are the X
+/// values of the scatter chart.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartSeries {
+    pub name: String,
+    pub categories: String,
+    pub values: String,
+}
+
+/// ChartLegend directly maps the settings of the chart legend. The position
+/// is one of none, top, bottom, left, right and top_right, defaults to right.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartLegend {
+    pub position: String,
+}
+
+/// ChartAxis directly maps the settings of the chart axis. The number format
+/// is the format code of the axis labels, such as 0.00%, and the axis labels
+/// use the format of the source data if it's empty. Set the secondary of the
+/// Y axis for the combo chart to plot the chart on the secondary axis.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartAxis {
+    pub none: bool,
+    pub title: String,
+    pub major_grid_lines: bool,
+    pub minor_grid_lines: bool,
+    pub reverse_order: bool,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub num_fmt: String,
+    pub secondary: bool,
+}
+
+/// ChartPlotArea directly maps the settings of the data labels in the plot
+/// area of the chart.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartPlotArea {
+    pub show_legend_key: bool,
+    pub show_val: bool,
+    pub show_cat_name: bool,
+    pub show_ser_name: bool,
+    pub show_percent: bool,
+}
+
+/// ChartSize directly maps the size of the chart in pixels.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChartSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+pub trait Charts {
+    /// add_chart provides a function to add the chart in a worksheet by given
+    /// worksheet name, the cell reference of the top-left corner of the chart,
+    /// the chart settings and the combo charts. The combo charts are plotted
+    /// in the same plot area with the chart, and the pie and scatter charts
+    /// can't be combined. For example, create the column chart of the sales
+    /// with the line chart of the growth rate on the secondary axis:
+    ///
+    /// ```ignore
+    /// spreadsheet.add_chart(
+    ///     "Sheet1",
+    ///     "E1",
+    ///     &Chart {
+    ///         type_attr: String::from("col"),
+    ///         series: vec![ChartSeries {
+    ///             name: String::from("Sheet1!$B$1"),
+    ///             categories: String::from("Sheet1!$A$2:$A$5"),
+    ///             values: String::from("Sheet1!$B$2:$B$5"),
+    ///         }],
+    ///         title: String::from("Sales"),
+    ///         ..Default::default()
+    ///     },
+    ///     &[Chart {
+    ///         type_attr: String::from("line"),
+    ///         series: vec![ChartSeries {
+    ///             name: String::from("Sheet1!$C$1"),
+    ///             categories: String::from("Sheet1!$A$2:$A$5"),
+    ///             values: String::from("Sheet1!$C$2:$C$5"),
+    ///         }],
+    ///         y_axis: ChartAxis {
+    ///             num_fmt: String::from("0%"),
+    ///             secondary: true,
+    ///             ..Default::default()
+    ///         },
+    ///         ..Default::default()
+    ///     }],
+    /// )
+    /// ```
+    fn add_chart(
+        &mut self,
+        sheet: &str,
+        cell: &str,
+        chart: &Chart,
+        combo: &[Chart],
+    ) -> Result<(), ExcelizeError>;
+}
+
+impl Charts for Spreadsheet {
+    fn add_chart(
+        &mut self,
+        sheet: &str,
+        cell: &str,
+        chart: &Chart,
+        combo: &[Chart],
+    ) -> Result<(), ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        check_chart(chart)?;
+        for combo_chart in combo {
+            check_chart(combo_chart)?;
+            for type_attr in [&chart.type_attr, &combo_chart.type_attr] {
+                if type_attr == "pie" || type_attr == "scatter" {
+                    return Err(ExcelizeError::CommonError(format!(
+                        "the {} chart can't be combined",
+                        type_attr
+                    )));
+                }
+            }
+        }
+        let legend = match chart.legend.position.as_str() {
+            "none" => None,
+            position => Some(
+                LEGEND_POSITIONS
+                    .iter()
+                    .find(|(p, _)| *p == position)
+                    .map(|(_, pos)| *pos)
+                    .ok_or_else(|| {
+                        ExcelizeError::CommonError(format!(
+                            "invalid chart legend position {}",
+                            position
+                        ))
+                    })?,
+            ),
+        };
+        let width = match chart.size.width {
+            0 => DEFAULT_CHART_WIDTH,
+            width => width,
+        };
+        let height = match chart.size.height {
+            0 => DEFAULT_CHART_HEIGHT,
+            height => height,
+        };
+        let anchor = chart_anchor(self, sheet, col, row, width, height)?;
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+
+        let chart_path = format!(
+            "xl/charts/chart{}.xml",
+            next_part_index(self, "xl/charts/chart", ".xml")
+        );
+        self.file.insert(
+            chart_path.clone(),
+            new_chart_space(chart, combo, legend)?.into_bytes(),
+        );
+        let drawing_path = match self
+            .get_worksheet(sheet)?
+            .drawing
+            .as_ref()
+            .and_then(|drawing| self.get_rels_target_path(&sheet_path, &drawing.rid))
+        {
+            Some(path) => path,
+            None => {
+                let path = format!(
+                    "xl/drawings/drawing{}.xml",
+                    next_part_index(self, "xl/drawings/drawing", ".xml")
+                );
+                let rid = self.add_rels(
+                    &get_rels_path(&sheet_path),
+                    SOURCE_RELATIONSHIP_DRAWING,
+                    &get_relative_target(&sheet_path, &path),
+                    None,
+                );
+                self.get_worksheet_mut(sheet)?.drawing = Some(CTDrawing { rid });
+                if let Some(ref mut types) = self.content_type {
+                    add_content_type_override(types, &path, CONTENT_TYPE_DRAWING);
+                }
+                path
+            }
+        };
+        let rid = self.add_rels(
+            &get_rels_path(&drawing_path),
+            SOURCE_RELATIONSHIP_CHART,
+            &get_relative_target(&drawing_path, &chart_path),
+            None,
+        );
+        if let Some(ref mut types) = self.content_type {
+            add_content_type_override(types, &chart_path, CONTENT_TYPE_CHART);
+        }
+        let drawing = match self.file.get(&drawing_path) {
+            Some(buf) => String::from_utf8_lossy(buf).into_owned(),
+            None => serialize_part(&XMLWsDr {
+                xmlns_xdr: String::from(NAMESPACE_SPREADSHEET_DRAWING),
+                xmlns_a: String::from(NAMESPACE_DRAWING_MAIN),
+                ..Default::default()
+            })?,
+        };
+        self.file.insert(
+            drawing_path,
+            add_drawing_graphic_frame(&drawing, &anchor, &rid)?.into_bytes(),
+        );
+        Ok(())
+    }
+}
+
+// check_chart provides a function to check the type and the series of the
+// chart.
+fn check_chart(chart: &Chart) -> Result<(), ExcelizeError> {
+    if !CHART_TYPES.iter().any(|(t, ..)| *t == chart.type_attr) {
+        return Err(ExcelizeError::CommonError(format!(
+            "unsupported chart type {}",
+            chart.type_attr
+        )));
+    }
+    if chart.series.is_empty() {
+        return Err(ExcelizeError::CommonError(String::from(
+            "the chart should contain at least one series",
+        )));
+    }
+    for series in &chart.series {
+        let references = match series.categories.is_empty() {
+            true => vec![&series.values],
+            false => vec![&series.categories, &series.values],
+        };
+        for reference in references {
+            if !matches!(
+                parse_reference(reference.trim_start_matches('=')),
+                Some((Some(_), _))
+            ) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid chart series reference {:?}",
+                    reference
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+// chart_anchor provides a function to get the two cell anchor of the chart,
+// which are the zero-based column and row index and the offsets in EMUs of
+// the top-left corner and the bottom-right corner, by given the top-left cell
+// and the size of the chart in pixels.
+fn chart_anchor(
+    spreadsheet: &Spreadsheet,
+    sheet: &str,
+    col: u32,
+    row: u32,
+    width: u32,
+    height: u32,
+) -> Result<[u32; 6], ExcelizeError> {
+    let (mut to_col, mut col_off) = (col, width);
+    while to_col < TOTAL_COLUMNS {
+        let pixels =
+            col_width_to_pixels(spreadsheet.get_col_width(sheet, &column_number_to_name(to_col)?)?);
+        if col_off < pixels {
+            break;
+        }
+        col_off -= pixels;
+        to_col += 1;
+    }
+    let (mut to_row, mut row_off) = (row, height);
+    while to_row < TOTAL_ROWS {
+        let pixels = row_height_to_pixels(spreadsheet.get_row_height(sheet, to_row)?);
+        if row_off < pixels {
+            break;
+        }
+        row_off -= pixels;
+        to_row += 1;
+    }
+    Ok([
+        col - 1,
+        row - 1,
+        to_col - 1,
+        col_off * EMU_PER_PIXEL,
+        to_row - 1,
+        row_off * EMU_PER_PIXEL,
+    ])
+}
+
+// col_width_to_pixels provides a function to convert the column width in
+// characters to pixels, the maximum digit width of the default font is 7
+// pixels with 5 pixels padding.
+fn col_width_to_pixels(width: f64) -> u32 {
+    match width {
+        w if w <= 0.0 => 0,
+        w if w < 1.0 => (w * 12.0 + 0.5).ceil() as u32,
+        w => (w * 7.0 + 0.5 + 5.0).ceil() as u32,
+    }
+}
+
+// row_height_to_pixels provides a function to convert the row height in
+// points to pixels.
+fn row_height_to_pixels(height: f64) -> u32 {
+    (height * 4.0 / 3.0).ceil() as u32
+}
+
+// add_drawing_graphic_frame provides a function to add the two cell anchor of
+// the chart into the drawing part, the ID of the shape is the next of the
+// maximum shape ID in the drawing. The anchor is written before the end of
+// the root element, and the other events are written as they were read.
+fn add_drawing_graphic_frame(
+    drawing: &str,
+    anchor: &[u32; 6],
+    rid: &str,
+) -> Result<String, ExcelizeError> {
+    let mut reader = Reader::from_str(drawing);
+    let mut buf = Vec::new();
+    let mut events = Vec::new();
+    let mut max_id = 1;
+    loop {
+        let event = match reader.read_event(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(event) => event.into_owned(),
+            Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+        };
+        buf.clear();
+        if let Event::Start(ref e) | Event::Empty(ref e) = event {
+            if e.local_name() == b"cNvPr" {
+                let id = e
+                    .attributes()
+                    .filter_map(Result::ok)
+                    .find(|attr| attr.key == b"id")
+                    .and_then(|attr| std::str::from_utf8(&attr.value).ok()?.parse().ok());
+                max_id = max_id.max(id.unwrap_or(0));
+            }
+        }
+        events.push(event);
+    }
+    let id = max_id + 1;
+    let anchor = to_string(&CTTwoCellAnchor {
+        edit_as: Some(String::from("oneCell")),
+        from: CTDrawingMarker {
+            col: STXCoordinate(i64::from(anchor[0])),
+            row: STXCoordinate(i64::from(anchor[1])),
+            ..Default::default()
+        },
+        to: CTDrawingMarker {
+            col: STXCoordinate(i64::from(anchor[2])),
+            col_off: STXCoordinate(i64::from(anchor[3])),
+            row: STXCoordinate(i64::from(anchor[4])),
+            row_off: STXCoordinate(i64::from(anchor[5])),
+        },
+        graphic_frame: Some(CTGraphicalObjectFrame {
+            nv_graphic_frame_pr: CTGraphicalObjectFrameNonVisual {
+                c_nv_pr: CTNonVisualDrawingProps {
+                    id,
+                    name: format!("Chart {}", id - 1),
+                },
+                ..Default::default()
+            },
+            graphic: CTGraphicalObject {
+                graphic_data: CTGraphicalObjectData {
+                    uri: String::from(NAMESPACE_CHART),
+                    chart: Some(CTChartRelId {
+                        xmlns_c: String::from(NAMESPACE_CHART),
+                        xmlns_r: String::from(NAMESPACE_RELATIONSHIPS),
+                        id: String::from(rid),
+                    }),
+                },
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+    .map_err(|e| ExcelizeError::CommonError(e.to_string()))?;
+    let mut writer = Writer::new(Vec::new());
+    let mut depth = 0;
+    for event in events {
+        let result = match event {
+            Event::Start(_) => {
+                depth += 1;
+                writer.write_event(event)
+            }
+            Event::End(_) => {
+                depth -= 1;
+                match depth {
+                    0 => writer
+                        .write(anchor.as_bytes())
+                        .and_then(|_| writer.write_event(event)),
+                    _ => writer.write_event(event),
+                }
+            }
+            Event::Empty(e) if depth == 0 => {
+                let end = Event::End(e.to_end().into_owned());
+                writer
+                    .write_event(Event::Start(e))
+                    .and_then(|_| writer.write(anchor.as_bytes()))
+                    .and_then(|_| writer.write_event(end))
+            }
+            event => writer.write_event(event),
+        };
+        if let Err(e) = result {
+            return Err(ExcelizeError::CommonError(e.to_string()));
+        }
+    }
+    String::from_utf8(writer.into_inner()).map_err(|e| ExcelizeError::CommonError(e.to_string()))
+}
+
+// new_chart_space provides a function to create the chart part by given the
+// chart settings, the combo charts and the legend position.
+fn new_chart_space(
+    chart: &Chart,
+    combo: &[Chart],
+    legend: Option<&str>,
+) -> Result<String, ExcelizeError> {
+    let mut plot_area = CTPlotArea {
+        layout: Some(CTLayout {}),
+        ..Default::default()
+    };
+    let mut index = 0;
+    let mut secondary_axis = None;
+    for (i, c) in std::iter::once(chart).chain(combo).enumerate() {
+        let secondary = i > 0 && c.y_axis.secondary;
+        if secondary && secondary_axis.is_none() {
+            secondary_axis = Some(&c.y_axis);
+        }
+        let axis_ids = match secondary {
+            true => SECONDARY_AXIS_IDS,
+            false => PRIMARY_AXIS_IDS,
+        };
+        let (element, group) = new_chart_group(c, &mut index, &axis_ids);
+        match element {
+            "areaChart" => plot_area.area_chart.push(group),
+            "barChart" => plot_area.bar_chart.push(group),
+            "lineChart" => plot_area.line_chart.push(group),
+            "pieChart" => plot_area.pie_chart.push(group),
+            _ => plot_area.scatter_chart.push(group),
+        }
+    }
+    let bar_dir = CHART_TYPES
+        .iter()
+        .find(|(t, ..)| *t == chart.type_attr)
+        .map_or("", |(_, _, dir, _)| *dir);
+    let (cat_pos, val_pos) = match bar_dir {
+        "bar" => ("l", "b"),
+        _ => ("b", "l"),
+    };
+    match chart.type_attr.as_str() {
+        "pie" => {}
+        "scatter" => {
+            plot_area.val_ax.push(new_val_axis(
+                &chart.x_axis,
+                PRIMARY_AXIS_IDS,
+                cat_pos,
+                "autoZero",
+                "midCat",
+            ));
+            plot_area.val_ax.push(new_val_axis(
+                &chart.y_axis,
+                [PRIMARY_AXIS_IDS[1], PRIMARY_AXIS_IDS[0]],
+                val_pos,
+                "autoZero",
+                "midCat",
+            ));
+        }
+        _ => {
+            plot_area
+                .cat_ax
+                .push(new_cat_axis(&chart.x_axis, PRIMARY_AXIS_IDS, cat_pos));
+            plot_area.val_ax.push(new_val_axis(
+                &chart.y_axis,
+                [PRIMARY_AXIS_IDS[1], PRIMARY_AXIS_IDS[0]],
+                val_pos,
+                "autoZero",
+                "between",
+            ));
+        }
+    }
+    if let Some(axis) = secondary_axis {
+        plot_area.cat_ax.push(new_cat_axis(
+            &ChartAxis {
+                none: true,
+                ..Default::default()
+            },
+            SECONDARY_AXIS_IDS,
+            cat_pos,
+        ));
+        let val_pos = match val_pos {
+            "b" => "t",
+            _ => "r",
+        };
+        plot_area.val_ax.push(new_val_axis(
+            axis,
+            [SECONDARY_AXIS_IDS[1], SECONDARY_AXIS_IDS[0]],
+            val_pos,
+            "max",
+            "between",
+        ));
+    }
+    serialize_part(&XMLChartSpace {
+        xmlns_c: String::from(NAMESPACE_CHART),
+        xmlns_a: String::from(NAMESPACE_DRAWING_MAIN),
+        xmlns_r: String::from(NAMESPACE_RELATIONSHIPS),
+        rounded_corners: stx_bool(false),
+        chart: CTChart {
+            title: new_chart_title(&chart.title),
+            auto_title_deleted: stx_bool(chart.title.is_empty()),
+            plot_area,
+            legend: legend.map(|pos| CTLegend {
+                legend_pos: stx_string(pos),
+                overlay: stx_bool(false),
+            }),
+            plot_vis_only: stx_bool(true),
+            disp_blanks_as: stx_string("gap"),
+        },
+    })
+}
+
+// new_chart_group provides a function to create the chart group of the chart
+// in the plot area, returns the element name and the chart group. The index
+// is the index of the first series and will be increased by the number of
+// the series.
+fn new_chart_group(
+    chart: &Chart,
+    index: &mut usize,
+    axis_ids: &[u32; 2],
+) -> (&'static str, CTChartGroup) {
+    let (_, element, bar_dir, grouping) = CHART_TYPES
+        .iter()
+        .find(|(t, ..)| *t == chart.type_attr)
+        .copied()
+        .unwrap_or(CHART_TYPES[0]);
+    let mut group = CTChartGroup {
+        vary_colors: stx_bool(element == "pieChart"),
+        ..Default::default()
+    };
+    match element {
+        "barChart" => {
+            group.bar_dir = stx_string(bar_dir);
+            group.grouping = stx_string(grouping);
+            group.gap_width = Some(STXInt { val: 150 });
+            if grouping != "clustered" {
+                group.overlap = Some(STXInt { val: 100 });
+            }
+        }
+        "lineChart" => {
+            group.grouping = stx_string(grouping);
+            group.marker = stx_bool(true);
+        }
+        "areaChart" => group.grouping = stx_string(grouping),
+        "pieChart" => group.first_slice_ang = Some(STXInt { val: 0 }),
+        _ => group.scatter_style = stx_string("lineMarker"),
+    }
+    for series in &chart.series {
+        group.ser.push(new_chart_series(element, series, *index));
+        *index += 1;
+    }
+    let plot_area = &chart.plot_area;
+    group.d_lbls = Some(CTDLbls {
+        show_legend_key: stx_bool(plot_area.show_legend_key),
+        show_val: stx_bool(plot_area.show_val),
+        show_cat_name: stx_bool(plot_area.show_cat_name),
+        show_ser_name: stx_bool(plot_area.show_ser_name),
+        show_percent: stx_bool(plot_area.show_percent),
+        show_bubble_size: stx_bool(false),
+    });
+    if element != "pieChart" {
+        group.ax_id = axis_ids
+            .iter()
+            .map(|id| STXInt { val: *id as i32 })
+            .collect();
+    }
+    (element, group)
+}
+
+// new_chart_series provides a function to create the series of the chart
+// group by given the element name of the chart group, the series settings
+// and the index of the series.
+fn new_chart_series(element: &str, series: &ChartSeries, index: usize) -> CTSer {
+    let name = series.name.trim_start_matches('=');
+    let tx = match parse_reference(name) {
+        Some((Some(_), _)) => Some(CTTx {
+            str_ref: Some(CTStrRef {
+                f: STXText(String::from(name)),
+            }),
+            ..Default::default()
+        }),
+        _ if !name.is_empty() => Some(CTTx {
+            v: Some(STXText(String::from(name))),
+            ..Default::default()
+        }),
+        _ => None,
+    };
+    let mut ser = CTSer {
+        idx: STXInt { val: index as i32 },
+        order: STXInt { val: index as i32 },
+        tx,
+        ..Default::default()
+    };
+    let categories = series.categories.trim_start_matches('=');
+    let values = Some(CTNumDataSource {
+        num_ref: Some(CTNumRef {
+            f: STXText(String::from(series.values.trim_start_matches('='))),
+        }),
+    });
+    match element {
+        "scatterChart" => {
+            ser.sp_pr = Some(CTShapeProperties {
+                ln: Some(CTLineProperties {
+                    w: Some(25400),
+                    no_fill: Some(CTNoFillProperties {}),
+                }),
+            });
+            ser.marker = Some(CTMarker {
+                symbol: stx_string("circle"),
+            });
+            if !categories.is_empty() {
+                ser.x_val = Some(CTAxDataSource {
+                    num_ref: Some(CTNumRef {
+                        f: STXText(String::from(categories)),
+                    }),
+                    ..Default::default()
+                });
+            }
+            ser.y_val = values;
+        }
+        _ => {
+            if element == "barChart" {
+                ser.invert_if_negative = stx_bool(false);
+            }
+            if !categories.is_empty() {
+                ser.cat = Some(CTAxDataSource {
+                    str_ref: Some(CTStrRef {
+                        f: STXText(String::from(categories)),
+                    }),
+                    ..Default::default()
+                });
+            }
+            ser.val = values;
+        }
+    }
+    if element == "lineChart" || element == "scatterChart" {
+        ser.smooth = stx_bool(false);
+    }
+    ser
+}
+
+// new_chart_title provides a function to create the title of the chart or the
+// axis, returns none if the title is empty.
+fn new_chart_title(title: &str) -> Option<CTTitle> {
+    match title {
+        "" => None,
+        title => Some(CTTitle {
+            tx: Some(CTTx {
+                rich: Some(CTTextBody {
+                    lst_style: Some(CTTextListStyle {}),
+                    p: vec![CTTextParagraph {
+                        r: vec![CTRegularTextRun {
+                            t: STXText(String::from(title)),
+                        }],
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            overlay: stx_bool(false),
+        }),
+    }
+}
+
+// new_axis provides a function to create the axis with the common child
+// elements of the category axis and the value axis from the scaling to the
+// cross axis.
+fn new_axis(axis: &ChartAxis, axis_ids: [u32; 2], pos: &str) -> CTAxis {
+    CTAxis {
+        ax_id: STXInt {
+            val: axis_ids[0] as i32,
+        },
+        scaling: CTScaling {
+            orientation: stx_string(match axis.reverse_order {
+                true => "maxMin",
+                false => "minMax",
+            }),
+            max: axis.maximum.map(|val| STXDouble { val }),
+            min: axis.minimum.map(|val| STXDouble { val }),
+        },
+        delete: stx_bool(axis.none),
+        ax_pos: STXstring {
+            val: String::from(pos),
+        },
+        major_gridlines: axis.major_grid_lines.then_some(CTChartLines {}),
+        minor_gridlines: axis.minor_grid_lines.then_some(CTChartLines {}),
+        title: new_chart_title(&axis.title),
+        num_fmt: Some(CTChartNumFmt {
+            format_code: match axis.num_fmt.as_str() {
+                "" => String::from("General"),
+                num_fmt => String::from(num_fmt),
+            },
+            source_linked: Some(axis.num_fmt.is_empty()),
+        }),
+        major_tick_mark: stx_string("none"),
+        minor_tick_mark: stx_string("none"),
+        tick_lbl_pos: stx_string("nextTo"),
+        cross_ax: STXInt {
+            val: axis_ids[1] as i32,
+        },
+        ..Default::default()
+    }
+}
+
+// new_cat_axis provides a function to create the category axis by given the
+// axis settings, the axis ID and the cross axis ID, and the position of the
+// axis.
+fn new_cat_axis(axis: &ChartAxis, axis_ids: [u32; 2], pos: &str) -> CTAxis {
+    CTAxis {
+        crosses: stx_string("autoZero"),
+        auto: stx_bool(true),
+        lbl_algn: stx_string("ctr"),
+        lbl_offset: Some(STXInt { val: 100 }),
+        no_multi_lvl_lbl: stx_bool(false),
+        ..new_axis(axis, axis_ids, pos)
+    }
+}
+
+// new_val_axis provides a function to create the value axis by given the
+// axis settings, the axis ID and the cross axis ID, the position of the axis,
+// where the axis crosses the cross axis and the cross between type.
+fn new_val_axis(
+    axis: &ChartAxis,
+    axis_ids: [u32; 2],
+    pos: &str,
+    crosses: &str,
+    cross_between: &str,
+) -> CTAxis {
+    CTAxis {
+        crosses: stx_string(crosses),
+        cross_between: stx_string(cross_between),
+        ..new_axis(axis, axis_ids, pos)
+    }
+}
+
+// stx_bool provides a function to create the element with the boolean val
+// attribute.
+fn stx_bool(val: bool) -> Option<STXBool> {
+    Some(STXBool { val: Some(val) })
+}
+
+// stx_string provides a function to create the element with the string val
+// attribute.
+fn stx_string(val: &str) -> Option<STXstring> {
+    Some(STXstring {
+        val: String::from(val),
+    })
+}
+
+// serialize_part provides a function to serialize the part with the XML
+// declaration.
+fn serialize_part<T: Serialize>(part: &T) -> Result<String, ExcelizeError> {
+    to_string(part)
+        .map(|xml| format!("{}{}", XML_HEADER, xml))
+        .map_err(|e| ExcelizeError::CommonError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::de::from_str;
+
+    fn series(values: &str) -> ChartSeries {
+        ChartSeries {
+            name: String::from("Sheet2!$A$1"),
+            categories: String::from("Sheet2!$A$2:$A$5"),
+            values: String::from(values),
+        }
+    }
+
+    fn part(wb: &Spreadsheet, path: &str) -> String {
+        String::from_utf8_lossy(&wb.file[path]).into_owned()
+    }
+
+    fn series_chart(type_attr: &str) -> Chart {
+        Chart {
+            type_attr: String::from(type_attr),
+            series: vec![series("Sheet2!$B$2:$B$5")],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_add_chart() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let chart = Chart {
+            type_attr: String::from("col"),
+            series: vec![series("Sheet2!$B$2:$B$5")],
+            title: String::from("Sales & Growth"),
+            y_axis: ChartAxis {
+                major_grid_lines: true,
+                minimum: Some(0.0),
+                ..Default::default()
+            },
+            plot_area: ChartPlotArea {
+                show_val: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let line = Chart {
+            type_attr: String::from("line"),
+            series: vec![series("Sheet2!$D$2:$D$5")],
+            y_axis: ChartAxis {
+                num_fmt: String::from("0.00%"),
+                secondary: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(wb
+            .add_chart(
+                "Sheet2",
+                "F1",
+                &Chart {
+                    type_attr: String::from("radar"),
+                    ..chart.clone()
+                },
+                &[]
+            )
+            .is_err());
+        assert!(wb
+            .add_chart(
+                "Sheet2",
+                "F1",
+                &Chart {
+                    series: Vec::new(),
+                    ..chart.clone()
+                },
+                &[]
+            )
+            .is_err());
+        assert!(wb
+            .add_chart("Sheet2", "F1", &chart, &[series_chart("pie")])
+            .is_err());
+        assert!(wb
+            .add_chart(
+                "Sheet2",
+                "F1",
+                &Chart {
+                    legend: ChartLegend {
+                        position: String::from("center"),
+                    },
+                    ..chart.clone()
+                },
+                &[]
+            )
+            .is_err());
+        wb.add_chart("Sheet2", "F1", &chart, &[line]).unwrap();
+
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        let rid = &ws.drawing.as_ref().unwrap().rid;
+        let sheet_path = wb.get_sheet_xml_path("Sheet2").unwrap();
+        assert_eq!(
+            wb.get_rels_target_path(&sheet_path, rid).as_deref(),
+            Some("xl/drawings/drawing2.xml")
+        );
+        let drawing = part(&wb, "xl/drawings/drawing2.xml");
+        assert!(drawing.contains(r#"<xdr:cNvPr id="2" name="Chart 1"/>"#));
+        assert!(drawing.contains("<xdr:from><xdr:col>5</xdr:col>"));
+        assert!(drawing.ends_with("<xdr:clientData/></xdr:twoCellAnchor></xdr:wsDr>"));
+        assert_eq!(
+            wb.get_rels_by_type("xl/drawings/drawing2.xml", SOURCE_RELATIONSHIP_CHART),
+            vec![(String::from("rId1"), String::from("xl/charts/chart3.xml"))]
+        );
+        let chart_space = part(&wb, "xl/charts/chart3.xml");
+        assert!(chart_space.contains("<a:t>Sales &amp; Growth</a:t>"));
+        assert!(chart_space.contains(r#"<c:barDir val="col"/><c:grouping val="clustered"/>"#));
+        assert!(chart_space.contains(r#"<c:showVal val="true"/>"#));
+        assert!(chart_space.contains("<c:lineChart>"));
+        assert!(chart_space.contains(r#"<c:idx val="1"/>"#));
+        assert!(chart_space.contains(r#"<c:axPos val="r"/>"#));
+        assert!(chart_space.contains(r#"<c:numFmt formatCode="0.00%" sourceLinked="false"/>"#));
+        assert!(chart_space.contains(r#"<c:crosses val="max"/>"#));
+        assert!(chart_space.contains(r#"<c:min val="0"/>"#));
+        assert!(chart_space.contains("<c:majorGridlines/>"));
+        let chart_space: XMLChartSpace = from_str(&chart_space).unwrap();
+        let plot_area = &chart_space.chart.plot_area;
+        assert_eq!(plot_area.bar_chart[0].ser[0].idx.val, 0);
+        assert_eq!(plot_area.line_chart[0].ser[0].idx.val, 1);
+        assert_eq!(plot_area.cat_ax.len(), 2);
+        assert_eq!(plot_area.val_ax[1].ax_pos.val, "r");
+        let drawing: XMLWsDr = from_str(&part(&wb, "xl/drawings/drawing2.xml")).unwrap();
+        let frame = drawing.two_cell_anchor[0].graphic_frame.as_ref().unwrap();
+        assert_eq!(frame.nv_graphic_frame_pr.c_nv_pr.id, 2);
+
+        wb.add_chart(
+            "Sheet1",
+            "A20",
+            &Chart {
+                legend: ChartLegend {
+                    position: String::from("none"),
+                },
+                size: ChartSize {
+                    width: 83,
+                    height: 31,
+                },
+                ..series_chart("pie")
+            },
+            &[],
+        )
+        .unwrap();
+        let drawing = part(&wb, "xl/drawings/drawing1.xml");
+        assert!(drawing.contains(r#"<xdr:cNvPr id="3" name="Chart 2"/>"#));
+        assert!(drawing.contains(r#"<xdr:cNvPr id="4" name="Chart 3"/>"#));
+        assert!(drawing.contains(r#"r:id="rId3"/>"#));
+        assert!(drawing.contains(
+            "<xdr:to><xdr:col>1</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>20</xdr:row><xdr:rowOff>95250</xdr:rowOff></xdr:to>"
+        ));
+        let chart_space = part(&wb, "xl/charts/chart4.xml");
+        assert!(chart_space.contains(r#"<c:pieChart><c:varyColors val="true"/>"#));
+        assert!(!chart_space.contains("<c:legend>"));
+        assert!(!chart_space.contains("<c:valAx>"));
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    column_name_to_number, split_cell_name, update_outline_level_col, Adjust, AdjustDirection,
+    CTCol, CTCols, ExcelizeError, Spreadsheet, Worksheet, XMLWorksheet, MAX_COLUMN_WIDTH,
+    TOTAL_COLUMNS,
+};
+
+// DEFAULT_COL_WIDTH specifies the default column width in characters, used
+// when neither the column nor the sheet format properties defines the width.
+static DEFAULT_COL_WIDTH: f64 = 9.140625;
+
+pub trait Col {
+    /// insert_cols provides a function to insert new columns before the given
+    /// column name and number of columns. The references of the cells, merged
+    /// cells, hyperlinks, auto filter, data validations, conditional formats,
+    /// defined names and formulas across the workbook will be adjusted. For
+    /// example, create two columns before column C in Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.insert_cols("Sheet1", "C", 2)
+    /// ```
+    fn insert_cols(&mut self, sheet: &str, col: &str, n: u32) -> Result<(), ExcelizeError>;
+    /// remove_col provides a function to remove single column by given
+    /// worksheet name and column name, the columns on the right are moved left
+    /// and the references to the removed cells in formulas become #REF!.
+    fn remove_col(&mut self, sheet: &str, col: &str) -> Result<(), ExcelizeError>;
+    /// set_col_width provides a function to set the width of a single column
+    /// or multiple columns. The width is measured in characters and should be
+    /// less than or equal to 255. For example, set the width of the columns
+    /// from A to D in Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.set_col_width("Sheet1", "A", "D", 20.0)
+    /// ```
+    fn set_col_width(
+        &mut self,
+        sheet: &str,
+        start_col: &str,
+        end_col: &str,
+        width: f64,
+    ) -> Result<(), ExcelizeError>;
+    /// get_col_width provides a function to get the width of the column by
+    /// given worksheet name and column name, the default width of the
+    /// worksheet will be returned if the column width isn't set.
+    fn get_col_width(&self, sheet: &str, col: &str) -> Result<f64, ExcelizeError>;
+    /// set_col_visible provides a function to set visible of a single column
+    /// or multiple columns by given worksheet name and columns range, such as
+    /// "D" or "D:F".
+    fn set_col_visible(
+        &mut self,
+        sheet: &str,
+        columns: &str,
+        visible: bool,
+    ) -> Result<(), ExcelizeError>;
+    /// get_col_visible provides a function to get visible of a single column
+    /// by given worksheet name and column name.
+    fn get_col_visible(&self, sheet: &str, col: &str) -> Result<bool, ExcelizeError>;
+    /// set_col_outline_level provides a function to set outline level of a
+    /// single column by given worksheet name and column name. The value of
+    /// the level should be in the range from 1 to 7.
+    fn set_col_outline_level(
+        &mut self,
+        sheet: &str,
+        col: &str,
+        level: u8,
+    ) -> Result<(), ExcelizeError>;
+    /// get_col_outline_level provides a function to get outline level of a
+    /// single column by given worksheet name and column name.
+    fn get_col_outline_level(&self, sheet: &str, col: &str) -> Result<u8, ExcelizeError>;
+    /// set_col_style provides a function to set the style of a single column
+    /// or multiple columns by given worksheet name, columns range and style
+    /// ID, the style of the existing cells in the columns will be updated as
+    /// well.
+    fn set_col_style(
+        &mut self,
+        sheet: &str,
+        columns: &str,
+        style: u32,
+    ) -> Result<(), ExcelizeError>;
+    /// get_col_style provides a function to get the style ID of a single
+    /// column by given worksheet name and column name.
+    fn get_col_style(&self, sheet: &str, col: &str) -> Result<u32, ExcelizeError>;
+}
+
+impl Col for Spreadsheet {
+    fn insert_cols(&mut self, sheet: &str, col: &str, n: u32) -> Result<(), ExcelizeError> {
+        let num = column_name_to_number(col)?;
+        if n < 1 || n > TOTAL_COLUMNS {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid number of columns {}",
+                n
+            )));
+        }
+        self.adjust_helper(sheet, AdjustDirection::Columns, num, n as i32)
+    }
+
+    fn remove_col(&mut self, sheet: &str, col: &str) -> Result<(), ExcelizeError> {
+        let num = column_name_to_number(col)?;
+        self.adjust_helper(sheet, AdjustDirection::Columns, num, -1)
+    }
+
+    fn set_col_width(
+        &mut self,
+        sheet: &str,
+        start_col: &str,
+        end_col: &str,
+        width: f64,
+    ) -> Result<(), ExcelizeError> {
+        let (min, max) = parse_col_range(&format!("{}:{}", start_col, end_col))?;
+        if !width.is_finite() {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid width {} of the column",
+                width
+            )));
+        }
+        if width > MAX_COLUMN_WIDTH as f64 {
+            return Err(ExcelizeError::CommonError(format!(
+                "the width of the column must be less than or equal to {} characters",
+                MAX_COLUMN_WIDTH
+            )));
+        }
+        if width < 0.0 {
+            return Err(ExcelizeError::CommonError(String::from(
+                "the width of the column must be greater than or equal to 0",
+            )));
+        }
+        let ws = self.get_worksheet_mut(sheet)?;
+        set_col_props(ws, min, max, |col| {
+            col.width = Some(width);
+            col.custom_width = Some(true);
+        });
+        Ok(())
+    }
+
+    fn get_col_width(&self, sheet: &str, col: &str) -> Result<f64, ExcelizeError> {
+        let num = column_name_to_number(col)?;
+        let ws = self.get_worksheet(sheet)?;
+        if let Some(width) = get_col_props(ws, num).and_then(|col| col.width) {
+            return Ok(width);
+        }
+        match ws.sheet_format_pr {
+            Some(ref format_pr) => match format_pr.default_col_width {
+                Some(width) if width > 0.0 => Ok(width),
+                _ => Ok(DEFAULT_COL_WIDTH),
+            },
+            None => Ok(DEFAULT_COL_WIDTH),
+        }
+    }
+
+    fn set_col_visible(
+        &mut self,
+        sheet: &str,
+        columns: &str,
+        visible: bool,
+    ) -> Result<(), ExcelizeError> {
+        let (min, max) = parse_col_range(columns)?;
+        let ws = self.get_worksheet_mut(sheet)?;
+        set_col_props(ws, min, max, |col| {
+            col.hidden = if visible { None } else { Some(true) };
+        });
+        Ok(())
+    }
+
+    fn get_col_visible(&self, sheet: &str, col: &str) -> Result<bool, ExcelizeError> {
+        let num = column_name_to_number(col)?;
+        let ws = self.get_worksheet(sheet)?;
+        Ok(get_col_props(ws, num).and_then(|col| col.hidden) != Some(true))
+    }
+
+    fn set_col_outline_level(
+        &mut self,
+        sheet: &str,
+        col: &str,
+        level: u8,
+    ) -> Result<(), ExcelizeError> {
+        let num = column_name_to_number(col)?;
+        if !(1..=7).contains(&level) {
+            return Err(ExcelizeError::CommonError(String::from(
+                "invalid outline level",
+            )));
+        }
+        let ws = self.get_worksheet_mut(sheet)?;
+        set_col_props(ws, num, num, |col| col.outline_level = Some(level));
+        update_outline_level_col(ws);
+        Ok(())
+    }
+
+    fn get_col_outline_level(&self, sheet: &str, col: &str) -> Result<u8, ExcelizeError> {
+        let num = column_name_to_number(col)?;
+        let ws = self.get_worksheet(sheet)?;
+        Ok(get_col_props(ws, num)
+            .and_then(|col| col.outline_level)
+            .unwrap_or(0))
+    }
+
+    fn set_col_style(
+        &mut self,
+        sheet: &str,
+        columns: &str,
+        style: u32,
+    ) -> Result<(), ExcelizeError> {
+        let (min, max) = parse_col_range(columns)?;
+        let ws = self.get_worksheet_mut(sheet)?;
+        set_col_props(ws, min, max, |col| col.style = Some(style));
+        if let Some(ref mut rows) = ws.sheet_data.row {
+            for row in rows.iter_mut() {
+                for c in row.c.iter_mut() {
+                    let (col, _) = split_cell_name(&c.r)?;
+                    let col = column_name_to_number(&col)?;
+                    if col >= min && col <= max {
+                        c.s = Some(style);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_col_style(&self, sheet: &str, col: &str) -> Result<u32, ExcelizeError> {
+        let num = column_name_to_number(col)?;
+        let ws = self.get_worksheet(sheet)?;
+        Ok(get_col_props(ws, num)
+            .and_then(|col| col.style)
+            .unwrap_or(0))
+    }
+}
+
+// parse_col_range provides a function to parse the columns range, such as "D"
+// or "D:F", and returns the sorted pair of column numbers.
+pub(crate) fn parse_col_range(columns: &str) -> Result<(u32, u32), ExcelizeError> {
+    let (start, end) = match columns.split_once(':') {
+        Some((start, end)) => (column_name_to_number(start)?, column_name_to_number(end)?),
+        None => {
+            let col = column_name_to_number(columns)?;
+            (col, col)
+        }
+    };
+    Ok((start.min(end), start.max(end)))
+}
+
+// get_col_props provides a function to get the column properties span which
+// contains the given column number.
+pub(crate) fn get_col_props(ws: &XMLWorksheet, col: u32) -> Option<&CTCol> {
+    ws.cols
+        .as_ref()
+        .and_then(|cols| cols.col.iter().find(|c| c.min <= col && col <= c.max))
+}
+
+// set_col_props provides a function to apply the properties setter on the
+// columns in the range [min, max]. The existing spans overlapping with the
+// range will be split, the columns without properties in the range will be
+// created, and the adjacent spans with the same properties will be merged.
+pub(crate) fn set_col_props<F: Fn(&mut CTCol)>(ws: &mut XMLWorksheet, min: u32, max: u32, f: F) {
+    let mut cols: Vec<CTCol> = Vec::new();
+    let mut existing = ws.cols.take().map(|cols| cols.col).unwrap_or_default();
+    existing.sort_by_key(|col| col.min);
+    let mut next = min;
+    for col in existing {
+        if col.max < min || col.min > max {
+            cols.push(col);
+            continue;
+        }
+        if col.min > next {
+            let mut gap = CTCol {
+                min: next,
+                max: col.min - 1,
+                ..Default::default()
+            };
+            f(&mut gap);
+            cols.push(gap);
+        }
+        if col.min < min {
+            let mut left = col.clone();
+            left.max = min - 1;
+            cols.push(left);
+        }
+        if col.max > max {
+            let mut right = col.clone();
+            right.min = max + 1;
+            cols.push(right);
+        }
+        let mut inner = col;
+        inner.min = inner.min.max(min);
+        inner.max = inner.max.min(max);
+        next = next.max(inner.max + 1);
+        f(&mut inner);
+        cols.push(inner);
+    }
+    if next <= max {
+        let mut gap = CTCol {
+            min: next,
+            max,
+            ..Default::default()
+        };
+        f(&mut gap);
+        cols.push(gap);
+    }
+    cols.sort_by_key(|col| col.min);
+    let mut merged: Vec<CTCol> = Vec::with_capacity(cols.len());
+    for col in cols {
+        if let Some(last) = merged.last_mut() {
+            let mut same = col.clone();
+            same.min = last.min;
+            same.max = last.max;
+            if last.max + 1 == col.min && same == *last {
+                last.max = col.max;
+                continue;
+            }
+        }
+        merged.push(col);
+    }
+    if !merged.is_empty() {
+        ws.cols = Some(CTCols { col: merged });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cell;
+
+    #[test]
+    fn test_insert_and_remove_cols() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        wb.insert_cols("Sheet2", "B", 2).unwrap();
+        assert_eq!(wb.get_cell_value("Sheet2", 2, 6).unwrap(), "200");
+        let ws = &wb.worksheets["Sheet2"];
+        assert_eq!(ws.dimension.as_ref().unwrap().ref_attr, "A1:F11");
+        let cols: Vec<(u32, u32)> = ws
+            .cols
+            .as_ref()
+            .unwrap()
+            .col
+            .iter()
+            .map(|c| (c.min, c.max))
+            .collect();
+        assert_eq!(cols, vec![(5, 5), (9, 9), (11, 11)]);
+        let sheet1 = &wb.worksheets["Sheet1"];
+        let f = sheet1.sheet_data.row.as_ref().unwrap()[0].c[1]
+            .f
+            .as_ref()
+            .unwrap();
+        assert_eq!(f.content.as_deref(), Some("SUM(Sheet2!F2,Sheet2!F11)"));
+
+        wb.remove_col("Sheet2", "F").unwrap();
+        assert_eq!(wb.get_cell_value("Sheet2", 2, 6).unwrap(), "");
+        let sheet1 = &wb.worksheets["Sheet1"];
+        let f = sheet1.sheet_data.row.as_ref().unwrap()[0].c[1]
+            .f
+            .as_ref()
+            .unwrap();
+        assert_eq!(f.content.as_deref(), Some("SUM(Sheet2!#REF!,Sheet2!#REF!)"));
+
+        assert!(wb.insert_cols("Sheet2", "XFE", 1).is_err());
+        assert!(wb.insert_cols("Sheet2", "A", 0).is_err());
+        assert!(wb.remove_col("SheetN", "A").is_err());
+    }
+
+    #[test]
+    fn test_col_props() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        wb.set_col_width("Sheet2", "D", "B", 20.0).unwrap();
+        let spans = |wb: &Spreadsheet| -> Vec<(u32, u32)> {
+            let cols = &wb.worksheets["Sheet2"].cols.as_ref().unwrap().col;
+            cols.iter().map(|c| (c.min, c.max)).collect()
+        };
+        assert_eq!(spans(&wb), vec![(2, 4), (7, 7), (9, 9)]);
+        assert_eq!(wb.get_col_width("Sheet2", "C").unwrap(), 20.0);
+        assert_eq!(wb.get_col_width("Sheet2", "A").unwrap(), DEFAULT_COL_WIDTH);
+        assert!(wb.set_col_width("Sheet2", "A", "A", 255.5).is_err());
+        assert!(wb.set_col_width("Sheet2", "A", "A", f64::NAN).is_err());
+        assert!(wb.set_col_width("Sheet2", "A", "A", f64::INFINITY).is_err());
+        assert_eq!(wb.get_col_width("Sheet2", "A").unwrap(), DEFAULT_COL_WIDTH);
+
+        wb.set_col_visible("Sheet2", "C", false).unwrap();
+        assert_eq!(spans(&wb), vec![(2, 2), (3, 3), (4, 4), (7, 7), (9, 9)]);
+        assert!(!wb.get_col_visible("Sheet2", "C").unwrap());
+        wb.set_col_visible("Sheet2", "C", true).unwrap();
+        assert_eq!(spans(&wb), vec![(2, 4), (7, 7), (9, 9)]);
+        assert!(wb.get_col_visible("Sheet2", "C").unwrap());
+
+        wb.set_col_outline_level("Sheet2", "H", 2).unwrap();
+        assert_eq!(wb.get_col_outline_level("Sheet2", "H").unwrap(), 2);
+        assert!(wb.set_col_outline_level("Sheet2", "H", 8).is_err());
+
+        wb.set_col_style("Sheet2", "C:D", 3).unwrap();
+        assert_eq!(wb.get_col_style("Sheet2", "D").unwrap(), 3);
+        let row = &wb.worksheets["Sheet2"].sheet_data.row.as_ref().unwrap()[1];
+        assert_eq!(row.c[3].s, Some(3));
+        assert_eq!(row.c[1].s, Some(1));
+        assert!(wb.set_col_style("SheetN", "A", 1).is_err());
+    }
+}
@@ -0,0 +1,819 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use quick_xml::de::from_str;
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::HashSet;
+
+use crate::{
+    add_content_type_default, add_content_type_override, cell_name_to_coordinates,
+    coordinates_to_cell_name, delete_content_type_override, get_relative_target, get_rels_path,
+    get_sheet_threaded_comments_path, xml_comments, CTLegacyDrawing, CTRst, ExcelizeError, RElt,
+    RPrElt, Rels, Spreadsheet, Worksheet, SOURCE_RELATIONSHIP_COMMENTS,
+    SOURCE_RELATIONSHIP_DRAWING_VML, TOTAL_CELL_CHARS,
+};
+
+static CONTENT_TYPE_COMMENTS: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.comments+xml";
+static CONTENT_TYPE_VML: &str = "application/vnd.openxmlformats-officedocument.vmlDrawing";
+static MAX_AUTHOR_LENGTH: usize = 255;
+
+/// Comment directly maps the settings of a cell comment. The text is the
+/// plain text of the comment, and the paragraph is the rich text runs of the
+/// comment which will be used instead of the text if it's not empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Comment {
+    pub author: String,
+    pub cell: String,
+    pub text: String,
+    pub paragraph: Vec<RichTextRun>,
+}
+
+/// RichTextRun directly maps the settings of a rich text run, the font is the
+/// run properties of the text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichTextRun {
+    pub text: String,
+    pub font: Option<RPrElt>,
+}
+
+pub trait Comments {
+    fn comments_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    /// add_comment provides a function to add a comment (note) to the cell
+    /// by given worksheet name and the comment settings. The comments part
+    /// and the VML drawing part which is required to show the notes will be
+    /// created with the relationships and the content types when the sheet
+    /// doesn't have comments. For example, add a comment in Sheet1!A30:
+    ///
+    /// ```ignore
+    /// spreadsheet.add_comment(
+    ///     "Sheet1",
+    ///     &Comment {
+    ///         author: String::from("Excelize"),
+    ///         cell: String::from("A30"),
+    ///         text: String::from("This is a comment."),
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// ```
+    fn add_comment(&mut self, sheet: &str, comment: &Comment) -> Result<(), ExcelizeError>;
+    /// get_comments provides a function to get the comments of the worksheet
+    /// by given worksheet name.
+    fn get_comments(&self, sheet: &str) -> Result<Vec<Comment>, ExcelizeError>;
+    /// delete_comment provides a function to delete the comment of the cell by
+    /// given worksheet name and cell reference. The comments part and the VML
+    /// drawing part will be removed when the last comment of the worksheet is
+    /// deleted.
+    fn delete_comment(&mut self, sheet: &str, cell: &str) -> Result<(), ExcelizeError>;
+}
+
+impl Comments for Spreadsheet {
+    fn comments_reader(&mut self, path: &str) -> Result<(), ExcelizeError> {
+        let comments: xml_comments::XMLComments;
+        match self.comments.get_key_value(path) {
+            Some(_) => Ok(()),
+            None => {
+                if let Some(buf) = self.file.get_key_value(path) {
+                    let s = match std::str::from_utf8(buf.1) {
+                        Ok(v) => v,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    };
+                    match from_str(s) {
+                        Ok(o) => comments = o,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    }
+                    self.comments.insert(String::from(path), comments);
+                    return Ok(());
+                }
+                Err(ExcelizeError::CommonError(String::from("comments is none")))
+            }
+        }
+    }
+
+    fn add_comment(&mut self, sheet: &str, comment: &Comment) -> Result<(), ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(&comment.cell)?;
+        let cell = coordinates_to_cell_name(col, row)?;
+        let length = comment.text.chars().count()
+            + comment
+                .paragraph
+                .iter()
+                .map(|run| run.text.chars().count())
+                .sum::<usize>();
+        if length > TOTAL_CELL_CHARS {
+            return Err(ExcelizeError::CommonError(format!(
+                "comment text length exceeds the limit {}",
+                TOTAL_CELL_CHARS
+            )));
+        }
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        let comments_path = match get_sheet_comments_path(self, &sheet_path) {
+            Some(path) => path,
+            None => {
+                let path = format!(
+                    "xl/comments{}.xml",
+                    next_part_index(self, "xl/comments", ".xml")
+                );
+                let target = get_relative_target(&sheet_path, &path);
+                self.add_rels(
+                    &get_rels_path(&sheet_path),
+                    SOURCE_RELATIONSHIP_COMMENTS,
+                    &target,
+                    None,
+                );
+                if let Some(ref mut types) = self.content_type {
+                    add_content_type_override(types, &path, CONTENT_TYPE_COMMENTS);
+                }
+                path
+            }
+        };
+        self.comments_reader(&comments_path).ok();
+        let comments = self.comments.entry(comments_path).or_default();
+        if comments
+            .comment_list
+            .comment
+            .iter()
+            .any(|c| c.ref_attr == cell)
+        {
+            return Err(ExcelizeError::CommonError(format!(
+                "the cell {} already has a comment",
+                cell
+            )));
+        }
+        let author: String = comment.author.chars().take(MAX_AUTHOR_LENGTH).collect();
+        let author_id = match comments.authors.author.iter().position(|a| *a == author) {
+            Some(i) => i,
+            None => {
+                comments.authors.author.push(author);
+                comments.authors.author.len() - 1
+            }
+        };
+        let mut text = CTRst::default();
+        match comment.paragraph.is_empty() {
+            true => text.t = Some(vec![comment.text.clone()]),
+            false => {
+                let mut runs = Vec::new();
+                if !comment.text.is_empty() {
+                    runs.push(RElt {
+                        t: comment.text.clone(),
+                        r_pr: None,
+                    });
+                }
+                runs.extend(comment.paragraph.iter().map(|run| RElt {
+                    t: run.text.clone(),
+                    r_pr: run.font.clone().map(|font| vec![font]),
+                }));
+                text.r = Some(runs);
+            }
+        }
+        comments
+            .comment_list
+            .comment
+            .push(xml_comments::XMLComment {
+                ref_attr: cell,
+                author_id: author_id as u32,
+                text,
+            });
+
+        let vml_path = match prepare_vml_drawing(self, sheet, &sheet_path)? {
+            Some(path) => path,
+            None => {
+                let index = next_part_index(self, "xl/drawings/vmlDrawing", ".vml");
+                let path = format!("xl/drawings/vmlDrawing{}.vml", index);
+                let vml = new_vml_drawing(next_vml_idmap(self));
+                self.file.insert(path.clone(), vml.into_bytes());
+                let target = get_relative_target(&sheet_path, &path);
+                let rid = self.add_rels(
+                    &get_rels_path(&sheet_path),
+                    SOURCE_RELATIONSHIP_DRAWING_VML,
+                    &target,
+                    None,
+                );
+                self.get_worksheet_mut(sheet)?.legacy_drawing = Some(CTLegacyDrawing { rid });
+                if let Some(ref mut types) = self.content_type {
+                    add_content_type_default(types, "vml", CONTENT_TYPE_VML);
+                }
+                path
+            }
+        };
+        let vml = get_vml_drawing(self, &vml_path);
+        self.file
+            .insert(vml_path, add_vml_shape(&vml, col, row)?.into_bytes());
+        Ok(())
+    }
+
+    fn get_comments(&self, sheet: &str) -> Result<Vec<Comment>, ExcelizeError> {
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        let comments = match get_sheet_comments_path(self, &sheet_path)
+            .and_then(|path| self.comments.get(&path))
+        {
+            Some(comments) => comments,
+            None => return Ok(Vec::new()),
+        };
+        Ok(comments
+            .comment_list
+            .comment
+            .iter()
+            .map(|c| {
+                let paragraph: Vec<RichTextRun> = c
+                    .text
+                    .r
+                    .iter()
+                    .flatten()
+                    .map(|run| RichTextRun {
+                        text: run.t.clone(),
+                        font: run.r_pr.as_ref().and_then(|r_pr| r_pr.first().cloned()),
+                    })
+                    .collect();
+                let text = match c.text.t {
+                    Some(ref t) => t.concat(),
+                    None => paragraph.iter().map(|run| run.text.as_str()).collect(),
+                };
+                Comment {
+                    author: comments
+                        .authors
+                        .author
+                        .get(c.author_id as usize)
+                        .cloned()
+                        .unwrap_or_default(),
+                    cell: c.ref_attr.clone(),
+                    text,
+                    paragraph,
+                }
+            })
+            .collect())
+    }
+
+    fn delete_comment(&mut self, sheet: &str, cell: &str) -> Result<(), ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        let cell = coordinates_to_cell_name(col, row)?;
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        let comments_path = get_sheet_comments_path(self, &sheet_path);
+        let comments = comments_path
+            .as_ref()
+            .and_then(|path| self.comments.get_mut(path));
+        let deleted = comments.map_or(false, |comments| {
+            let count = comments.comment_list.comment.len();
+            comments.comment_list.comment.retain(|c| c.ref_attr != cell);
+            comments.comment_list.comment.len() != count
+        });
+        if !deleted {
+            return Err(ExcelizeError::CommonError(format!(
+                "the cell {} doesn't have a comment",
+                cell
+            )));
+        }
+        if let Some(vml_path) = prepare_vml_drawing(self, sheet, &sheet_path)? {
+            let vml = delete_vml_shape(&get_vml_drawing(self, &vml_path), col, row)?;
+            self.file.insert(vml_path, vml.into_bytes());
+        }
+        if let Some(path) =
+            comments_path.filter(|path| self.comments[path].comment_list.comment.is_empty())
+        {
+            delete_comments_part(self, sheet, &sheet_path, &path)?;
+        }
+        Ok(())
+    }
+}
+
+// delete_comments_part provides a function to delete the empty comments part
+// of the worksheet with its relationship and content type, the VML drawing
+// part and the legacy drawing of the worksheet will be deleted as well if
+// there are no shapes left in the drawing.
+fn delete_comments_part(
+    spreadsheet: &mut Spreadsheet,
+    sheet: &str,
+    sheet_path: &str,
+    path: &str,
+) -> Result<(), ExcelizeError> {
+    let rels_path = get_rels_path(sheet_path);
+    for (rid, _) in spreadsheet
+        .get_rels_by_type(sheet_path, SOURCE_RELATIONSHIP_COMMENTS)
+        .into_iter()
+        .filter(|(_, target)| target == path)
+    {
+        spreadsheet.delete_rels(&rels_path, &rid);
+    }
+    if let Some(ref mut types) = spreadsheet.content_type {
+        delete_content_type_override(types, path);
+    }
+    spreadsheet.comments.remove(path);
+    spreadsheet.file.remove(path);
+    let vml_path = match prepare_vml_drawing(spreadsheet, sheet, sheet_path)? {
+        Some(vml_path) => vml_path,
+        None => return Ok(()),
+    };
+    let parts = read_vml(&get_vml_drawing(spreadsheet, &vml_path))?;
+    if parts.iter().any(|part| matches!(part, VMLPart::Shape(_))) {
+        return Ok(());
+    }
+    let ws = spreadsheet.get_worksheet_mut(sheet)?;
+    if let Some(drawing) = ws.legacy_drawing.take() {
+        spreadsheet.delete_rels(&rels_path, &drawing.rid);
+    }
+    spreadsheet.file.remove(&vml_path);
+    Ok(())
+}
+
+// get_sheet_comments_path provides a function to get the path of the comments
+// part of the worksheet by given worksheet part path.
+pub(crate) fn get_sheet_comments_path(
+    spreadsheet: &Spreadsheet,
+    sheet_path: &str,
+) -> Option<String> {
+    spreadsheet
+        .get_rels_by_type(sheet_path, SOURCE_RELATIONSHIP_COMMENTS)
+        .into_iter()
+        .next()
+        .map(|(_, path)| path)
+}
+
+// move_comments provides a function to move the comments of the worksheet,
+// their shapes in the VML drawing and the threaded comments by given function
+// which maps the column and row number of the cell of each comment to the new
+// one, the comment will be deleted if the function returns None. The replies
+// of the deleted threads are deleted as well.
+pub(crate) fn move_comments<F: Fn(u32, u32) -> Option<(u32, u32)>>(
+    spreadsheet: &mut Spreadsheet,
+    sheet: &str,
+    f: F,
+) -> Result<(), ExcelizeError> {
+    let sheet_path = spreadsheet.get_sheet_xml_path(sheet)?;
+    if let Some(comments) = get_sheet_comments_path(spreadsheet, &sheet_path)
+        .and_then(|path| spreadsheet.comments.get_mut(&path))
+    {
+        comments.comment_list.comment.retain_mut(|comment| {
+            match move_cell_ref(&comment.ref_attr, &f) {
+                Some(cell) => {
+                    comment.ref_attr = cell;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+    if let Some(comments) = get_sheet_threaded_comments_path(spreadsheet, &sheet_path)
+        .and_then(|path| spreadsheet.threaded_comments.get_mut(&path))
+    {
+        comments.threaded_comment.retain_mut(|comment| {
+            match move_cell_ref(&comment.ref_attr, &f) {
+                Some(cell) => {
+                    comment.ref_attr = cell;
+                    true
+                }
+                None => false,
+            }
+        });
+        let ids: HashSet<String> = comments
+            .threaded_comment
+            .iter()
+            .filter(|c| c.parent_id.is_none())
+            .map(|c| c.id.clone())
+            .collect();
+        comments
+            .threaded_comment
+            .retain(|c| c.parent_id.as_ref().map_or(true, |id| ids.contains(id)));
+    }
+    if let Some(vml_path) = prepare_vml_drawing(spreadsheet, sheet, &sheet_path)?
+        .filter(|path| spreadsheet.file.contains_key(path))
+    {
+        let vml = get_vml_drawing(spreadsheet, &vml_path);
+        let vml = adjust_vml_shapes(&vml, f)?;
+        spreadsheet.file.insert(vml_path, vml.into_bytes());
+    }
+    Ok(())
+}
+
+// move_cell_ref provides a function to map the cell reference by given
+// function which maps the column and row number of the cell.
+fn move_cell_ref<F: Fn(u32, u32) -> Option<(u32, u32)>>(cell: &str, f: &F) -> Option<String> {
+    let (col, row) = cell_name_to_coordinates(cell).ok()?;
+    let (col, row) = f(col, row)?;
+    coordinates_to_cell_name(col, row).ok()
+}
+
+// prepare_vml_drawing provides a function to get the path of the VML drawing
+// part of the worksheet, returns None if the worksheet doesn't have the legacy
+// drawing.
+fn prepare_vml_drawing(
+    spreadsheet: &Spreadsheet,
+    sheet: &str,
+    sheet_path: &str,
+) -> Result<Option<String>, ExcelizeError> {
+    Ok(spreadsheet
+        .get_worksheet(sheet)?
+        .legacy_drawing
+        .as_ref()
+        .and_then(|drawing| spreadsheet.get_rels_target_path(sheet_path, &drawing.rid)))
+}
+
+// next_part_index provides a function to get the next index of the part name
+// with the given prefix and suffix, for example 2 for xl/comments2.xml when
+// the xl/comments1.xml exists.
+pub(crate) fn next_part_index(spreadsheet: &Spreadsheet, prefix: &str, suffix: &str) -> usize {
+    spreadsheet
+        .file
+        .keys()
+        .chain(spreadsheet.comments.keys())
+        .chain(spreadsheet.threaded_comments.keys())
+        .chain(spreadsheet.tables.keys())
+        .chain(spreadsheet.pivot_cache_definitions.keys())
+        .chain(spreadsheet.pivot_cache_records.keys())
+        .chain(spreadsheet.pivot_tables.keys())
+        .filter_map(|path| {
+            path.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse::<usize>()
+                .ok()
+        })
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+// get_vml_drawing provides a function to get the content of the VML drawing
+// part by given path, a new drawing with the next free ID map will be created
+// if the part doesn't exist.
+fn get_vml_drawing(spreadsheet: &Spreadsheet, path: &str) -> String {
+    spreadsheet
+        .file
+        .get(path)
+        .map(|buf| String::from_utf8_lossy(buf).into_owned())
+        .unwrap_or_else(|| new_vml_drawing(next_vml_idmap(spreadsheet)))
+}
+
+// next_vml_idmap provides a function to get the next free ID map of the VML
+// drawings in the workbook, the shape IDs of each drawing are allocated in
+// blocks of 1024 by the ID map.
+fn next_vml_idmap(spreadsheet: &Spreadsheet) -> usize {
+    spreadsheet
+        .file
+        .iter()
+        .filter(|(path, _)| path.ends_with(".vml"))
+        .filter_map(|(_, buf)| read_vml(&String::from_utf8_lossy(buf)).ok())
+        .flat_map(|parts| vml_idmaps(&parts))
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+// new_vml_drawing provides a function to create the VML drawing part with the
+// shape type of the comments.
+fn new_vml_drawing(idmap: usize) -> String {
+    format!(
+        r##"<xml xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office" xmlns:x="urn:schemas-microsoft-com:office:excel"><o:shapelayout v:ext="edit"><o:idmap v:ext="edit" data="{}"/></o:shapelayout><v:shapetype id="_x0000_t202" coordsize="21600,21600" o:spt="202" path="m,l,21600r21600,l21600,xe"><v:stroke joinstyle="miter"/><v:path gradientshapeok="t" o:connecttype="rect"/></v:shapetype></xml>"##,
+        idmap
+    )
+}
+
+// VMLPart is a part of the VML drawing, which is either an event out of the
+// shapes or the events of a whole v:shape element.
+enum VMLPart {
+    Event(Event<'static>),
+    Shape(Vec<Event<'static>>),
+}
+
+// read_vml provides a function to read the VML drawing into the parts, the
+// events of each v:shape element are grouped into one part. The end names are
+// not checked since the VML drawings written by Excel are not always
+// well-formed.
+fn read_vml(vml: &str) -> Result<Vec<VMLPart>, ExcelizeError> {
+    let mut reader = Reader::from_str(vml);
+    reader.check_end_names(false);
+    let mut buf = Vec::new();
+    let mut parts = Vec::new();
+    let mut shape: Option<Vec<Event<'static>>> = None;
+    loop {
+        let event = match reader.read_event(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(event) => event.into_owned(),
+            Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+        };
+        buf.clear();
+        match shape {
+            Some(ref mut events) => {
+                let end = matches!(event, Event::End(ref e) if e.name() == b"v:shape");
+                events.push(event);
+                if end {
+                    let events = std::mem::take(events);
+                    shape = None;
+                    parts.push(VMLPart::Shape(events));
+                }
+            }
+            None if matches!(event, Event::Start(ref e) if e.name() == b"v:shape") => {
+                shape = Some(vec![event])
+            }
+            None => parts.push(VMLPart::Event(event)),
+        }
+    }
+    if let Some(events) = shape {
+        parts.push(VMLPart::Shape(events));
+    }
+    Ok(parts)
+}
+
+// write_vml provides a function to write the parts of the VML drawing, the
+// events which are not changed are written as they were read.
+fn write_vml(parts: &[VMLPart]) -> Result<String, ExcelizeError> {
+    let mut writer = Writer::new(Vec::new());
+    for part in parts {
+        let events = match part {
+            VMLPart::Event(event) => std::slice::from_ref(event),
+            VMLPart::Shape(events) => &events[..],
+        };
+        for event in events {
+            if let Err(e) = writer.write_event(event) {
+                return Err(ExcelizeError::CommonError(e.to_string()));
+            }
+        }
+    }
+    match String::from_utf8(writer.into_inner()) {
+        Ok(vml) => Ok(vml),
+        Err(e) => Err(ExcelizeError::CommonError(e.to_string())),
+    }
+}
+
+// vml_idmaps provides a function to get the ID maps in the shape layout of
+// the VML drawing, the data of the ID map is a comma separated list.
+fn vml_idmaps(parts: &[VMLPart]) -> Vec<usize> {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            VMLPart::Event(Event::Start(e)) | VMLPart::Event(Event::Empty(e))
+                if e.name() == b"o:idmap" =>
+            {
+                vml_attr(e, b"data")
+            }
+            _ => None,
+        })
+        .flat_map(|data| {
+            data.split(',')
+                .filter_map(|id| id.trim().parse().ok())
+                .collect::<Vec<usize>>()
+        })
+        .collect()
+}
+
+// vml_attr provides a function to get the unescaped value of the attribute of
+// the element in the VML drawing by given attribute name.
+fn vml_attr(e: &BytesStart, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key == name)
+        .and_then(|attr| {
+            let value = attr.unescaped_value().ok()?;
+            Some(String::from_utf8_lossy(&value).into_owned())
+        })
+}
+
+// add_vml_shape provides a function to add the comment shape of the cell into
+// the VML drawing before the end of the root element, the shape ID is the
+// next of the maximum shape ID in the drawing.
+fn add_vml_shape(vml: &str, col: u32, row: u32) -> Result<String, ExcelizeError> {
+    let mut parts = read_vml(vml)?;
+    let idmap = vml_idmaps(&parts).into_iter().next().unwrap_or(1);
+    let id = parts
+        .iter()
+        .filter_map(|part| match part {
+            VMLPart::Shape(events) => match events.first() {
+                Some(Event::Start(e)) => vml_attr(e, b"id"),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter_map(|id| id.strip_prefix("_x0000_s")?.parse::<usize>().ok())
+        .max()
+        .unwrap_or(idmap * 1024)
+        + 1;
+    let shape = format!(
+        r##"<v:shape id="_x0000_s{}" type="#_x0000_t202" style="position:absolute;margin-left:59.25pt;margin-top:1.5pt;width:108pt;height:59.25pt;z-index:1;visibility:hidden" fillcolor="#fbf6d6" o:insetmode="auto" strokecolor="#edeaa1"><v:fill color2="#fbfe82" angle="-180" type="gradient"><o:fill v:ext="view" type="gradientUnscaled"/></v:fill><v:shadow on="t" color="black" obscured="t"/><v:path o:connecttype="none"/><v:textbox style="mso-direction-alt:auto"><div style="text-align:left"></div></v:textbox><x:ClientData ObjectType="Note"><x:MoveWithCells/><x:SizeWithCells/><x:Anchor>{}, 15, {}, 2, {}, 15, {}, 16</x:Anchor><x:AutoFill>False</x:AutoFill><x:Row>{}</x:Row><x:Column>{}</x:Column></x:ClientData></v:shape>"##,
+        id,
+        col,
+        row.saturating_sub(2),
+        col + 2,
+        row + 2,
+        row - 1,
+        col - 1
+    );
+    let end = parts
+        .iter()
+        .rposition(|part| matches!(part, VMLPart::Event(Event::End(e)) if e.name() == b"xml"));
+    let end = end.unwrap_or(parts.len());
+    parts.splice(end..end, read_vml(&shape)?);
+    write_vml(&parts)
+}
+
+// delete_vml_shape provides a function to delete the comment shapes of the
+// cell from the VML drawing.
+fn delete_vml_shape(vml: &str, col: u32, row: u32) -> Result<String, ExcelizeError> {
+    adjust_vml_shapes(vml, |c, r| (c != col || r != row).then_some((c, r)))
+}
+
+// adjust_vml_shapes provides a function to move the comment shapes in the VML
+// drawing by given function which maps the column and row number of the cell
+// of each shape to the new one, the shape will be deleted if the function
+// returns None. The shapes without the cell are kept as is.
+fn adjust_vml_shapes<F: Fn(u32, u32) -> Option<(u32, u32)>>(
+    vml: &str,
+    f: F,
+) -> Result<String, ExcelizeError> {
+    let mut parts = read_vml(vml)?;
+    parts.retain_mut(|part| {
+        let shape = match part {
+            VMLPart::Shape(shape) => shape,
+            VMLPart::Event(_) => return true,
+        };
+        let cell = vml_value(shape, b"x:Column").zip(vml_value(shape, b"x:Row"));
+        match cell {
+            Some((col, row)) => match f(col + 1, row + 1) {
+                Some((new_col, new_row)) => {
+                    move_vml_shape(shape, (col, row), (new_col - 1, new_row - 1));
+                    true
+                }
+                None => false,
+            },
+            None => true,
+        }
+    });
+    write_vml(&parts)
+}
+
+// vml_text provides a function to get the index and the unescaped content of
+// the text event in the element of the VML shape by given tag name.
+fn vml_text(shape: &[Event], tag: &[u8]) -> Option<(usize, String)> {
+    let start = shape
+        .iter()
+        .position(|event| matches!(event, Event::Start(e) if e.name() == tag))?;
+    match shape.get(start + 1) {
+        Some(Event::Text(e)) => {
+            let text = e.unescaped().ok()?;
+            Some((start + 1, String::from_utf8_lossy(&text).into_owned()))
+        }
+        _ => None,
+    }
+}
+
+// vml_value provides a function to get the zero-based number in the element
+// of the VML shape by given tag name.
+fn vml_value(shape: &[Event], tag: &[u8]) -> Option<u32> {
+    vml_text(shape, tag)?.1.trim().parse().ok()
+}
+
+// set_vml_text provides a function to set the content of the text event in the
+// element of the VML shape by given tag name.
+fn set_vml_text(shape: &mut [Event<'static>], tag: &[u8], text: &str) {
+    if let Some((i, _)) = vml_text(shape, tag) {
+        shape[i] = Event::Text(BytesText::from_plain_str(text).into_owned());
+    }
+}
+
+// move_vml_shape provides a function to update the zero-based column and row
+// number of the cell and the anchor of the comment shape.
+fn move_vml_shape(shape: &mut [Event<'static>], from: (u32, u32), to: (u32, u32)) {
+    let (col_delta, row_delta) = (to.0 as i64 - from.0 as i64, to.1 as i64 - from.1 as i64);
+    set_vml_text(shape, b"x:Column", &to.0.to_string());
+    set_vml_text(shape, b"x:Row", &to.1.to_string());
+    let anchor = vml_text(shape, b"x:Anchor").and_then(|(_, anchor)| {
+        anchor
+            .split(',')
+            .enumerate()
+            .map(|(i, value)| {
+                let value: i64 = value.trim().parse().ok()?;
+                let delta = match i {
+                    0 | 4 => col_delta,
+                    2 | 6 => row_delta,
+                    _ => 0,
+                };
+                Some((value + delta).max(0).to_string())
+            })
+            .collect::<Option<Vec<String>>>()
+    });
+    if let Some(anchor) = anchor {
+        set_vml_text(shape, b"x:Anchor", &anchor.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{xml_content_types, Col, Rows, STXBool};
+
+    #[test]
+    fn test_comments() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let comments = wb.get_comments("Sheet1").unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author, "Microsoft Office User");
+        assert_eq!(comments[0].cell, "A22");
+        assert!(comments[0].text.starts_with("Hyperlink test"));
+        assert!(wb
+            .add_comment(
+                "Sheet1",
+                &Comment {
+                    cell: String::from("A22"),
+                    ..Default::default()
+                }
+            )
+            .is_err());
+
+        wb.add_comment(
+            "Sheet1",
+            &Comment {
+                author: String::from("Excelize"),
+                cell: String::from("B2"),
+                paragraph: vec![
+                    RichTextRun {
+                        text: String::from("Excelize: "),
+                        font: Some(RPrElt {
+                            b: Some(STXBool { val: None }),
+                            ..Default::default()
+                        }),
+                    },
+                    RichTextRun {
+                        text: String::from("This is a comment."),
+                        font: None,
+                    },
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let comments = wb.get_comments("Sheet1").unwrap();
+        assert_eq!(comments[1].author, "Excelize");
+        assert_eq!(comments[1].text, "Excelize: This is a comment.");
+        let vml = get_vml_drawing(&wb, "xl/drawings/vmlDrawing1.vml");
+        assert!(vml.contains("_x0000_s1027"));
+
+        assert!(wb.get_comments("Sheet2").unwrap().is_empty());
+        wb.add_comment(
+            "Sheet2",
+            &Comment {
+                author: String::from("Excelize"),
+                cell: String::from("C3"),
+                text: String::from("Note"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(wb.get_comments("Sheet2").unwrap()[0].text, "Note");
+        assert!(wb.comments.contains_key("xl/comments2.xml"));
+        let vml = get_vml_drawing(&wb, "xl/drawings/vmlDrawing2.vml");
+        assert!(vml.contains("<x:Row>2</x:Row><x:Column>2</x:Column>"));
+        assert!(vml.contains(r#"<o:idmap v:ext="edit" data="2"/>"#));
+        assert!(vml.contains(r#"<v:shape id="_x0000_s2049""#));
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        assert!(ws.legacy_drawing.is_some());
+
+        // the parts of the comments are deleted with the last comment
+        wb.delete_comment("Sheet2", "C3").unwrap();
+        assert!(wb.delete_comment("Sheet2", "C3").is_err());
+        assert!(wb.get_comments("Sheet2").unwrap().is_empty());
+        assert!(!wb.comments.contains_key("xl/comments2.xml"));
+        assert!(!wb.file.contains_key("xl/drawings/vmlDrawing2.vml"));
+        assert!(wb.get_worksheet("Sheet2").unwrap().legacy_drawing.is_none());
+        assert!(wb
+            .get_rels_by_type("xl/worksheets/sheet2.xml", SOURCE_RELATIONSHIP_COMMENTS)
+            .is_empty());
+        assert!(wb
+            .get_rels_by_type("xl/worksheets/sheet2.xml", SOURCE_RELATIONSHIP_DRAWING_VML)
+            .is_empty());
+        let types = wb.content_type.as_ref().unwrap();
+        assert!(!types.fields.iter().any(|field| matches!(
+            field,
+            xml_content_types::XMLOverride::Override { part_name, .. }
+                if part_name == "/xl/comments2.xml"
+        )));
+
+        wb.insert_rows("Sheet1", 10, 2).unwrap();
+        wb.remove_col("Sheet1", "B").unwrap();
+        let comments = wb.get_comments("Sheet1").unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].cell, "A24");
+        let vml = get_vml_drawing(&wb, "xl/drawings/vmlDrawing1.vml");
+        assert_eq!(vml.matches("<v:shape ").count(), 1);
+        assert!(vml.contains("<x:Anchor>0, 43, 23, 9, 1, 63, 25, 1</x:Anchor>"));
+        assert!(vml.contains("<x:Row>23</x:Row>"));
+    }
+
+    #[test]
+    fn test_adjust_vml_shapes() {
+        let vml = "<xml xmlns:v='urn:schemas-microsoft-com:vml'>\n <v:shapetype id='_x0000_t202'/>\n <v:shape id='_x0000_s1025' type='#_x0000_t201'><x:ClientData ObjectType='Button'><x:Anchor>1, 0, 1, 0, 2, 0, 2, 0</x:Anchor></x:ClientData></v:shape>\n <v:shape id='_x0000_s1026'><v:textbox><div>x:Row</div><br></v:textbox><x:ClientData ObjectType='Note'><x:Anchor>\n  3, 15, 0, 2, 5, 15, 4, 16</x:Anchor><x:Row>1</x:Row><x:Column>2</x:Column></x:ClientData></v:shape>\n</xml>";
+        // the shapes without the cell and the content out of the shapes are
+        // kept as is
+        let moved = adjust_vml_shapes(vml, |col, row| Some((col, row + 1))).unwrap();
+        assert_eq!(
+            moved,
+            vml.replace("<x:Row>1</x:Row>", "<x:Row>2</x:Row>")
+                .replace("\n  3, 15, 0, 2, 5, 15, 4, 16", "3, 15, 1, 2, 5, 15, 5, 16")
+        );
+        let deleted = delete_vml_shape(vml, 3, 2).unwrap();
+        assert_eq!(deleted.matches("<v:shape ").count(), 1);
+        assert!(deleted.contains("ObjectType='Button'"));
+        let added = add_vml_shape(vml, 1, 1).unwrap();
+        assert!(added.contains(r#"<v:shape id="_x0000_s1027""#));
+        assert!(added.ends_with("</v:shape></xml>"));
+    }
+}
@@ -0,0 +1,1436 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::{
+    calc::{calc_formula, compare_formula_values, get_cell_formula_value, FormulaValue},
+    cell_name_to_coordinates, coordinates_to_cell_name, join_sqref, month_serial, new_guid,
+    offset_formula, parse_sqref, serial_to_datetime, today_serial, xml_worksheet, CTColor, CTDxf,
+    ExcelizeError, Spreadsheet, Worksheet, XMLWorksheet,
+};
+
+static EXT_URI_CONDITIONAL_FORMATTING_RULE_ID: &str = "{B025F937-C7B1-47D3-B67F-A62EFF666E3E}";
+static EXT_URI_CONDITIONAL_FORMATTINGS: &str = "{78C0D931-6437-407d-A8EE-F0AAD7539E65}";
+static CELL_IS_CRITERIA: [(&str, &str, &str); 8] = [
+    ("between", "between", "between"),
+    ("notBetween", "not between", "not between"),
+    ("equal", "equal to", "=="),
+    ("notEqual", "not equal to", "!="),
+    ("greaterThan", "greater than", ">"),
+    ("lessThan", "less than", "<"),
+    ("greaterThanOrEqual", "greater than or equal to", ">="),
+    ("lessThanOrEqual", "less than or equal to", "<="),
+];
+static TEXT_CRITERIA: [(&str, &str, &str); 4] = [
+    ("containsText", "containsText", "containing"),
+    ("notContainsText", "notContains", "not containing"),
+    ("beginsWith", "beginsWith", "begins with"),
+    ("endsWith", "endsWith", "ends with"),
+];
+static TIME_PERIOD_CRITERIA: [(&str, &str); 10] = [
+    ("yesterday", "yesterday"),
+    ("today", "today"),
+    ("tomorrow", "tomorrow"),
+    ("last7Days", "last 7 days"),
+    ("lastWeek", "last week"),
+    ("thisWeek", "this week"),
+    ("nextWeek", "next week"),
+    ("lastMonth", "last month"),
+    ("thisMonth", "this month"),
+    ("nextMonth", "next month"),
+];
+static BLANK_ERROR_TYPES: [(&str, &str); 4] = [
+    ("containsBlanks", "blanks"),
+    ("notContainsBlanks", "no_blanks"),
+    ("containsErrors", "errors"),
+    ("notContainsErrors", "no_errors"),
+];
+static ICON_STYLES: [&str; 17] = [
+    "3Arrows",
+    "3ArrowsGray",
+    "3Flags",
+    "3TrafficLights1",
+    "3TrafficLights2",
+    "3Signs",
+    "3Symbols",
+    "3Symbols2",
+    "4Arrows",
+    "4ArrowsGray",
+    "4RedToBlack",
+    "4Rating",
+    "4TrafficLights",
+    "5Arrows",
+    "5ArrowsGray",
+    "5Rating",
+    "5Quarters",
+];
+
+/// ConditionalFormatOptions directly maps the settings of the conditional
+/// formatting rule. The type is one of "cell", "formula", "text",
+/// "time_period", "top", "bottom", "average", "duplicate", "unique",
+/// "blanks", "no_blanks", "errors", "no_errors", "2_color_scale",
+/// "3_color_scale", "data_bar" and "icon_set". The format is the index of the
+/// differential format created by the new_conditional_style function. The
+/// colors are in the "#RRGGBB" format. The rule will be inserted at the
+/// priority if it's specified, otherwise it will be evaluated after the
+/// existing rules of the worksheet.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConditionalFormatOptions {
+    pub type_attr: String,
+    pub above_average: bool,
+    pub percent: bool,
+    pub format: Option<u32>,
+    pub criteria: String,
+    pub value: String,
+    pub min_type: String,
+    pub mid_type: String,
+    pub max_type: String,
+    pub min_value: String,
+    pub mid_value: String,
+    pub max_value: String,
+    pub min_color: String,
+    pub mid_color: String,
+    pub max_color: String,
+    pub bar_color: String,
+    pub bar_border_color: String,
+    pub bar_direction: String,
+    pub bar_only: bool,
+    pub bar_solid: bool,
+    pub icon_style: String,
+    pub reverse_icons: bool,
+    pub icons_only: bool,
+    pub stop_if_true: bool,
+    pub priority: Option<i32>,
+}
+
+/// EffectiveConditionalFormat directly maps the result of the conditional
+/// formatting rules evaluated on a cell. The format is the merged
+/// differential format of the matched rules, the color is the interpolated
+/// color of the color scale in the "#RRGGBB" format, the bar length is the
+/// percentage of the cell width filled by the data bar, and the icon index is
+/// the position of the icon in the icon set counts from the lowest values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EffectiveConditionalFormat {
+    pub format: Option<CTDxf>,
+    pub color: Option<String>,
+    pub bar_length: Option<f64>,
+    pub bar_color: Option<String>,
+    pub icon_style: Option<String>,
+    pub icon_index: Option<usize>,
+    pub show_value: bool,
+}
+
+pub trait ConditionalFormats {
+    /// set_conditional_format provides a function to add the conditional
+    /// formatting rules on the range by given worksheet name, range reference
+    /// and the rules settings. The data bars are written with the x14
+    /// extension for the solid fill, border and direction settings. For
+    /// example, highlight the cells greater than 6 in Sheet1!A1:A10 with the
+    /// red fill:
+    ///
+    /// ```ignore
+    /// let format = spreadsheet.new_conditional_style(&CTDxf {
+    ///     fill: Some(CTFill {
+    ///         pattern_fill: Some(CTPatternFill {
+    ///             pattern_type: Some(String::from("solid")),
+    ///             bg_color: Some(CTColor {
+    ///                 rgb: Some(String::from("FFFF0000")),
+    ///                 ..Default::default()
+    ///             }),
+    ///             ..Default::default()
+    ///         }),
+    ///     }),
+    ///     ..Default::default()
+    /// })?;
+    /// spreadsheet.set_conditional_format(
+    ///     "Sheet1",
+    ///     "A1:A10",
+    ///     &[ConditionalFormatOptions {
+    ///         type_attr: String::from("cell"),
+    ///         criteria: String::from(">"),
+    ///         value: String::from("6"),
+    ///         format: Some(format),
+    ///         ..Default::default()
+    ///     }],
+    /// )?;
+    /// ```
+    fn set_conditional_format(
+        &mut self,
+        sheet: &str,
+        sqref: &str,
+        rules: &[ConditionalFormatOptions],
+    ) -> Result<(), ExcelizeError>;
+    /// get_conditional_formats provides a function to get the conditional
+    /// formatting rules of the worksheet by given worksheet name, the rules
+    /// are grouped by the range reference.
+    fn get_conditional_formats(
+        &self,
+        sheet: &str,
+    ) -> Result<HashMap<String, Vec<ConditionalFormatOptions>>, ExcelizeError>;
+    /// get_effective_conditional_format provides a function to evaluate the
+    /// conditional formatting rules of the worksheet on the cell by given
+    /// worksheet name and cell reference. The rules are evaluated in the
+    /// priority order against the cell value and the statistics of the
+    /// ranges, the differential formats of the matched rules are merged with
+    /// the higher priority rule takes precedence, and the evaluation stops at
+    /// the matched rule with the stop if true setting.
+    fn get_effective_conditional_format(
+        &self,
+        sheet: &str,
+        cell: &str,
+    ) -> Result<EffectiveConditionalFormat, ExcelizeError>;
+}
+
+impl ConditionalFormats for Spreadsheet {
+    fn set_conditional_format(
+        &mut self,
+        sheet: &str,
+        sqref: &str,
+        rules: &[ConditionalFormatOptions],
+    ) -> Result<(), ExcelizeError> {
+        let refs = parse_sqref(sqref)?;
+        if refs.is_empty() {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid range reference {:?}",
+                sqref
+            )));
+        }
+        let sqref = join_sqref(&refs)?;
+        let cell = coordinates_to_cell_name(refs[0][0], refs[0][1])?;
+        let dxf_count = self
+            .styles
+            .as_ref()
+            .and_then(|styles| styles.dxfs.as_ref())
+            .map_or(0, |dxfs| dxfs.dxf.len() as u32);
+        let mut cf_rules = Vec::new();
+        let mut x14_rules = Vec::new();
+        for opts in rules {
+            if let Some(format) = opts.format {
+                if format >= dxf_count {
+                    return Err(ExcelizeError::CommonError(format!(
+                        "invalid conditional format style ID {}",
+                        format
+                    )));
+                }
+            }
+            let (rule, x14_rule) = new_cf_rule(opts, &cell)?;
+            cf_rules.push((opts.priority, rule));
+            if let Some(x14_rule) = x14_rule {
+                x14_rules.push(x14_rule);
+            }
+        }
+        let ws = self.get_worksheet_mut(sheet)?;
+        let conditional_formatting = ws.conditional_formatting.get_or_insert_with(Vec::new);
+        let mut new_rules: Vec<xml_worksheet::CTCfRule> = Vec::new();
+        for (priority, mut rule) in cf_rules {
+            let existing = conditional_formatting
+                .iter_mut()
+                .flat_map(|cf| cf.cf_rule.iter_mut())
+                .chain(new_rules.iter_mut());
+            rule.priority = match priority {
+                Some(priority) => {
+                    for r in existing.filter(|r| r.priority >= priority) {
+                        r.priority += 1;
+                    }
+                    priority
+                }
+                None => existing.map(|r| r.priority).max().unwrap_or(0) + 1,
+            };
+            new_rules.push(rule);
+        }
+        conditional_formatting.push(xml_worksheet::CTConditionalFormatting {
+            pivot: None,
+            sqref: sqref.clone(),
+            cf_rule: new_rules,
+        });
+        if !x14_rules.is_empty() {
+            let ext_lst = ws.ext_lst.get_or_insert_with(Default::default);
+            let ext = match ext_lst
+                .ext
+                .iter()
+                .position(|ext| ext.uri == EXT_URI_CONDITIONAL_FORMATTINGS)
+            {
+                Some(i) => &mut ext_lst.ext[i],
+                None => {
+                    ext_lst.ext.push(xml_worksheet::CTWorksheetExt {
+                        uri: String::from(EXT_URI_CONDITIONAL_FORMATTINGS),
+                        conditional_formattings: None,
+                    });
+                    ext_lst.ext.last_mut().unwrap()
+                }
+            };
+            ext.conditional_formattings
+                .get_or_insert_with(Default::default)
+                .conditional_formatting
+                .push(xml_worksheet::CTX14ConditionalFormatting {
+                    cf_rule: x14_rules,
+                    sqref,
+                });
+        }
+        Ok(())
+    }
+
+    fn get_conditional_formats(
+        &self,
+        sheet: &str,
+    ) -> Result<HashMap<String, Vec<ConditionalFormatOptions>>, ExcelizeError> {
+        let ws = self.get_worksheet(sheet)?;
+        let x14_rules: Vec<&xml_worksheet::CTX14CfRule> = ws
+            .ext_lst
+            .iter()
+            .flat_map(|ext_lst| ext_lst.ext.iter())
+            .filter_map(|ext| ext.conditional_formattings.as_ref())
+            .flat_map(|cfs| cfs.conditional_formatting.iter())
+            .flat_map(|cf| cf.cf_rule.iter())
+            .collect();
+        let mut formats: HashMap<String, Vec<ConditionalFormatOptions>> = HashMap::new();
+        for cf in ws.conditional_formatting.iter().flatten() {
+            let rules = formats.entry(cf.sqref.clone()).or_default();
+            for rule in &cf.cf_rule {
+                let x14_rule = get_x14_rule_id(rule)
+                    .and_then(|id| x14_rules.iter().find(|r| r.id.as_deref() == Some(id)));
+                rules.push(get_cf_rule_options(rule, x14_rule.copied()));
+            }
+        }
+        Ok(formats)
+    }
+
+    fn get_effective_conditional_format(
+        &self,
+        sheet: &str,
+        cell: &str,
+    ) -> Result<EffectiveConditionalFormat, ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        let ws = self.get_worksheet(sheet)?;
+        let mut rules = Vec::new();
+        for cf in ws.conditional_formatting.iter().flatten() {
+            let refs = parse_sqref(&cf.sqref)?;
+            if refs.iter().any(|r| in_range(r, col, row)) {
+                rules.extend(cf.cf_rule.iter().map(|rule| (rule, refs.clone())));
+            }
+        }
+        rules.sort_by_key(|(rule, _)| rule.priority);
+        let x14_rules: Vec<&xml_worksheet::CTX14CfRule> = ws
+            .ext_lst
+            .iter()
+            .flat_map(|ext_lst| ext_lst.ext.iter())
+            .filter_map(|ext| ext.conditional_formattings.as_ref())
+            .flat_map(|cfs| cfs.conditional_formatting.iter())
+            .flat_map(|cf| cf.cf_rule.iter())
+            .collect();
+        let value = get_cell_formula_value(self, ws, col, row);
+        let mut effective = EffectiveConditionalFormat {
+            show_value: true,
+            ..Default::default()
+        };
+        for (rule, refs) in rules {
+            let ctx = RuleContext {
+                spreadsheet: self,
+                sheet,
+                ws,
+                refs: &refs,
+                value: &value,
+                offset: (
+                    i64::from(col) - i64::from(refs[0][0]),
+                    i64::from(row) - i64::from(refs[0][1]),
+                ),
+            };
+            let matched = match rule.type_attr.as_deref().unwrap_or_default() {
+                "colorScale" => match ctx.eval_color_scale(rule) {
+                    Some(color) => {
+                        effective.color.get_or_insert(color);
+                        true
+                    }
+                    None => false,
+                },
+                "dataBar" => {
+                    let x14_rule = get_x14_rule_id(rule)
+                        .and_then(|id| x14_rules.iter().find(|r| r.id.as_deref() == Some(id)));
+                    match ctx.eval_data_bar(rule, x14_rule.copied()) {
+                        Some(length) if effective.bar_length.is_none() => {
+                            let bar = rule.data_bar.as_ref();
+                            effective.bar_length = Some(length);
+                            effective.bar_color =
+                                Some(get_color(bar.and_then(|bar| bar.color.as_ref())));
+                            effective.show_value &=
+                                bar.and_then(|bar| bar.show_value).unwrap_or(true);
+                            true
+                        }
+                        length => length.is_some(),
+                    }
+                }
+                "iconSet" => match ctx.eval_icon_set(rule) {
+                    Some(index) if effective.icon_index.is_none() => {
+                        let icon_set = rule.icon_set.as_ref();
+                        effective.icon_style = Some(
+                            icon_set
+                                .and_then(|icon_set| icon_set.icon_set.clone())
+                                .unwrap_or_else(|| String::from("3TrafficLights1")),
+                        );
+                        effective.icon_index = Some(index);
+                        effective.show_value &= icon_set
+                            .and_then(|icon_set| icon_set.show_value)
+                            .unwrap_or(true);
+                        true
+                    }
+                    index => index.is_some(),
+                },
+                _ => {
+                    let matched = ctx.match_rule(rule);
+                    if let Some(dxf) = rule.dxf_id.filter(|_| matched).and_then(|id| {
+                        self.styles
+                            .as_ref()
+                            .and_then(|styles| styles.dxfs.as_ref())
+                            .and_then(|dxfs| dxfs.dxf.get(id as usize))
+                    }) {
+                        let format = effective.format.get_or_insert_with(Default::default);
+                        format.font = format.font.take().or_else(|| dxf.font.clone());
+                        format.num_fmt = format.num_fmt.take().or_else(|| dxf.num_fmt.clone());
+                        format.fill = format.fill.take().or_else(|| dxf.fill.clone());
+                        format.border = format.border.take().or_else(|| dxf.border.clone());
+                    }
+                    matched
+                }
+            };
+            if matched && rule.stop_if_true == Some(true) {
+                break;
+            }
+        }
+        Ok(effective)
+    }
+}
+
+// RuleContext defined the cell and the ranges which the conditional
+// formatting rule is evaluated on.
+struct RuleContext<'a> {
+    spreadsheet: &'a Spreadsheet,
+    sheet: &'a str,
+    ws: &'a XMLWorksheet,
+    refs: &'a [[u32; 4]],
+    value: &'a FormulaValue,
+    offset: (i64, i64),
+}
+
+impl<'a> RuleContext<'a> {
+    // eval provides a function to evaluate the formula of the rule, the
+    // relative references are offset by the distance from the top-left cell
+    // of the ranges.
+    fn eval(&self, formula: &str) -> FormulaValue {
+        calc_formula(
+            self.spreadsheet,
+            self.sheet,
+            &offset_formula(formula, self.offset.0, self.offset.1),
+        )
+        .unwrap_or_else(|_| FormulaValue::Error(String::from("#NAME?")))
+    }
+
+    // values provides a function to get the values of the cells in the
+    // ranges, the ranges are clipped to the used area of the worksheet.
+    fn values(&self) -> Vec<FormulaValue> {
+        self.ws
+            .sheet_data
+            .row
+            .iter()
+            .flatten()
+            .flat_map(|r| r.c.iter())
+            .filter_map(|c| cell_name_to_coordinates(&c.r).ok())
+            .filter(|(col, row)| self.refs.iter().any(|r| in_range(r, *col, *row)))
+            .map(|(col, row)| get_cell_formula_value(self.spreadsheet, self.ws, col, row))
+            .collect()
+    }
+
+    // numbers provides a function to get the sorted numbers in the ranges.
+    fn numbers(&self) -> Vec<f64> {
+        let mut numbers: Vec<f64> = self
+            .values()
+            .into_iter()
+            .filter_map(|v| match v {
+                FormulaValue::Number(n) => Some(n),
+                _ => None,
+            })
+            .collect();
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        numbers
+    }
+
+    // number provides a function to get the numeric value of the cell, the
+    // blank, text and error cells are not formatted by the rules based on
+    // the statistics of the ranges.
+    fn number(&self) -> Option<f64> {
+        match self.value {
+            FormulaValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    // cfvo_value provides a function to get the threshold of the conditional
+    // format value object by given sorted numbers in the ranges.
+    fn cfvo_value(&self, cfvo: &xml_worksheet::CTCfvo, numbers: &[f64]) -> Option<f64> {
+        let (min, max) = (*numbers.first()?, *numbers.last()?);
+        let val = || self.eval(cfvo.val.as_deref()?).to_number().ok();
+        match cfvo.type_attr.as_str() {
+            "min" | "autoMin" => Some(min),
+            "max" | "autoMax" => Some(max),
+            "percent" => Some(min + (max - min) * val()? / 100.0),
+            "percentile" => {
+                let rank = (val()? / 100.0).clamp(0.0, 1.0) * (numbers.len() - 1) as f64;
+                let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+                Some(numbers[lower] + (numbers[upper] - numbers[lower]) * rank.fract())
+            }
+            _ => val(),
+        }
+    }
+
+    // eval_color_scale provides a function to interpolate the color of the
+    // cell between the colors of the color scale.
+    fn eval_color_scale(&self, rule: &xml_worksheet::CTCfRule) -> Option<String> {
+        let scale = rule.color_scale.as_ref()?;
+        let v = self.number()?;
+        let numbers = self.numbers();
+        let mut stops = Vec::new();
+        for (cfvo, color) in scale.cfvo.iter().zip(&scale.color) {
+            let rgb = rgb_hex(color.rgb.as_deref()?)?;
+            let rgb = u32::from_str_radix(rgb, 16).ok()?;
+            stops.push((self.cfvo_value(cfvo, &numbers)?, rgb));
+        }
+        let (first, last) = (stops.first()?, stops.last()?);
+        let rgb = match stops.windows(2).find(|w| v < w[1].0) {
+            _ if v <= first.0 => first.1,
+            Some(w) => {
+                let t = match w[1].0 > w[0].0 {
+                    true => (v - w[0].0) / (w[1].0 - w[0].0),
+                    false => 0.0,
+                };
+                [16, 8, 0].iter().fold(0, |rgb, shift| {
+                    let (a, b) = ((w[0].1 >> shift) & 0xff, (w[1].1 >> shift) & 0xff);
+                    let c = (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u32;
+                    rgb | (c << shift)
+                })
+            }
+            None => last.1,
+        };
+        Some(format!("#{:06X}", rgb))
+    }
+
+    // eval_data_bar provides a function to calculate the length of the data
+    // bar of the cell in percentage of the cell width, the lengths of the x14
+    // extension rule take precedence.
+    fn eval_data_bar(
+        &self,
+        rule: &xml_worksheet::CTCfRule,
+        x14_rule: Option<&xml_worksheet::CTX14CfRule>,
+    ) -> Option<f64> {
+        let bar = rule.data_bar.as_ref()?;
+        let v = self.number()?;
+        let numbers = self.numbers();
+        let min = self.cfvo_value(bar.cfvo.first()?, &numbers)?;
+        let max = self.cfvo_value(bar.cfvo.last()?, &numbers)?;
+        let x14_bar = x14_rule.and_then(|r| r.data_bar.as_ref());
+        let min_length = f64::from(
+            x14_bar
+                .and_then(|bar| bar.min_length)
+                .or(bar.min_length)
+                .unwrap_or(10),
+        );
+        let max_length = f64::from(
+            x14_bar
+                .and_then(|bar| bar.max_length)
+                .or(bar.max_length)
+                .unwrap_or(90),
+        );
+        let ratio = match max > min {
+            true => ((v - min) / (max - min)).clamp(0.0, 1.0),
+            false => f64::from(u8::from(v >= max)),
+        };
+        Some(min_length + (max_length - min_length) * ratio)
+    }
+
+    // eval_icon_set provides a function to get the index of the icon of the
+    // cell, the icon with the highest threshold which the value reaches will
+    // be used.
+    fn eval_icon_set(&self, rule: &xml_worksheet::CTCfRule) -> Option<usize> {
+        let icon_set = rule.icon_set.as_ref()?;
+        let v = self.number()?;
+        let numbers = self.numbers();
+        let mut index = 0;
+        for (i, cfvo) in icon_set.cfvo.iter().enumerate().skip(1) {
+            let threshold = self.cfvo_value(cfvo, &numbers)?;
+            if v > threshold || (v == threshold && cfvo.gte.unwrap_or(true)) {
+                index = i;
+            }
+        }
+        Some(match icon_set.reverse.unwrap_or(false) {
+            true => icon_set.cfvo.len().saturating_sub(index + 1),
+            false => index,
+        })
+    }
+
+    // match_rule provides a function to check if the cell matches the rule
+    // which applies the differential format.
+    fn match_rule(&self, rule: &xml_worksheet::CTCfRule) -> bool {
+        let formula = |i: usize| {
+            rule.formula
+                .as_ref()
+                .and_then(|f| f.get(i))
+                .map_or(FormulaValue::Blank, |f| self.eval(f))
+        };
+        let text = || self.value.to_text().ok().map(|s| s.to_lowercase());
+        let pattern = || rule.text.as_deref().unwrap_or_default().to_lowercase();
+        match rule.type_attr.as_deref().unwrap_or_default() {
+            "cellIs" => {
+                if matches!(self.value, FormulaValue::Error(_)) {
+                    return false;
+                }
+                let (a, b) = (formula(0), formula(1));
+                if matches!(a, FormulaValue::Error(_)) {
+                    return false;
+                }
+                let cmp = |f: &FormulaValue| compare_formula_values(self.value, f);
+                let between = || {
+                    let (low, high) = match compare_formula_values(&a, &b) {
+                        Ordering::Greater => (&b, &a),
+                        _ => (&a, &b),
+                    };
+                    cmp(low) != Ordering::Less && cmp(high) != Ordering::Greater
+                };
+                match rule.operator.as_deref().unwrap_or("between") {
+                    "between" => between(),
+                    "notBetween" => !between(),
+                    "equal" => cmp(&a) == Ordering::Equal,
+                    "notEqual" => cmp(&a) != Ordering::Equal,
+                    "greaterThan" => cmp(&a) == Ordering::Greater,
+                    "lessThan" => cmp(&a) == Ordering::Less,
+                    "greaterThanOrEqual" => cmp(&a) != Ordering::Less,
+                    "lessThanOrEqual" => cmp(&a) != Ordering::Greater,
+                    _ => false,
+                }
+            }
+            "expression" => formula(0).to_bool().unwrap_or(false),
+            "containsText" => text().map_or(false, |s| s.contains(&pattern())),
+            "notContainsText" => text().map_or(false, |s| !s.contains(&pattern())),
+            "beginsWith" => text().map_or(false, |s| s.starts_with(&pattern())),
+            "endsWith" => text().map_or(false, |s| s.ends_with(&pattern())),
+            "containsBlanks" => text().map_or(false, |s| s.trim().is_empty()),
+            "notContainsBlanks" => text().map_or(true, |s| !s.trim().is_empty()),
+            "containsErrors" => matches!(self.value, FormulaValue::Error(_)),
+            "notContainsErrors" => !matches!(self.value, FormulaValue::Error(_)),
+            "timePeriod" => self.number().map_or(false, |v| {
+                match_time_period(rule.time_period.as_deref().unwrap_or_default(), v)
+            }),
+            "top10" => self.number().map_or(false, |v| {
+                let mut numbers = self.numbers();
+                let bottom = rule.bottom.unwrap_or(false);
+                if !bottom {
+                    numbers.reverse();
+                }
+                let rank = rule.rank.unwrap_or(10) as usize;
+                let rank = match rule.percent.unwrap_or(false) {
+                    true => (numbers.len() * rank / 100).max(1),
+                    false => rank,
+                };
+                match numbers.get(rank.min(numbers.len()).saturating_sub(1)) {
+                    Some(threshold) if bottom => v <= *threshold,
+                    Some(threshold) => v >= *threshold,
+                    None => false,
+                }
+            }),
+            "aboveAverage" => self.number().map_or(false, |v| {
+                let numbers = self.numbers();
+                let n = numbers.len() as f64;
+                let mean = numbers.iter().sum::<f64>() / n;
+                let deviation = match rule.std_dev {
+                    Some(std_dev) => {
+                        let variance = numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                        f64::from(std_dev) * variance.sqrt()
+                    }
+                    None => 0.0,
+                };
+                let equal = rule.equal_average.unwrap_or(false) && rule.std_dev.is_none();
+                match rule.above_average.unwrap_or(true) {
+                    true => v > mean + deviation || (equal && v == mean),
+                    false => v < mean - deviation || (equal && v == mean),
+                }
+            }),
+            type_attr @ ("duplicateValues" | "uniqueValues") => {
+                if matches!(self.value, FormulaValue::Blank | FormulaValue::Error(_)) {
+                    return false;
+                }
+                let count = self
+                    .values()
+                    .iter()
+                    .filter(|v| {
+                        std::mem::discriminant(*v) == std::mem::discriminant(self.value)
+                            && compare_formula_values(v, self.value) == Ordering::Equal
+                    })
+                    .count();
+                (count > 1) == (type_attr == "duplicateValues")
+            }
+            _ => false,
+        }
+    }
+}
+
+// new_cf_rule provides a function to create the conditional formatting rule
+// and the x14 extension rule by given rule settings and the top-left cell of
+// the range which is referenced by the generated formulas.
+fn new_cf_rule(
+    opts: &ConditionalFormatOptions,
+    cell: &str,
+) -> Result<(xml_worksheet::CTCfRule, Option<xml_worksheet::CTX14CfRule>), ExcelizeError> {
+    let mut rule = xml_worksheet::CTCfRule {
+        dxf_id: opts.format,
+        stop_if_true: opts.stop_if_true.then_some(true),
+        ..Default::default()
+    };
+    let text = format!("\"{}\"", opts.value.replace('"', "\"\""));
+    let mut x14_rule = None;
+    match opts.type_attr.as_str() {
+        "cell" => {
+            let operator = CELL_IS_CRITERIA
+                .iter()
+                .find(|(_, name, symbol)| opts.criteria == *name || opts.criteria == *symbol)
+                .map(|(operator, _, _)| *operator)
+                .ok_or_else(|| invalid_criteria(&opts.criteria))?;
+            rule.type_attr = Some(String::from("cellIs"));
+            rule.operator = Some(String::from(operator));
+            rule.formula = Some(match operator {
+                "between" | "notBetween" => {
+                    vec![opts.min_value.clone(), opts.max_value.clone()]
+                }
+                _ => vec![opts.value.clone()],
+            });
+        }
+        "formula" => {
+            rule.type_attr = Some(String::from("expression"));
+            rule.formula = Some(vec![String::from(
+                opts.criteria.strip_prefix('=').unwrap_or(&opts.criteria),
+            )]);
+        }
+        "text" => {
+            let (type_attr, operator, _) = TEXT_CRITERIA
+                .iter()
+                .find(|(_, _, name)| opts.criteria == *name)
+                .ok_or_else(|| invalid_criteria(&opts.criteria))?;
+            rule.type_attr = Some(String::from(*type_attr));
+            rule.operator = Some(String::from(*operator));
+            rule.text = Some(opts.value.clone());
+            rule.formula = Some(vec![match *type_attr {
+                "containsText" => format!("NOT(ISERROR(SEARCH({},{})))", text, cell),
+                "notContainsText" => format!("ISERROR(SEARCH({},{}))", text, cell),
+                "beginsWith" => format!("LEFT({},LEN({}))={}", cell, text, text),
+                _ => format!("RIGHT({},LEN({}))={}", cell, text, text),
+            }]);
+        }
+        "time_period" => {
+            let (time_period, _) = TIME_PERIOD_CRITERIA
+                .iter()
+                .find(|(_, name)| opts.criteria == *name)
+                .ok_or_else(|| invalid_criteria(&opts.criteria))?;
+            rule.type_attr = Some(String::from("timePeriod"));
+            rule.time_period = Some(String::from(*time_period));
+            rule.formula = Some(vec![time_period_formula(time_period, cell)]);
+        }
+        "top" | "bottom" => {
+            rule.type_attr = Some(String::from("top10"));
+            rule.rank = Some(match opts.value.as_str() {
+                "" => 10,
+                value => value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|rank| (1..=1000).contains(rank))
+                    .ok_or_else(|| {
+                        ExcelizeError::CommonError(format!("invalid rank value {}", value))
+                    })?,
+            });
+            rule.percent = opts.percent.then_some(true);
+            rule.bottom = (opts.type_attr == "bottom").then_some(true);
+        }
+        "average" => {
+            rule.type_attr = Some(String::from("aboveAverage"));
+            rule.above_average = (!opts.above_average).then_some(false);
+        }
+        "duplicate" => rule.type_attr = Some(String::from("duplicateValues")),
+        "unique" => rule.type_attr = Some(String::from("uniqueValues")),
+        "blanks" | "no_blanks" | "errors" | "no_errors" => {
+            let (type_attr, _) = BLANK_ERROR_TYPES
+                .iter()
+                .find(|(_, name)| opts.type_attr == *name)
+                .unwrap_or(&BLANK_ERROR_TYPES[0]);
+            rule.type_attr = Some(String::from(*type_attr));
+            rule.formula = Some(vec![match *type_attr {
+                "containsBlanks" => format!("LEN(TRIM({}))=0", cell),
+                "notContainsBlanks" => format!("LEN(TRIM({}))>0", cell),
+                "containsErrors" => format!("ISERROR({})", cell),
+                _ => format!("NOT(ISERROR({}))", cell),
+            }]);
+        }
+        "2_color_scale" | "3_color_scale" => {
+            let three = opts.type_attr == "3_color_scale";
+            let mut cfvo = vec![new_cfvo(&opts.min_type, "min", &opts.min_value)?];
+            let mut color = vec![new_color(&opts.min_color, "#F8696B")];
+            if three {
+                cfvo.push(new_cfvo(
+                    &opts.mid_type,
+                    "percentile",
+                    match opts.mid_value.as_str() {
+                        "" => "50",
+                        value => value,
+                    },
+                )?);
+                color.push(new_color(&opts.mid_color, "#FFEB84"));
+            }
+            cfvo.push(new_cfvo(&opts.max_type, "max", &opts.max_value)?);
+            color.push(new_color(&opts.max_color, "#63BE7B"));
+            rule.type_attr = Some(String::from("colorScale"));
+            rule.color_scale = Some(xml_worksheet::CTColorScale { cfvo, color });
+        }
+        "data_bar" => {
+            let min = new_cfvo(&opts.min_type, "min", &opts.min_value)?;
+            let max = new_cfvo(&opts.max_type, "max", &opts.max_value)?;
+            let color = new_color(&opts.bar_color, "#638EC6");
+            let id = new_guid();
+            let x14_cfvo = |cfvo: &xml_worksheet::CTCfvo, auto: &str| xml_worksheet::CTX14Cfvo {
+                type_attr: match cfvo.type_attr.as_str() {
+                    "min" | "max" => String::from(auto),
+                    type_attr => String::from(type_attr),
+                },
+                gte: None,
+                f: cfvo.val.clone(),
+            };
+            if !["", "context", "leftToRight", "rightToLeft"].contains(&opts.bar_direction.as_str())
+            {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid data bar direction {}",
+                    opts.bar_direction
+                )));
+            }
+            x14_rule = Some(xml_worksheet::CTX14CfRule {
+                type_attr: Some(String::from("dataBar")),
+                priority: None,
+                id: Some(id.clone()),
+                data_bar: Some(xml_worksheet::CTX14DataBar {
+                    min_length: Some(0),
+                    max_length: Some(100),
+                    border: (!opts.bar_border_color.is_empty()).then_some(true),
+                    gradient: opts.bar_solid.then_some(false),
+                    direction: match opts.bar_direction.as_str() {
+                        "" | "context" => None,
+                        direction => Some(String::from(direction)),
+                    },
+                    cfvo: vec![x14_cfvo(&min, "autoMin"), x14_cfvo(&max, "autoMax")],
+                    fill_color: Some(color.clone()),
+                    border_color: (!opts.bar_border_color.is_empty())
+                        .then(|| new_color(&opts.bar_border_color, "")),
+                    negative_fill_color: Some(new_color("#FF0000", "")),
+                    axis_color: Some(new_color("#000000", "")),
+                    ..Default::default()
+                }),
+            });
+            rule.type_attr = Some(String::from("dataBar"));
+            rule.data_bar = Some(xml_worksheet::CTDataBar {
+                show_value: opts.bar_only.then_some(false),
+                cfvo: vec![min, max],
+                color: Some(color),
+                ..Default::default()
+            });
+            rule.ext_lst = Some(xml_worksheet::CTCfRuleExtLst {
+                ext: vec![xml_worksheet::CTCfRuleExt {
+                    uri: String::from(EXT_URI_CONDITIONAL_FORMATTING_RULE_ID),
+                    id: Some(id),
+                }],
+            });
+        }
+        "icon_set" => {
+            if !ICON_STYLES.contains(&opts.icon_style.as_str()) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid icon style {}",
+                    opts.icon_style
+                )));
+            }
+            let count = icon_count(&opts.icon_style);
+            rule.type_attr = Some(String::from("iconSet"));
+            rule.icon_set = Some(xml_worksheet::CTIconSet {
+                icon_set: Some(opts.icon_style.clone()),
+                show_value: opts.icons_only.then_some(false),
+                reverse: opts.reverse_icons.then_some(true),
+                cfvo: (0..count)
+                    .map(|i| xml_worksheet::CTCfvo {
+                        type_attr: String::from("percent"),
+                        val: Some(((100 * i) as f64 / count as f64).round().to_string()),
+                        gte: None,
+                    })
+                    .collect(),
+                ..Default::default()
+            });
+        }
+        type_attr => {
+            return Err(ExcelizeError::CommonError(format!(
+                "unsupported conditional format type {}",
+                type_attr
+            )))
+        }
+    }
+    Ok((rule, x14_rule))
+}
+
+fn invalid_criteria(criteria: &str) -> ExcelizeError {
+    ExcelizeError::CommonError(format!("invalid conditional format criteria {}", criteria))
+}
+
+// icon_count provides a function to get the number of icons of the icon set.
+pub(crate) fn icon_count(icon_style: &str) -> usize {
+    match icon_style.chars().next() {
+        Some('4') => 4,
+        Some('5') => 5,
+        _ => 3,
+    }
+}
+
+// new_cfvo provides a function to create the conditional format value object
+// by given type and value, the default type will be used if the type is
+// empty.
+fn new_cfvo(
+    type_attr: &str,
+    default: &str,
+    value: &str,
+) -> Result<xml_worksheet::CTCfvo, ExcelizeError> {
+    let type_attr = match type_attr {
+        "" => default,
+        type_attr => type_attr,
+    };
+    match type_attr {
+        "min" | "max" => Ok(xml_worksheet::CTCfvo {
+            type_attr: String::from(type_attr),
+            ..Default::default()
+        }),
+        "num" | "percent" | "percentile" | "formula" => {
+            if value.is_empty() {
+                return Err(ExcelizeError::CommonError(format!(
+                    "the value of the {} type can't be empty",
+                    type_attr
+                )));
+            }
+            Ok(xml_worksheet::CTCfvo {
+                type_attr: String::from(type_attr),
+                val: Some(String::from(value.strip_prefix('=').unwrap_or(value))),
+                gte: None,
+            })
+        }
+        _ => Err(ExcelizeError::CommonError(format!(
+            "invalid conditional format value type {}",
+            type_attr
+        ))),
+    }
+}
+
+// new_color provides a function to create the ARGB color by given color in
+// the "#RRGGBB" format, the default color will be used if the color is empty.
+fn new_color(color: &str, default: &str) -> CTColor {
+    let color = match color {
+        "" => default,
+        color => color,
+    };
+    let rgb = color.trim_start_matches('#').to_uppercase();
+    CTColor {
+        rgb: Some(match rgb.len() {
+            6 => format!("FF{}", rgb),
+            _ => rgb,
+        }),
+        ..Default::default()
+    }
+}
+
+// get_color provides a function to convert the ARGB color to the "#RRGGBB"
+// format.
+fn get_color(color: Option<&CTColor>) -> String {
+    color
+        .and_then(|color| rgb_hex(color.rgb.as_deref()?))
+        .map(|rgb| format!("#{}", rgb))
+        .unwrap_or_default()
+}
+
+// rgb_hex provides a function to get the "RRGGBB" part of the ARGB color,
+// returns None if the color isn't in the hexadecimal format.
+fn rgb_hex(rgb: &str) -> Option<&str> {
+    match rgb.len() >= 6 && rgb.chars().all(|c| c.is_ascii_hexdigit()) {
+        true => Some(&rgb[rgb.len() - 6..]),
+        false => None,
+    }
+}
+
+// in_range provides a function to check if the cell is in the range by given
+// coordinates of the range and the cell.
+fn in_range(r: &[u32; 4], col: u32, row: u32) -> bool {
+    (r[0]..=r[2]).contains(&col) && (r[1]..=r[3]).contains(&row)
+}
+
+// match_time_period provides a function to check if the date serial number
+// is in the time period relative to today, the weeks start on Sunday.
+fn match_time_period(time_period: &str, serial: f64) -> bool {
+    let today = today_serial().floor();
+    let day = serial.floor();
+    let week = today - (today - 1.0).rem_euclid(7.0);
+    let month = |offset: i64| {
+        serial_to_datetime(today).map_or(0.0, |dt| month_serial(dt[0], dt[1] + offset))
+    };
+    let (start, end) = match time_period {
+        "yesterday" => (today - 1.0, today),
+        "today" => (today, today + 1.0),
+        "tomorrow" => (today + 1.0, today + 2.0),
+        "last7Days" => (today - 6.0, today + 1.0),
+        "lastWeek" => (week - 7.0, week),
+        "thisWeek" => (week, week + 7.0),
+        "nextWeek" => (week + 7.0, week + 14.0),
+        "lastMonth" => (month(-1), month(0)),
+        "thisMonth" => (month(0), month(1)),
+        "nextMonth" => (month(1), month(2)),
+        _ => return false,
+    };
+    (start..end).contains(&day)
+}
+
+// time_period_formula provides a function to generate the formula of the
+// time period rule by given time period and the top-left cell of the range.
+fn time_period_formula(time_period: &str, cell: &str) -> String {
+    match time_period {
+        "yesterday" => format!("FLOOR({},1)=TODAY()-1", cell),
+        "tomorrow" => format!("FLOOR({},1)=TODAY()+1", cell),
+        "last7Days" => format!(
+            "AND(TODAY()-FLOOR({0},1)<=6,FLOOR({0},1)<=TODAY())",
+            cell
+        ),
+        "lastWeek" => format!(
+            "AND(TODAY()-ROUNDDOWN({0},0)>=(WEEKDAY(TODAY())),TODAY()-ROUNDDOWN({0},0)<(WEEKDAY(TODAY())+7))",
+            cell
+        ),
+        "thisWeek" => format!(
+            "AND(TODAY()-ROUNDDOWN({0},0)<=WEEKDAY(TODAY())-1,ROUNDDOWN({0},0)-TODAY()<=7-WEEKDAY(TODAY()))",
+            cell
+        ),
+        "nextWeek" => format!(
+            "AND(ROUNDDOWN({0},0)-TODAY()>(7-WEEKDAY(TODAY())),ROUNDDOWN({0},0)-TODAY()<(15-WEEKDAY(TODAY())))",
+            cell
+        ),
+        "lastMonth" => format!(
+            "AND(MONTH({0})=MONTH(EDATE(TODAY(),0-1)),YEAR({0})=YEAR(EDATE(TODAY(),0-1)))",
+            cell
+        ),
+        "thisMonth" => format!(
+            "AND(MONTH({0})=MONTH(TODAY()),YEAR({0})=YEAR(TODAY()))",
+            cell
+        ),
+        "nextMonth" => format!(
+            "AND(MONTH({0})=MONTH(EDATE(TODAY(),0+1)),YEAR({0})=YEAR(EDATE(TODAY(),0+1)))",
+            cell
+        ),
+        _ => format!("FLOOR({},1)=TODAY()", cell),
+    }
+}
+
+// get_x14_rule_id provides a function to get the ID of the x14 extension rule
+// which is referenced by the conditional formatting rule.
+fn get_x14_rule_id(rule: &xml_worksheet::CTCfRule) -> Option<&str> {
+    rule.ext_lst
+        .as_ref()?
+        .ext
+        .iter()
+        .find(|ext| ext.uri == EXT_URI_CONDITIONAL_FORMATTING_RULE_ID)?
+        .id
+        .as_deref()
+}
+
+// get_cf_rule_options provides a function to convert the conditional
+// formatting rule and the x14 extension rule to the rule settings.
+fn get_cf_rule_options(
+    rule: &xml_worksheet::CTCfRule,
+    x14_rule: Option<&xml_worksheet::CTX14CfRule>,
+) -> ConditionalFormatOptions {
+    let mut opts = ConditionalFormatOptions {
+        format: rule.dxf_id,
+        stop_if_true: rule.stop_if_true.unwrap_or(false),
+        priority: Some(rule.priority),
+        ..Default::default()
+    };
+    let formula = |i: usize| {
+        rule.formula
+            .as_ref()
+            .and_then(|f| f.get(i))
+            .cloned()
+            .unwrap_or_default()
+    };
+    let cfvo = |cfvo: Option<&xml_worksheet::CTCfvo>| {
+        cfvo.map(|c| (c.type_attr.clone(), c.val.clone().unwrap_or_default()))
+            .unwrap_or_default()
+    };
+    let type_attr = rule.type_attr.as_deref().unwrap_or_default();
+    opts.type_attr = String::from(match type_attr {
+        "cellIs" => {
+            let operator = rule.operator.as_deref().unwrap_or("between");
+            opts.criteria = CELL_IS_CRITERIA
+                .iter()
+                .find(|(o, _, _)| *o == operator)
+                .map(|(_, name, _)| String::from(*name))
+                .unwrap_or_default();
+            match operator {
+                "between" | "notBetween" => {
+                    opts.min_value = formula(0);
+                    opts.max_value = formula(1);
+                }
+                _ => opts.value = formula(0),
+            }
+            "cell"
+        }
+        "expression" => {
+            opts.criteria = formula(0);
+            "formula"
+        }
+        "containsText" | "notContainsText" | "beginsWith" | "endsWith" => {
+            opts.criteria = TEXT_CRITERIA
+                .iter()
+                .find(|(t, _, _)| *t == type_attr)
+                .map(|(_, _, name)| String::from(*name))
+                .unwrap_or_default();
+            opts.value = rule.text.clone().unwrap_or_default();
+            "text"
+        }
+        "timePeriod" => {
+            let time_period = rule.time_period.as_deref().unwrap_or_default();
+            opts.criteria = TIME_PERIOD_CRITERIA
+                .iter()
+                .find(|(t, _)| *t == time_period)
+                .map(|(_, name)| String::from(*name))
+                .unwrap_or_default();
+            "time_period"
+        }
+        "top10" => {
+            opts.value = rule.rank.unwrap_or(10).to_string();
+            opts.percent = rule.percent.unwrap_or(false);
+            match rule.bottom.unwrap_or(false) {
+                true => "bottom",
+                false => "top",
+            }
+        }
+        "aboveAverage" => {
+            opts.above_average = rule.above_average.unwrap_or(true);
+            "average"
+        }
+        "duplicateValues" => "duplicate",
+        "uniqueValues" => "unique",
+        "colorScale" => {
+            let scale = rule.color_scale.clone().unwrap_or_default();
+            let three = scale.cfvo.len() > 2;
+            (opts.min_type, opts.min_value) = cfvo(scale.cfvo.first());
+            (opts.max_type, opts.max_value) = cfvo(scale.cfvo.last());
+            opts.min_color = get_color(scale.color.first());
+            opts.max_color = get_color(scale.color.last());
+            if three {
+                (opts.mid_type, opts.mid_value) = cfvo(scale.cfvo.get(1));
+                opts.mid_color = get_color(scale.color.get(1));
+                "3_color_scale"
+            } else {
+                "2_color_scale"
+            }
+        }
+        "dataBar" => {
+            let bar = rule.data_bar.clone().unwrap_or_default();
+            (opts.min_type, opts.min_value) = cfvo(bar.cfvo.first());
+            (opts.max_type, opts.max_value) = cfvo(bar.cfvo.last());
+            opts.bar_color = get_color(bar.color.as_ref());
+            opts.bar_only = bar.show_value == Some(false);
+            if let Some(bar) = x14_rule.and_then(|r| r.data_bar.as_ref()) {
+                opts.bar_solid = bar.gradient == Some(false);
+                opts.bar_direction = bar.direction.clone().unwrap_or_default();
+                opts.bar_border_color = get_color(bar.border_color.as_ref());
+            }
+            "data_bar"
+        }
+        "iconSet" => {
+            let icon_set = rule.icon_set.clone().unwrap_or_default();
+            opts.icon_style = icon_set
+                .icon_set
+                .unwrap_or_else(|| String::from("3TrafficLights1"));
+            opts.reverse_icons = icon_set.reverse.unwrap_or(false);
+            opts.icons_only = icon_set.show_value == Some(false);
+            "icon_set"
+        }
+        type_attr => BLANK_ERROR_TYPES
+            .iter()
+            .find(|(t, _)| *t == type_attr)
+            .map_or(type_attr, |(_, name)| *name),
+    });
+    opts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CTFill, CTPatternFill, Styles};
+
+    #[test]
+    fn test_conditional_format() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert!(wb.get_conditional_formats("Sheet2").unwrap().is_empty());
+        let format = wb
+            .new_conditional_style(&CTDxf {
+                fill: Some(CTFill {
+                    pattern_fill: Some(CTPatternFill {
+                        pattern_type: Some(String::from("solid")),
+                        bg_color: Some(new_color("#FF0000", "")),
+                        ..Default::default()
+                    }),
+                }),
+                ..Default::default()
+            })
+            .unwrap();
+        let cell_rule = ConditionalFormatOptions {
+            type_attr: String::from("cell"),
+            criteria: String::from(">"),
+            value: String::from("20"),
+            format: Some(format),
+            stop_if_true: true,
+            ..Default::default()
+        };
+        wb.set_conditional_format(
+            "Sheet2",
+            "B2:B5",
+            &[
+                cell_rule.clone(),
+                ConditionalFormatOptions {
+                    type_attr: String::from("3_color_scale"),
+                    ..Default::default()
+                },
+            ],
+        )
+        .unwrap();
+        wb.set_conditional_format(
+            "Sheet2",
+            "D2:D11",
+            &[
+                ConditionalFormatOptions {
+                    type_attr: String::from("data_bar"),
+                    bar_solid: true,
+                    bar_direction: String::from("rightToLeft"),
+                    bar_border_color: String::from("#638EC6"),
+                    ..Default::default()
+                },
+                ConditionalFormatOptions {
+                    type_attr: String::from("icon_set"),
+                    icon_style: String::from("4Arrows"),
+                    priority: Some(1),
+                    ..Default::default()
+                },
+            ],
+        )
+        .unwrap();
+        wb.set_conditional_format(
+            "Sheet2",
+            "C2:C11",
+            &[ConditionalFormatOptions {
+                type_attr: String::from("text"),
+                criteria: String::from("begins with"),
+                value: String::from("A"),
+                format: Some(format),
+                ..Default::default()
+            }],
+        )
+        .unwrap();
+        assert!(wb
+            .set_conditional_format(
+                "Sheet2",
+                "A1",
+                &[ConditionalFormatOptions {
+                    format: Some(format + 1),
+                    ..cell_rule.clone()
+                }],
+            )
+            .is_err());
+        assert!(wb
+            .set_conditional_format(
+                "Sheet2",
+                "A1",
+                &[ConditionalFormatOptions {
+                    type_attr: String::from("icon_set"),
+                    icon_style: String::from("3Stars"),
+                    ..Default::default()
+                }],
+            )
+            .is_err());
+
+        let formats = wb.get_conditional_formats("Sheet2").unwrap();
+        let rules = &formats["B2:B5"];
+        assert_eq!(rules[0].criteria, "greater than");
+        assert_eq!(rules[0].value, "20");
+        assert!(rules[0].stop_if_true);
+        assert_eq!(rules[0].priority, Some(2));
+        assert_eq!(rules[1].type_attr, "3_color_scale");
+        assert_eq!(rules[1].mid_type, "percentile");
+        assert_eq!(rules[1].mid_value, "50");
+        assert_eq!(rules[1].max_color, "#63BE7B");
+        let rules = &formats["D2:D11"];
+        assert_eq!(rules[0].type_attr, "data_bar");
+        assert!(rules[0].bar_solid);
+        assert_eq!(rules[0].bar_direction, "rightToLeft");
+        assert_eq!(rules[0].bar_border_color, "#638EC6");
+        assert_eq!(rules[0].priority, Some(4));
+        assert_eq!(rules[1].icon_style, "4Arrows");
+        assert_eq!(rules[1].priority, Some(1));
+        let rules = &formats["C2:C11"];
+        assert_eq!(rules[0].criteria, "begins with");
+        assert_eq!(rules[0].value, "A");
+
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        let cf = &ws.conditional_formatting.as_ref().unwrap()[2];
+        assert_eq!(
+            cf.cf_rule[0].formula.as_ref().unwrap()[0],
+            "LEFT(C2,LEN(\"A\"))=\"A\""
+        );
+        let icon_set = ws.conditional_formatting.as_ref().unwrap()[1].cf_rule[1]
+            .icon_set
+            .as_ref()
+            .unwrap();
+        let thresholds: Vec<&str> = icon_set
+            .cfvo
+            .iter()
+            .map(|c| c.val.as_deref().unwrap())
+            .collect();
+        assert_eq!(thresholds, vec!["0", "25", "50", "75"]);
+    }
+
+    #[test]
+    fn test_get_effective_conditional_format() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let red = wb
+            .new_conditional_style(&CTDxf {
+                fill: Some(CTFill {
+                    pattern_fill: Some(CTPatternFill {
+                        pattern_type: Some(String::from("solid")),
+                        bg_color: Some(new_color("#FF0000", "")),
+                        ..Default::default()
+                    }),
+                }),
+                ..Default::default()
+            })
+            .unwrap();
+        let bold = wb
+            .new_conditional_style(&CTDxf {
+                border: Some(Default::default()),
+                fill: Some(CTFill { pattern_fill: None }),
+                ..Default::default()
+            })
+            .unwrap();
+        let rule = |type_attr: &str, format: Option<u32>| ConditionalFormatOptions {
+            type_attr: String::from(type_attr),
+            format,
+            ..Default::default()
+        };
+        wb.set_conditional_format(
+            "Sheet2",
+            "B2:B5",
+            &[
+                ConditionalFormatOptions {
+                    criteria: String::from(">"),
+                    value: String::from("20"),
+                    stop_if_true: true,
+                    ..rule("cell", Some(red))
+                },
+                ConditionalFormatOptions {
+                    criteria: String::from("=B2<100"),
+                    ..rule("formula", Some(bold))
+                },
+            ],
+        )
+        .unwrap();
+        wb.set_conditional_format(
+            "Sheet2",
+            "D2:D11",
+            &[
+                rule("2_color_scale", None),
+                rule("data_bar", None),
+                ConditionalFormatOptions {
+                    icon_style: String::from("3Arrows"),
+                    ..rule("icon_set", None)
+                },
+                ConditionalFormatOptions {
+                    value: String::from("3"),
+                    ..rule("top", Some(red))
+                },
+                rule("duplicate", Some(bold)),
+            ],
+        )
+        .unwrap();
+        wb.set_conditional_format(
+            "Sheet2",
+            "C2:C11",
+            &[ConditionalFormatOptions {
+                criteria: String::from("begins with"),
+                value: String::from("a"),
+                ..rule("text", Some(red))
+            }],
+        )
+        .unwrap();
+
+        let effective = wb.get_effective_conditional_format("Sheet2", "B2").unwrap();
+        assert_eq!(effective.format.unwrap().border, Some(Default::default()));
+        let effective = wb.get_effective_conditional_format("Sheet2", "B3").unwrap();
+        let format = effective.format.unwrap();
+        assert!(format.fill.unwrap().pattern_fill.is_some());
+        assert!(format.border.is_none());
+
+        let effective = wb.get_effective_conditional_format("Sheet2", "D5").unwrap();
+        assert_eq!(effective.color.as_deref(), Some("#63BE7B"));
+        assert_eq!(effective.bar_length, Some(100.0));
+        assert_eq!(effective.bar_color.as_deref(), Some("#638EC6"));
+        assert_eq!(effective.icon_style.as_deref(), Some("3Arrows"));
+        assert_eq!(effective.icon_index, Some(2));
+        assert!(effective.format.is_some());
+        let effective = wb
+            .get_effective_conditional_format("Sheet2", "D11")
+            .unwrap();
+        assert_eq!(effective.color.as_deref(), Some("#F8696B"));
+        assert_eq!(effective.bar_length, Some(0.0));
+        assert_eq!(effective.icon_index, Some(0));
+        assert!(effective.format.is_none());
+        let effective = wb.get_effective_conditional_format("Sheet2", "D6").unwrap();
+        assert_eq!(effective.icon_index, Some(1));
+        assert!(effective.format.is_none());
+        let effective = wb.get_effective_conditional_format("Sheet2", "D2").unwrap();
+        assert_eq!(effective.format.unwrap().border, Some(Default::default()));
+
+        for (cell, matched) in [("C6", true), ("C8", true), ("C9", true), ("C2", false)] {
+            let effective = wb.get_effective_conditional_format("Sheet2", cell).unwrap();
+            assert_eq!(effective.format.is_some(), matched);
+        }
+        let effective = wb.get_effective_conditional_format("Sheet2", "A1").unwrap();
+        assert_eq!(
+            effective,
+            EffectiveConditionalFormat {
+                show_value: true,
+                ..Default::default()
+            }
+        );
+        // the colors which aren't in the hexadecimal format are ignored
+        let ws = wb.get_worksheet_mut("Sheet2").unwrap();
+        for rule in ws
+            .conditional_formatting
+            .iter_mut()
+            .flatten()
+            .flat_map(|cf| cf.cf_rule.iter_mut())
+        {
+            if let Some(ref mut scale) = rule.color_scale {
+                scale.color[0].rgb = Some(String::from("FF\u{e9}3BE7B"));
+            }
+        }
+        let effective = wb.get_effective_conditional_format("Sheet2", "D5").unwrap();
+        assert_eq!(effective.color, None);
+        let color = new_color("\u{e9}\u{e9}\u{e9}\u{e9}", "");
+        assert_eq!(get_color(Some(&color)), "");
+        assert_eq!(get_color(Some(&new_color("#00ff00", ""))), "#00FF00");
+
+        assert!(match_time_period("today", today_serial() + 0.5));
+        assert!(!match_time_period("yesterday", today_serial()));
+        assert!(match_time_period("thisWeek", today_serial()));
+    }
+}
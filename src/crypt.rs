@@ -0,0 +1,138 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::ExcelizeError;
+use rand::RngCore;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+static MAX_PASSWORD_LENGTH: usize = 255;
+
+/// PROTECTION_SPIN_COUNT specifies the number of times the hashing function
+/// shall be iteratively run when generating the protection password hash.
+pub(crate) static PROTECTION_SPIN_COUNT: u32 = 100000;
+
+// hashing provides a function to calculate the hash of the concatenated data
+// by given hash algorithm name.
+fn hashing(algorithm: &str, data: &[&[u8]]) -> Result<Vec<u8>, ExcelizeError> {
+    fn digest<D: Digest>(data: &[&[u8]]) -> Vec<u8> {
+        let mut hasher = D::new();
+        for d in data {
+            hasher.update(d);
+        }
+        hasher.finalize().to_vec()
+    }
+    match algorithm {
+        "SHA-256" => Ok(digest::<Sha256>(data)),
+        "SHA-384" => Ok(digest::<Sha384>(data)),
+        "SHA-512" => Ok(digest::<Sha512>(data)),
+        _ => Err(ExcelizeError::CommonError(format!(
+            "unsupported hash algorithm {:?}",
+            algorithm
+        ))),
+    }
+}
+
+// check_password_length provides a function to check if the password length
+// is in the range from 1 to 255 characters.
+fn check_password_length(password: &str) -> Result<(), ExcelizeError> {
+    let length = password.chars().count();
+    if length < 1 || length > MAX_PASSWORD_LENGTH {
+        return Err(ExcelizeError::CommonError(format!(
+            "the length of the password must be in the range from 1 to {} characters",
+            MAX_PASSWORD_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+// gen_iso_passwd_hash implements the ISO/IEC 29500 password hashing algorithm
+// used by the sheet, workbook and range protection. The base64 encoded salt
+// will be used if it's given, otherwise a random 16 bytes salt will be
+// generated. Returns the base64 encoded hash value and salt value.
+pub(crate) fn gen_iso_passwd_hash(
+    password: &str,
+    algorithm: &str,
+    salt: Option<&str>,
+    spin_count: u32,
+) -> Result<(String, String), ExcelizeError> {
+    check_password_length(password)?;
+    let salt = match salt {
+        Some(salt) => match base64::decode(salt) {
+            Ok(s) => s,
+            Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+        },
+        None => {
+            let mut s = vec![0u8; 16];
+            rand::thread_rng().fill_bytes(&mut s);
+            s
+        }
+    };
+    let password: Vec<u8> = password
+        .encode_utf16()
+        .flat_map(|u| u.to_le_bytes())
+        .collect();
+    let mut key = hashing(algorithm, &[&salt, &password])?;
+    for i in 0..spin_count {
+        key = hashing(algorithm, &[&key, &i.to_le_bytes()])?;
+    }
+    Ok((base64::encode(&key), base64::encode(&salt)))
+}
+
+// verify_iso_passwd_hash provides a function to check if the password matches
+// the hash value generated by the given algorithm, salt and spin count.
+pub(crate) fn verify_iso_passwd_hash(
+    password: &str,
+    algorithm: &str,
+    hash_value: &str,
+    salt: &str,
+    spin_count: u32,
+) -> Result<bool, ExcelizeError> {
+    let (hash, _) = gen_iso_passwd_hash(password, algorithm, Some(salt), spin_count)?;
+    Ok(hash == hash_value)
+}
+
+// gen_legacy_passwd_hash provides a function to generate the legacy 16 bits
+// XOR password hash used by the password attribute of the protection
+// elements, it's returned as the hexadecimal string. Each character is
+// rotated left within 15 bits by its position, so that the long passwords
+// could be hashed without overflow.
+pub(crate) fn gen_legacy_passwd_hash(password: &str) -> Result<String, ExcelizeError> {
+    check_password_length(password)?;
+    let chars: Vec<u32> = password.chars().map(|c| c as u32 & 0x7fff).collect();
+    let mut hash: u32 = 0;
+    for c in chars
+        .iter()
+        .rev()
+        .chain(std::iter::once(&(chars.len() as u32)))
+    {
+        hash = ((hash >> 14) & 0x01) | ((hash << 1) & 0x7fff);
+        hash ^= c;
+    }
+    hash ^= 0xCE4B;
+    Ok(format!("{:X}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passwd_hash() {
+        let (hash, salt) =
+            gen_iso_passwd_hash("password", "SHA-512", Some("Ji7cyBXK5dPzNUU2IrK0aw=="), 10)
+                .unwrap();
+        assert_eq!(salt, "Ji7cyBXK5dPzNUU2IrK0aw==");
+        assert!(verify_iso_passwd_hash("password", "SHA-512", &hash, &salt, 10).unwrap());
+        assert!(!verify_iso_passwd_hash("Password", "SHA-512", &hash, &salt, 10).unwrap());
+        assert!(gen_iso_passwd_hash("password", "MD5", None, 10).is_err());
+        assert!(gen_iso_passwd_hash("", "SHA-512", None, 10).is_err());
+        assert_eq!(gen_legacy_passwd_hash("password").unwrap(), "83AF");
+        let long_password = "p".repeat(255);
+        assert!(gen_legacy_passwd_hash(&long_password).is_ok());
+        assert_ne!(
+            gen_legacy_passwd_hash(&long_password).unwrap(),
+            gen_legacy_passwd_hash(&"p".repeat(254)).unwrap()
+        );
+    }
+}
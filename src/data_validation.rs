@@ -0,0 +1,690 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    calc::{calc_formula, get_cell_formula_value, number_to_text, FormulaValue},
+    cell_name_to_coordinates, coordinates_to_cell_name, coordinates_to_range_ref, offset_formula,
+    range_ref_to_coordinates, xml_worksheet, ExcelizeError, Spreadsheet, Worksheet,
+};
+
+static DATA_VALIDATION_TYPES: [&str; 8] = [
+    "none",
+    "whole",
+    "decimal",
+    "list",
+    "date",
+    "time",
+    "textLength",
+    "custom",
+];
+static DATA_VALIDATION_OPERATORS: [&str; 8] = [
+    "between",
+    "notBetween",
+    "equal",
+    "notEqual",
+    "lessThan",
+    "lessThanOrEqual",
+    "greaterThan",
+    "greaterThanOrEqual",
+];
+static DATA_VALIDATION_ERROR_STYLES: [&str; 3] = ["stop", "warning", "information"];
+static MAX_DATA_VALIDATION_FORMULA_LENGTH: usize = 255;
+static MAX_DATA_VALIDATION_TITLE_LENGTH: usize = 32;
+static MAX_DATA_VALIDATION_MESSAGE_LENGTH: usize = 255;
+
+/// DataValidation directly maps the settings of the data validation rule. The
+/// type is one of "none", "whole", "decimal", "list", "date", "time",
+/// "textLength" and "custom", the operator is used by the whole, decimal,
+/// date, time and text length types. Note that the show_drop_down hides the
+/// in-cell dropdown of the list type when it's true.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataValidation {
+    pub sqref: String,
+    pub type_attr: String,
+    pub operator: String,
+    pub formula1: String,
+    pub formula2: String,
+    pub allow_blank: bool,
+    pub show_drop_down: bool,
+    pub show_input_message: bool,
+    pub show_error_message: bool,
+    pub error_style: Option<String>,
+    pub error_title: Option<String>,
+    pub error: Option<String>,
+    pub prompt_title: Option<String>,
+    pub prompt: Option<String>,
+}
+
+impl DataValidation {
+    // new provides a function to create the data validation rule on the range
+    // by given range reference, the blank cells are allowed if the
+    // allow_blank is true.
+    pub fn new(sqref: &str, allow_blank: bool) -> Self {
+        DataValidation {
+            sqref: String::from(sqref),
+            allow_blank,
+            ..Default::default()
+        }
+    }
+
+    // set_drop_list provides a function to set the inline dropdown list of
+    // the data validation, the total length of the list items can't exceed
+    // 255 characters.
+    pub fn set_drop_list(&mut self, keys: &[&str]) -> Result<(), ExcelizeError> {
+        let list = keys.join(",");
+        if list.chars().count() > MAX_DATA_VALIDATION_FORMULA_LENGTH {
+            return Err(ExcelizeError::CommonError(format!(
+                "data validation must be 0-{} characters",
+                MAX_DATA_VALIDATION_FORMULA_LENGTH
+            )));
+        }
+        self.type_attr = String::from("list");
+        self.formula1 = format!("\"{}\"", list);
+        Ok(())
+    }
+
+    // set_sqref_drop_list provides a function to set the dropdown list of the
+    // data validation from the cells of the range or the defined name, for
+    // example "$E$1:$E$3", "Sheet2!$A$1:$A$3" or "Fruits".
+    pub fn set_sqref_drop_list(&mut self, sqref: &str) {
+        self.type_attr = String::from("list");
+        self.formula1 = String::from(sqref);
+    }
+
+    // set_range provides a function to set the bounds of the data validation
+    // by given formulas, type and operator, the second formula is only used
+    // by the between and not between operators.
+    pub fn set_range(
+        &mut self,
+        formula1: &str,
+        formula2: &str,
+        type_attr: &str,
+        operator: &str,
+    ) -> Result<(), ExcelizeError> {
+        if !["whole", "decimal", "date", "time", "textLength"].contains(&type_attr) {
+            return Err(ExcelizeError::CommonError(format!(
+                "unsupported data validation range type {}",
+                type_attr
+            )));
+        }
+        if !DATA_VALIDATION_OPERATORS.contains(&operator) {
+            return Err(ExcelizeError::CommonError(format!(
+                "unsupported data validation operator {}",
+                operator
+            )));
+        }
+        self.type_attr = String::from(type_attr);
+        self.operator = String::from(operator);
+        self.formula1 = String::from(formula1);
+        self.formula2 = match operator {
+            "between" | "notBetween" => String::from(formula2),
+            _ => String::new(),
+        };
+        Ok(())
+    }
+
+    // set_formula provides a function to set the custom formula of the data
+    // validation, the value is valid when the formula is evaluated to true.
+    pub fn set_formula(&mut self, formula: &str) {
+        self.type_attr = String::from("custom");
+        self.operator = String::new();
+        self.formula1 = String::from(formula.trim_start_matches('='));
+        self.formula2 = String::new();
+    }
+
+    // set_input provides a function to set the input prompt of the data
+    // validation by given title and message.
+    pub fn set_input(&mut self, title: &str, message: &str) {
+        self.show_input_message = true;
+        self.prompt_title = Some(String::from(title));
+        self.prompt = Some(String::from(message));
+    }
+
+    // set_error provides a function to set the error alert of the data
+    // validation by given style, title and message, the style is one of
+    // "stop", "warning" and "information".
+    pub fn set_error(&mut self, style: &str, title: &str, message: &str) {
+        self.show_error_message = true;
+        self.error_style = Some(String::from(style));
+        self.error_title = Some(String::from(title));
+        self.error = Some(String::from(message));
+    }
+}
+
+/// DataValidationViolation directly maps the cell whose value violates the
+/// data validation rule. The message is the error message of the rule if it's
+/// specified, otherwise a message which describes the constraint.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataValidationViolation {
+    pub cell: String,
+    pub rule: DataValidation,
+    pub message: String,
+}
+
+pub trait DataValidations {
+    /// add_data_validation provides a function to add the data validation
+    /// rule by given worksheet name and the data validation settings. For
+    /// example, set a dropdown list on Sheet1!A1:A10 with an input prompt:
+    ///
+    /// ```ignore
+    /// let mut dv = DataValidation::new("A1:A10", true);
+    /// dv.set_drop_list(&["Apple", "Orange", "Banana"])?;
+    /// dv.set_input("Fruit", "Select a fruit from the list");
+    /// spreadsheet.add_data_validation("Sheet1", &dv)?;
+    /// ```
+    fn add_data_validation(
+        &mut self,
+        sheet: &str,
+        data_validation: &DataValidation,
+    ) -> Result<(), ExcelizeError>;
+    /// get_data_validations provides a function to get the data validation
+    /// rules of the worksheet by given worksheet name.
+    fn get_data_validations(&self, sheet: &str) -> Result<Vec<DataValidation>, ExcelizeError>;
+    /// delete_data_validation provides a function to delete the data
+    /// validation of the cells by given worksheet name and range reference.
+    /// The ranges of the rules that partially overlap the given range will be
+    /// split into the remaining ranges. All the rules of the worksheet will be
+    /// deleted if the range reference is empty.
+    fn delete_data_validation(&mut self, sheet: &str, sqref: &str) -> Result<(), ExcelizeError>;
+    /// validate_sheet provides a function to evaluate the data validation
+    /// rules of the worksheet against the current cell values by given
+    /// worksheet name, and returns the cells which violate the rules. The
+    /// lists are resolved from the inline items, the ranges or the defined
+    /// names, and the bounds and custom formulas are evaluated with the
+    /// relative references adjusted to each cell. The blank cells are not
+    /// validated.
+    fn validate_sheet(&self, sheet: &str) -> Result<Vec<DataValidationViolation>, ExcelizeError>;
+}
+
+impl DataValidations for Spreadsheet {
+    fn add_data_validation(
+        &mut self,
+        sheet: &str,
+        data_validation: &DataValidation,
+    ) -> Result<(), ExcelizeError> {
+        let dv = data_validation;
+        let refs = parse_sqref(&dv.sqref)?;
+        if refs.is_empty() {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid range reference {:?}",
+                dv.sqref
+            )));
+        }
+        let type_attr = match dv.type_attr.as_str() {
+            "" => "none",
+            type_attr => type_attr,
+        };
+        if !DATA_VALIDATION_TYPES.contains(&type_attr) {
+            return Err(ExcelizeError::CommonError(format!(
+                "unsupported data validation type {}",
+                type_attr
+            )));
+        }
+        let has_operator = ["whole", "decimal", "date", "time", "textLength"].contains(&type_attr);
+        if has_operator
+            && !dv.operator.is_empty()
+            && !DATA_VALIDATION_OPERATORS.contains(&dv.operator.as_str())
+        {
+            return Err(ExcelizeError::CommonError(format!(
+                "unsupported data validation operator {}",
+                dv.operator
+            )));
+        }
+        if type_attr != "none" && dv.formula1.is_empty() {
+            return Err(ExcelizeError::CommonError(String::from(
+                "data validation formula can't be empty",
+            )));
+        }
+        if has_operator
+            && ["", "between", "notBetween"].contains(&dv.operator.as_str())
+            && dv.formula2.is_empty()
+        {
+            return Err(ExcelizeError::CommonError(String::from(
+                "data validation formula2 can't be empty for the between operators",
+            )));
+        }
+        if dv.formula1.chars().count() > MAX_DATA_VALIDATION_FORMULA_LENGTH
+            || dv.formula2.chars().count() > MAX_DATA_VALIDATION_FORMULA_LENGTH
+        {
+            return Err(ExcelizeError::CommonError(format!(
+                "data validation must be 0-{} characters",
+                MAX_DATA_VALIDATION_FORMULA_LENGTH
+            )));
+        }
+        if let Some(ref style) = dv.error_style {
+            if !DATA_VALIDATION_ERROR_STYLES.contains(&style.as_str()) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "unsupported data validation error style {}",
+                    style
+                )));
+            }
+        }
+        for (text, max) in [
+            (&dv.error_title, MAX_DATA_VALIDATION_TITLE_LENGTH),
+            (&dv.prompt_title, MAX_DATA_VALIDATION_TITLE_LENGTH),
+            (&dv.error, MAX_DATA_VALIDATION_MESSAGE_LENGTH),
+            (&dv.prompt, MAX_DATA_VALIDATION_MESSAGE_LENGTH),
+        ] {
+            if text.as_ref().map_or(0, |t| t.chars().count()) > max {
+                return Err(ExcelizeError::CommonError(format!(
+                    "data validation title and message must be 0-{} characters",
+                    max
+                )));
+            }
+        }
+        let optional = |s: &str| (!s.is_empty()).then(|| String::from(s));
+        let rule = xml_worksheet::CTDataValidation {
+            type_attr: (type_attr != "none").then(|| String::from(type_attr)),
+            error_style: dv.error_style.clone(),
+            operator: if has_operator {
+                optional(&dv.operator)
+            } else {
+                None
+            },
+            allow_blank: dv.allow_blank.then_some(true),
+            show_drop_down: dv.show_drop_down.then_some(true),
+            show_input_message: dv.show_input_message.then_some(true),
+            show_error_message: dv.show_error_message.then_some(true),
+            error_title: dv.error_title.clone(),
+            error: dv.error.clone(),
+            prompt_title: dv.prompt_title.clone(),
+            prompt: dv.prompt.clone(),
+            sqref: join_sqref(&refs)?,
+            formula1: optional(&dv.formula1),
+            formula2: if has_operator {
+                optional(&dv.formula2)
+            } else {
+                None
+            },
+            ..Default::default()
+        };
+        let ws = self.get_worksheet_mut(sheet)?;
+        let data_validations = ws.data_validations.get_or_insert_with(Default::default);
+        data_validations.data_validation.push(rule);
+        data_validations.count = Some(data_validations.data_validation.len() as u32);
+        Ok(())
+    }
+
+    fn get_data_validations(&self, sheet: &str) -> Result<Vec<DataValidation>, ExcelizeError> {
+        let ws = self.get_worksheet(sheet)?;
+        Ok(ws
+            .data_validations
+            .iter()
+            .flat_map(|dvs| dvs.data_validation.iter())
+            .map(|dv| DataValidation {
+                sqref: dv.sqref.clone(),
+                type_attr: dv.type_attr.clone().unwrap_or_else(|| String::from("none")),
+                operator: dv.operator.clone().unwrap_or_default(),
+                formula1: dv.formula1.clone().unwrap_or_default(),
+                formula2: dv.formula2.clone().unwrap_or_default(),
+                allow_blank: dv.allow_blank.unwrap_or(false),
+                show_drop_down: dv.show_drop_down.unwrap_or(false),
+                show_input_message: dv.show_input_message.unwrap_or(false),
+                show_error_message: dv.show_error_message.unwrap_or(false),
+                error_style: dv.error_style.clone(),
+                error_title: dv.error_title.clone(),
+                error: dv.error.clone(),
+                prompt_title: dv.prompt_title.clone(),
+                prompt: dv.prompt.clone(),
+            })
+            .collect())
+    }
+
+    fn delete_data_validation(&mut self, sheet: &str, sqref: &str) -> Result<(), ExcelizeError> {
+        let deletes = parse_sqref(sqref)?;
+        let ws = self.get_worksheet_mut(sheet)?;
+        let data_validations = match ws.data_validations {
+            Some(ref mut data_validations) => data_validations,
+            None => return Ok(()),
+        };
+        if deletes.is_empty() {
+            ws.data_validations = None;
+            return Ok(());
+        }
+        let mut rules = Vec::new();
+        for mut rule in data_validations.data_validation.drain(..) {
+            let mut refs = parse_sqref(&rule.sqref)?;
+            for delete in &deletes {
+                refs = refs
+                    .iter()
+                    .flat_map(|r| subtract_range(r, delete))
+                    .collect();
+            }
+            if !refs.is_empty() {
+                rule.sqref = join_sqref(&refs)?;
+                rules.push(rule);
+            }
+        }
+        if rules.is_empty() {
+            ws.data_validations = None;
+            return Ok(());
+        }
+        data_validations.count = Some(rules.len() as u32);
+        data_validations.data_validation = rules;
+        Ok(())
+    }
+
+    fn validate_sheet(&self, sheet: &str) -> Result<Vec<DataValidationViolation>, ExcelizeError> {
+        let ws = self.get_worksheet(sheet)?;
+        let mut violations = Vec::new();
+        for rule in self.get_data_validations(sheet)? {
+            let refs = parse_sqref(&rule.sqref)?;
+            let base = match refs.first() {
+                Some(r) => (r[0], r[1]),
+                None => continue,
+            };
+            let mut cells = Vec::new();
+            for r in ws.sheet_data.row.iter().flatten() {
+                for c in &r.c {
+                    if let Ok((col, row)) = cell_name_to_coordinates(&c.r) {
+                        if refs
+                            .iter()
+                            .any(|r| (r[0]..=r[2]).contains(&col) && (r[1]..=r[3]).contains(&row))
+                        {
+                            cells.push((row, col));
+                        }
+                    }
+                }
+            }
+            cells.sort_unstable();
+            cells.dedup();
+            for (row, col) in cells {
+                let value = get_cell_formula_value(self, ws, col, row);
+                if matches!(value, FormulaValue::Blank)
+                    || value == FormulaValue::Text(String::new())
+                {
+                    continue;
+                }
+                let offset = (
+                    i64::from(col) - i64::from(base.0),
+                    i64::from(row) - i64::from(base.1),
+                );
+                if let Some(message) = validate_cell_value(self, sheet, &rule, &value, offset) {
+                    violations.push(DataValidationViolation {
+                        cell: coordinates_to_cell_name(col, row)?,
+                        message: match rule.error {
+                            Some(ref error) if !error.is_empty() => error.clone(),
+                            _ => message,
+                        },
+                        rule: rule.clone(),
+                    });
+                }
+            }
+        }
+        Ok(violations)
+    }
+}
+
+// validate_cell_value provides a function to check the value of the cell
+// against the data validation rule, the relative references in the formulas
+// of the rule are offset by the distance from the top-left cell of the rule.
+// Returns the message describes the constraint if the value is invalid.
+fn validate_cell_value(
+    spreadsheet: &Spreadsheet,
+    sheet: &str,
+    rule: &DataValidation,
+    value: &FormulaValue,
+    offset: (i64, i64),
+) -> Option<String> {
+    let eval = |formula: &str| {
+        calc_formula(
+            spreadsheet,
+            sheet,
+            &offset_formula(formula, offset.0, offset.1),
+        )
+        .unwrap_or_else(|_| FormulaValue::Error(String::from("#NAME?")))
+    };
+    if rule.type_attr == "none" {
+        return None;
+    }
+    if let FormulaValue::Error(e) = value {
+        return Some(format!("the value {} is an error", e));
+    }
+    let noun = match rule.type_attr.as_str() {
+        "whole" => "a whole number",
+        "decimal" => "a decimal",
+        "date" => "a date",
+        "time" => "a time",
+        "textLength" => "text length",
+        "list" => {
+            let text = value.to_text().unwrap_or_default();
+            let items: Vec<String> = match rule.formula1.strip_prefix('"') {
+                Some(list) => list
+                    .strip_suffix('"')
+                    .unwrap_or(list)
+                    .split(',')
+                    .map(|item| String::from(item.trim()))
+                    .collect(),
+                None => eval(&rule.formula1)
+                    .values()
+                    .iter()
+                    .filter_map(|item| item.to_text().ok())
+                    .collect(),
+            };
+            return match items
+                .iter()
+                .any(|item| item.to_lowercase() == text.to_lowercase())
+            {
+                true => None,
+                false => Some(format!("the value {} is not in the list", text)),
+            };
+        }
+        "custom" => {
+            return match eval(&rule.formula1) {
+                FormulaValue::Bool(true) => None,
+                FormulaValue::Number(n) if n != 0.0 => None,
+                _ => Some(format!(
+                    "the value doesn't satisfy the formula {}",
+                    rule.formula1
+                )),
+            };
+        }
+        _ => return None,
+    };
+    let n = match (rule.type_attr.as_str(), value) {
+        ("textLength", value) => value.to_text().unwrap_or_default().chars().count() as f64,
+        (_, FormulaValue::Number(n)) => *n,
+        _ => return Some(format!("the value must be {}", noun)),
+    };
+    if rule.type_attr == "whole" && n.fract() != 0.0 {
+        return Some(format!("the value must be {}", noun));
+    }
+    let bound = |formula: &str| eval(formula).to_number().ok();
+    let (f1, f2) = match (bound(&rule.formula1), rule.operator.as_str()) {
+        (Some(f1), "" | "between" | "notBetween") => match bound(&rule.formula2) {
+            Some(f2) => (f1, f2),
+            None => return Some(String::from("the bounds of the rule can't be evaluated")),
+        },
+        (Some(f1), _) => (f1, f1),
+        (None, _) => return Some(String::from("the bounds of the rule can't be evaluated")),
+    };
+    let (valid, constraint) = match rule.operator.as_str() {
+        "notBetween" => (
+            n < f1.min(f2) || n > f1.max(f2),
+            format!(
+                "not between {} and {}",
+                number_to_text(f1),
+                number_to_text(f2)
+            ),
+        ),
+        "equal" => (n == f1, format!("equal to {}", number_to_text(f1))),
+        "notEqual" => (n != f1, format!("not equal to {}", number_to_text(f1))),
+        "lessThan" => (n < f1, format!("less than {}", number_to_text(f1))),
+        "lessThanOrEqual" => (
+            n <= f1,
+            format!("less than or equal to {}", number_to_text(f1)),
+        ),
+        "greaterThan" => (n > f1, format!("greater than {}", number_to_text(f1))),
+        "greaterThanOrEqual" => (
+            n >= f1,
+            format!("greater than or equal to {}", number_to_text(f1)),
+        ),
+        _ => (
+            n >= f1.min(f2) && n <= f1.max(f2),
+            format!("between {} and {}", number_to_text(f1), number_to_text(f2)),
+        ),
+    };
+    match (valid, rule.type_attr.as_str()) {
+        (true, _) => None,
+        (false, "textLength") => Some(format!("the text length must be {}", constraint)),
+        (false, _) => Some(format!("the value must be {} {}", noun, constraint)),
+    }
+}
+
+// parse_sqref provides a function to convert the space-separated range
+// references to the coordinates of the ranges.
+pub(crate) fn parse_sqref(sqref: &str) -> Result<Vec<[u32; 4]>, ExcelizeError> {
+    sqref
+        .split_whitespace()
+        .map(range_ref_to_coordinates)
+        .collect()
+}
+
+// join_sqref provides a function to convert the coordinates of the ranges to
+// the space-separated range references.
+pub(crate) fn join_sqref(refs: &[[u32; 4]]) -> Result<String, ExcelizeError> {
+    Ok(refs
+        .iter()
+        .map(coordinates_to_range_ref)
+        .collect::<Result<Vec<String>, ExcelizeError>>()?
+        .join(" "))
+}
+
+// subtract_range provides a function to subtract the range b from the range
+// a, the remaining area is split into up to four ranges above, below, left
+// and right of the intersection.
+fn subtract_range(a: &[u32; 4], b: &[u32; 4]) -> Vec<[u32; 4]> {
+    let (x1, y1, x2, y2) = (
+        a[0].max(b[0]),
+        a[1].max(b[1]),
+        a[2].min(b[2]),
+        a[3].min(b[3]),
+    );
+    if x1 > x2 || y1 > y2 {
+        return vec![*a];
+    }
+    let mut refs = Vec::new();
+    if a[1] < y1 {
+        refs.push([a[0], a[1], a[2], y1 - 1]);
+    }
+    if y2 < a[3] {
+        refs.push([a[0], y2 + 1, a[2], a[3]]);
+    }
+    if a[0] < x1 {
+        refs.push([a[0], y1, x1 - 1, y2]);
+    }
+    if x2 < a[2] {
+        refs.push([x2 + 1, y1, a[2], y2]);
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_validations() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert!(wb.get_data_validations("Sheet1").unwrap().is_empty());
+
+        let mut dv = DataValidation::new("A1:C3", true);
+        dv.set_drop_list(&["Apple", "Orange"]).unwrap();
+        dv.set_input("Fruit", "Select a fruit");
+        dv.set_error("stop", "Error", "Invalid fruit");
+        wb.add_data_validation("Sheet1", &dv).unwrap();
+        assert!(dv.set_drop_list(&["A"; 200]).is_err());
+
+        let mut dv = DataValidation::new("E1:E10", false);
+        dv.set_range("10", "20", "whole", "between").unwrap();
+        wb.add_data_validation("Sheet1", &dv).unwrap();
+        assert!(dv.set_range("1", "", "list", "equal").is_err());
+        dv.formula2 = String::new();
+        assert!(wb.add_data_validation("Sheet1", &dv).is_err());
+
+        let mut dv = DataValidation::new("F1", false);
+        dv.set_formula("=ISNUMBER(F1)");
+        wb.add_data_validation("Sheet1", &dv).unwrap();
+        dv.sqref = String::from("F0");
+        assert!(wb.add_data_validation("Sheet1", &dv).is_err());
+
+        let dvs = wb.get_data_validations("Sheet1").unwrap();
+        assert_eq!(dvs.len(), 3);
+        assert_eq!(dvs[0].type_attr, "list");
+        assert_eq!(dvs[0].formula1, "\"Apple,Orange\"");
+        assert_eq!(dvs[0].prompt.as_deref(), Some("Select a fruit"));
+        assert_eq!(dvs[1].formula2, "20");
+        assert_eq!(dvs[2].formula1, "ISNUMBER(F1)");
+
+        wb.delete_data_validation("Sheet1", "B2 E1:F10").unwrap();
+        let dvs = wb.get_data_validations("Sheet1").unwrap();
+        assert_eq!(dvs.len(), 1);
+        assert_eq!(dvs[0].sqref, "A1:C1 A3:C3 A2 C2");
+        let ws = wb.get_worksheet("Sheet1").unwrap();
+        assert_eq!(ws.data_validations.as_ref().unwrap().count, Some(1));
+
+        wb.delete_data_validation("Sheet1", "").unwrap();
+        assert!(wb.get_data_validations("Sheet1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_sheet() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        wb.workbook.as_mut().unwrap().defined_names = Some(crate::XMLDefinedNames {
+            defined_name: vec![crate::XMLDefinedName {
+                name: String::from("Brands"),
+                comment: None,
+                local_sheet_id: None,
+                hidden: None,
+                data: String::from("Sheet2!$C$2:$C$11"),
+            }],
+        });
+        let mut dv = DataValidation::new("B2:B5", true);
+        dv.set_range("20", "60", "whole", "between").unwrap();
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        let mut dv = DataValidation::new("C2:C5", true);
+        dv.set_drop_list(&["hp", "DELL"]).unwrap();
+        dv.set_error("stop", "Brand", "Invalid brand");
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        let mut dv = DataValidation::new("C2:C11", true);
+        dv.set_sqref_drop_list("Brands");
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        let mut dv = DataValidation::new("D2:D11", true);
+        dv.set_formula("D2<MAX($D$2:$D$11)*0.8");
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        let mut dv = DataValidation::new("A2:A5", true);
+        dv.set_range("10", "", "textLength", "lessThan").unwrap();
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+
+        let violations = wb.validate_sheet("Sheet2").unwrap();
+        let cells: Vec<&str> = violations.iter().map(|v| v.cell.as_str()).collect();
+        assert_eq!(cells, vec!["B2", "C4", "C5", "D3", "D5", "A3", "A4"]);
+        assert_eq!(
+            violations[0].message,
+            "the value must be a whole number between 20 and 60"
+        );
+        assert_eq!(violations[1].message, "Invalid brand");
+        assert_eq!(violations[3].rule.type_attr, "custom");
+        assert_eq!(
+            violations[5].message,
+            "the text length must be less than 10"
+        );
+        assert!(wb.validate_sheet("Sheet1").unwrap().is_empty());
+
+        // the dates out of range are evaluated to the #NUM! error
+        let mut dv = DataValidation::new("D6", true);
+        dv.set_formula("DATE(1E+18,1,1)>0");
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        let mut dv = DataValidation::new("D7", true);
+        dv.set_formula("YEAR(1E+300)>0");
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        // the texts of the non-finite numbers are evaluated to the #VALUE! error
+        let mut dv = DataValidation::new("D8", true);
+        dv.set_formula("MID(\"abc\",\"nan\",1)=\"a\"");
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        let violations = wb.validate_sheet("Sheet2").unwrap();
+        let cells: Vec<&str> = violations.iter().map(|v| v.cell.as_str()).collect();
+        assert!(cells.contains(&"D6") && cells.contains(&"D7") && cells.contains(&"D8"));
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    calc::parse_reference, cell_name_to_coordinates, coordinates_to_range_ref, ExcelizeError,
+    Spreadsheet, Worksheet, XMLDefinedName, XMLDefinedNames, FILTER_DATABASE, TOTAL_COLUMNS,
+    TOTAL_ROWS,
+};
+
+// PRINT_AREA and PRINT_TITLES defined the built-in defined names of the print
+// area and the print titles of the worksheet.
+pub(crate) static PRINT_AREA: &str = "_xlnm.Print_Area";
+pub(crate) static PRINT_TITLES: &str = "_xlnm.Print_Titles";
+static WORKBOOK_SCOPE: &str = "Workbook";
+static MAX_DEFINED_NAME_LENGTH: usize = 255;
+static MAX_DEFINED_NAME_DEPTH: usize = 16;
+
+/// DefinedName directly maps the settings of the defined name. The scope is
+/// the worksheet name for the worksheet scoped name, or "Workbook" for the
+/// workbook scoped name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DefinedName {
+    pub name: String,
+    pub refers_to: String,
+    pub scope: String,
+    pub comment: String,
+    pub hidden: bool,
+}
+
+pub trait DefinedNames {
+    /// set_defined_name provides a function to add the defined name by given
+    /// name, formula the name refers to, scope and comment. The scope is the
+    /// worksheet name, or the empty string or "Workbook" for the workbook
+    /// scope. The built-in names _xlnm.Print_Area, _xlnm.Print_Titles and
+    /// _xlnm._FilterDatabase are supported for the worksheet scope. For
+    /// example, create the name Amount refers to Sheet1!$A$2:$D$5 for the
+    /// workbook, and set the print titles of Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.set_defined_name("Amount", "Sheet1!$A$2:$D$5", "", "")?;
+    /// spreadsheet.set_defined_name(
+    ///     "_xlnm.Print_Titles",
+    ///     "Sheet1!$A:$A,Sheet1!$1:$1",
+    ///     "Sheet1",
+    ///     "",
+    /// )?;
+    /// ```
+    fn set_defined_name(
+        &mut self,
+        name: &str,
+        refers_to: &str,
+        scope: &str,
+        comment: &str,
+    ) -> Result<(), ExcelizeError>;
+    /// get_defined_names provides a function to get the defined names of the
+    /// workbook.
+    fn get_defined_names(&self) -> Vec<DefinedName>;
+    /// delete_defined_name provides a function to delete the defined name by
+    /// given name and scope.
+    fn delete_defined_name(&mut self, name: &str, scope: &str) -> Result<(), ExcelizeError>;
+    /// resolve_defined_name provides a function to get the worksheet names and
+    /// the range references which the defined name refers to. The worksheet
+    /// scoped name is qualified by the worksheet name, such as
+    /// Sheet1!_xlnm.Print_Area, the names refer to other names are resolved
+    /// recursively.
+    fn resolve_defined_name(&self, name: &str) -> Result<Vec<(String, String)>, ExcelizeError>;
+}
+
+impl DefinedNames for Spreadsheet {
+    fn set_defined_name(
+        &mut self,
+        name: &str,
+        refers_to: &str,
+        scope: &str,
+        comment: &str,
+    ) -> Result<(), ExcelizeError> {
+        check_defined_name(name)?;
+        let refers_to = refers_to.trim().trim_start_matches('=');
+        if refers_to.is_empty() {
+            return Err(ExcelizeError::CommonError(format!(
+                "the defined name {} refers to nothing",
+                name
+            )));
+        }
+        let local_sheet_id = get_defined_name_scope(self, scope)?;
+        let built_in = name.starts_with("_xlnm.");
+        if built_in && local_sheet_id.is_none() {
+            return Err(ExcelizeError::CommonError(format!(
+                "the built-in name {} must be scoped to a worksheet",
+                name
+            )));
+        }
+        if (name == PRINT_AREA || name == PRINT_TITLES)
+            && split_union(refers_to)
+                .iter()
+                .any(|part| parse_reference(part).is_none())
+        {
+            return Err(ExcelizeError::CommonError(format!(
+                "the built-in name {} must refer to ranges",
+                name
+            )));
+        }
+        let wb = self
+            .workbook
+            .as_mut()
+            .ok_or_else(|| ExcelizeError::CommonError(String::from("workbook is not exist")))?;
+        let defined_names = wb.defined_names.get_or_insert_with(|| XMLDefinedNames {
+            defined_name: Vec::new(),
+        });
+        if defined_names.defined_name.iter().any(|d| {
+            d.name.to_lowercase() == name.to_lowercase() && d.local_sheet_id == local_sheet_id
+        }) {
+            return Err(ExcelizeError::CommonError(format!(
+                "the same name {} already exists on the scope",
+                name
+            )));
+        }
+        defined_names.defined_name.push(XMLDefinedName {
+            name: String::from(name),
+            comment: (!comment.is_empty()).then(|| String::from(comment)),
+            local_sheet_id,
+            hidden: (name == FILTER_DATABASE).then_some(true),
+            data: String::from(refers_to),
+        });
+        Ok(())
+    }
+
+    fn get_defined_names(&self) -> Vec<DefinedName> {
+        let wb = match self.workbook.as_ref() {
+            Some(wb) => wb,
+            None => return Vec::new(),
+        };
+        wb.defined_names
+            .iter()
+            .flat_map(|d| d.defined_name.iter())
+            .map(|d| DefinedName {
+                name: d.name.clone(),
+                refers_to: d.data.clone(),
+                scope: d
+                    .local_sheet_id
+                    .and_then(|id| wb.sheets.sheet.get(id as usize))
+                    .map_or_else(|| String::from(WORKBOOK_SCOPE), |s| s.name.clone()),
+                comment: d.comment.clone().unwrap_or_default(),
+                hidden: d.hidden.unwrap_or(false),
+            })
+            .collect()
+    }
+
+    fn delete_defined_name(&mut self, name: &str, scope: &str) -> Result<(), ExcelizeError> {
+        let local_sheet_id = get_defined_name_scope(self, scope)?;
+        let defined_names = self
+            .workbook
+            .as_mut()
+            .and_then(|wb| wb.defined_names.as_mut());
+        if let Some(defined_names) = defined_names {
+            if let Some(i) = defined_names.defined_name.iter().position(|d| {
+                d.name.to_lowercase() == name.to_lowercase() && d.local_sheet_id == local_sheet_id
+            }) {
+                defined_names.defined_name.remove(i);
+                return Ok(());
+            }
+        }
+        Err(ExcelizeError::CommonError(format!(
+            "no defined name {} on the scope",
+            name
+        )))
+    }
+
+    fn resolve_defined_name(&self, name: &str) -> Result<Vec<(String, String)>, ExcelizeError> {
+        let (scope, name) = match name.rsplit_once('!') {
+            Some((sheet, name)) => {
+                let sheet = sheet
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+                    .map_or_else(|| String::from(sheet), |s| s.replace("''", "'"));
+                (Some(self.get_sheet_index(&sheet)? as u32), name)
+            }
+            None => (None, name),
+        };
+        let mut ranges = Vec::new();
+        resolve_name(self, name, scope, 0, &mut ranges)?;
+        Ok(ranges)
+    }
+}
+
+// get_defined_name_scope provides a function to get the local sheet ID of
+// the defined name by given scope, returns None for the workbook scope.
+fn get_defined_name_scope(
+    spreadsheet: &Spreadsheet,
+    scope: &str,
+) -> Result<Option<u32>, ExcelizeError> {
+    match scope {
+        "" => Ok(None),
+        scope if scope == WORKBOOK_SCOPE => Ok(None),
+        sheet => Ok(Some(spreadsheet.get_sheet_index(sheet)? as u32)),
+    }
+}
+
+// resolve_name provides a function to resolve the defined name in the
+// scope to the ranges, the workbook scoped name will be used if the name
+// is not defined in the worksheet scope.
+fn resolve_name(
+    spreadsheet: &Spreadsheet,
+    name: &str,
+    scope: Option<u32>,
+    depth: usize,
+    ranges: &mut Vec<(String, String)>,
+) -> Result<(), ExcelizeError> {
+    if depth >= MAX_DEFINED_NAME_DEPTH {
+        return Err(ExcelizeError::CommonError(format!(
+            "circular reference in the defined name {}",
+            name
+        )));
+    }
+    let wb = spreadsheet
+        .workbook
+        .as_ref()
+        .ok_or_else(|| ExcelizeError::CommonError(String::from("workbook is not exist")))?;
+    let defined_names = wb
+        .defined_names
+        .as_ref()
+        .map(|d| d.defined_name.as_slice())
+        .unwrap_or_default();
+    let find = |local_sheet_id: Option<u32>| {
+        defined_names.iter().find(|d| {
+            d.name.to_lowercase() == name.to_lowercase() && d.local_sheet_id == local_sheet_id
+        })
+    };
+    let defined_name = scope
+        .and_then(|id| find(Some(id)))
+        .or_else(|| find(None))
+        .ok_or_else(|| {
+            ExcelizeError::CommonError(format!("the defined name {} is not exist", name))
+        })?;
+    let local_sheet = defined_name
+        .local_sheet_id
+        .and_then(|id| wb.sheets.sheet.get(id as usize))
+        .map(|s| s.name.clone());
+    for part in split_union(defined_name.data.trim_start_matches('=')) {
+        match parse_reference(part) {
+            Some((sheet, area)) => {
+                let sheet = sheet.or_else(|| local_sheet.clone()).ok_or_else(|| {
+                    ExcelizeError::CommonError(format!(
+                        "the reference {} of the defined name {} has no worksheet",
+                        part, name
+                    ))
+                })?;
+                ranges.push((sheet, coordinates_to_range_ref(&area)?));
+            }
+            None if check_defined_name(part).is_ok() => resolve_name(
+                spreadsheet,
+                part,
+                defined_name.local_sheet_id,
+                depth + 1,
+                ranges,
+            )?,
+            None => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "the defined name {} doesn't refer to a range",
+                    name
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+// check_defined_name provides a function to check the syntax of the defined
+// name. The name starts with a letter, underscore or backslash, followed by
+// the letters, digits, underscores, periods and backslashes, and can't be the
+// same as a cell reference. The names prefixed with _xlnm. are reserved for
+// the built-in names.
+pub(crate) fn check_defined_name(name: &str) -> Result<(), ExcelizeError> {
+    let invalid = || ExcelizeError::CommonError(format!("invalid defined name {:?}", name));
+    if name.starts_with("_xlnm.") {
+        return match [PRINT_AREA, PRINT_TITLES, FILTER_DATABASE].contains(&name) {
+            true => Ok(()),
+            false => Err(invalid()),
+        };
+    }
+    if name.chars().count() > MAX_DEFINED_NAME_LENGTH {
+        return Err(ExcelizeError::CommonError(format!(
+            "the defined name {} exceeds the {} characters limit",
+            name, MAX_DEFINED_NAME_LENGTH
+        )));
+    }
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == '\\' => {}
+        _ => return Err(invalid()),
+    }
+    if !chars.all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '\\') {
+        return Err(invalid());
+    }
+    let is_cell = cell_name_to_coordinates(name).map_or(false, |(col, row)| {
+        col <= TOTAL_COLUMNS && row <= TOTAL_ROWS
+    });
+    let upper = name.to_uppercase();
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    let is_r1c1 = match upper.strip_prefix('R') {
+        Some(rest) => match rest.split_once('C') {
+            Some((row, col)) => digits(row) && digits(col),
+            None => digits(rest),
+        },
+        None => upper.strip_prefix('C').map_or(false, digits),
+    };
+    if is_cell || is_r1c1 {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+// split_union provides a function to split the union of the references by the
+// commas outside the quoted worksheet names.
+fn split_union(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut start, mut quoted) = (0, false);
+    for (i, c) in text.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            ',' if !quoted => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defined_names() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        for name in [
+            "",
+            "1st",
+            "A1",
+            "xfd1048576",
+            "R",
+            "c",
+            "R1C1",
+            "r2",
+            "a b",
+            "_xlnm.Foo",
+        ] {
+            assert!(wb.set_defined_name(name, "Sheet1!$A$1", "", "").is_err());
+        }
+        wb.set_defined_name("Amount", "=Sheet2!$D$2:$D$11", "", "Amounts")
+            .unwrap();
+        wb.set_defined_name("Amount", "Sheet2!$D$2:$D$5", "Sheet2", "")
+            .unwrap();
+        wb.set_defined_name("All", "Amount,'Sheet1'!$A$1", "Sheet1", "")
+            .unwrap();
+        wb.set_defined_name(
+            "_xlnm.Print_Titles",
+            "Sheet2!$1:$1,Sheet2!$A:$A",
+            "Sheet2",
+            "",
+        )
+        .unwrap();
+        wb.set_defined_name("Tax", "0.2", "", "").unwrap();
+        assert!(wb
+            .set_defined_name("AMOUNT", "Sheet2!$A$1", "", "")
+            .is_err());
+        assert!(wb
+            .set_defined_name("_xlnm.Print_Area", "Sheet2!$A$1", "", "")
+            .is_err());
+        assert!(wb
+            .set_defined_name("_xlnm.Print_Area", "0.2", "Sheet2", "")
+            .is_err());
+        assert!(wb
+            .set_defined_name("Total", "Sheet1!$A$1", "Sheet3", "")
+            .is_err());
+
+        let names = wb.get_defined_names();
+        assert_eq!(names.len(), 5);
+        assert_eq!(
+            names[0],
+            DefinedName {
+                name: String::from("Amount"),
+                refers_to: String::from("Sheet2!$D$2:$D$11"),
+                scope: String::from("Workbook"),
+                comment: String::from("Amounts"),
+                hidden: false,
+            }
+        );
+        assert_eq!(names[1].scope, "Sheet2");
+
+        let range = |sheet: &str, range: &str| (String::from(sheet), String::from(range));
+        assert_eq!(
+            wb.resolve_defined_name("Amount").unwrap(),
+            vec![range("Sheet2", "D2:D11")]
+        );
+        assert_eq!(
+            wb.resolve_defined_name("Sheet2!Amount").unwrap(),
+            vec![range("Sheet2", "D2:D5")]
+        );
+        assert_eq!(
+            wb.resolve_defined_name("'Sheet1'!All").unwrap(),
+            vec![range("Sheet2", "D2:D11"), range("Sheet1", "A1")]
+        );
+        assert_eq!(
+            wb.resolve_defined_name("Sheet2!_xlnm.Print_Titles")
+                .unwrap(),
+            vec![range("Sheet2", "A1:XFD1"), range("Sheet2", "A1:A1048576")]
+        );
+        assert!(wb.resolve_defined_name("Tax").is_err());
+        assert!(wb.resolve_defined_name("All").is_err());
+
+        wb.delete_defined_name("amount", "Sheet2").unwrap();
+        assert!(wb.delete_defined_name("Amount", "Sheet2").is_err());
+        assert_eq!(
+            wb.resolve_defined_name("Sheet2!Amount").unwrap(),
+            vec![range("Sheet2", "D2:D11")]
+        );
+        assert_eq!(wb.get_defined_names().len(), 4);
+
+        // the names are case-insensitive for the non-ASCII letters as well
+        wb.set_defined_name("\u{c4}rger", "Sheet1!$A$1", "", "")
+            .unwrap();
+        assert!(wb
+            .set_defined_name("\u{e4}RGER", "Sheet1!$A$1", "", "")
+            .is_err());
+        assert_eq!(
+            wb.resolve_defined_name("\u{e4}rger").unwrap(),
+            vec![range("Sheet1", "A1")]
+        );
+        wb.delete_defined_name("\u{e4}rger", "").unwrap();
+        assert_eq!(wb.get_defined_names().len(), 4);
+    }
+}
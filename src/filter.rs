@@ -0,0 +1,654 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    cell_name_to_coordinates, get_row, prepare_row, range_ref_to_coordinates, CTCustomFilter,
+    CTCustomFilters, CTDateGroupItem, CTDynamicFilter, CTFilterColumn, CTFilters, CTTop10, Cell,
+    ExcelizeError, Spreadsheet, Worksheet, XMLWorksheet, MAX_DATE_SERIAL,
+};
+use std::cmp::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub trait Filter {
+    /// get_filtered_rows provides a function to evaluate the AutoFilter of
+    /// the worksheet by given worksheet name, and returns the row numbers in
+    /// the AutoFilter range which match the criteria of all filter columns,
+    /// the header row of the range is not included. The discrete values and
+    /// date groups, custom filters with wildcards, top 10 and dynamic filters
+    /// are supported, and the color and icon filters don't hide any rows.
+    /// The date based dynamic filters are evaluated with the current date in
+    /// UTC.
+    fn get_filtered_rows(&self, sheet: &str) -> Result<Vec<u32>, ExcelizeError>;
+    /// apply_auto_filter provides a function to evaluate the AutoFilter of the
+    /// worksheet by given worksheet name, hide the rows in the AutoFilter
+    /// range which don't match the criteria and show the others. The visible
+    /// row numbers will be returned.
+    fn apply_auto_filter(&mut self, sheet: &str) -> Result<Vec<u32>, ExcelizeError>;
+}
+
+impl Filter for Spreadsheet {
+    fn get_filtered_rows(&self, sheet: &str) -> Result<Vec<u32>, ExcelizeError> {
+        Ok(evaluate_auto_filter(self, sheet, today_serial())?.1)
+    }
+
+    fn apply_auto_filter(&mut self, sheet: &str) -> Result<Vec<u32>, ExcelizeError> {
+        let (coordinates, rows) = evaluate_auto_filter(self, sheet, today_serial())?;
+        let ws = self.get_worksheet_mut(sheet)?;
+        for row in coordinates[1] + 1..=coordinates[3] {
+            if rows.binary_search(&row).is_err() {
+                prepare_row(ws, row).hidden = Some(true);
+            } else if let Some(r) = ws
+                .sheet_data
+                .row
+                .iter_mut()
+                .flatten()
+                .find(|r| r.r == Some(row))
+            {
+                r.hidden = None;
+            }
+        }
+        Ok(rows)
+    }
+}
+
+// FilterValue defined the value of a cell to be evaluated by the filter
+// criteria.
+#[derive(Debug, Clone, PartialEq)]
+enum FilterValue {
+    Blank,
+    Number(f64),
+    Text(String),
+}
+
+impl FilterValue {
+    fn number(&self) -> Option<f64> {
+        match self {
+            FilterValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+// evaluate_auto_filter provides a function to evaluate the AutoFilter of the
+// worksheet with the given serial number of today, and returns the AutoFilter
+// range coordinates and the matched row numbers.
+fn evaluate_auto_filter(
+    spreadsheet: &Spreadsheet,
+    sheet: &str,
+    today: f64,
+) -> Result<([u32; 4], Vec<u32>), ExcelizeError> {
+    let ws = spreadsheet.get_worksheet(sheet)?;
+    let auto_filter = match ws.auto_filter {
+        Some(ref auto_filter) => auto_filter,
+        None => {
+            return Err(ExcelizeError::CommonError(format!(
+                "sheet {} has no AutoFilter",
+                sheet
+            )))
+        }
+    };
+    let coordinates =
+        range_ref_to_coordinates(auto_filter.ref_attr.as_deref().unwrap_or_default())?;
+    let rows: Vec<u32> = (coordinates[1] + 1..=coordinates[3]).collect();
+    let mut visible = vec![true; rows.len()];
+    for column in &auto_filter.filter_column {
+        let col = coordinates[0] + column.col_id;
+        let values: Vec<FilterValue> = rows
+            .iter()
+            .map(|row| get_filter_value(spreadsheet, ws, col, *row))
+            .collect();
+        for (v, matched) in visible
+            .iter_mut()
+            .zip(match_filter_column(column, &values, today)?)
+        {
+            *v &= matched;
+        }
+    }
+    let rows = rows
+        .into_iter()
+        .zip(visible)
+        .filter_map(|(row, v)| v.then_some(row))
+        .collect();
+    Ok((coordinates, rows))
+}
+
+// get_filter_value provides a function to get the value of the cell by given
+// column and row number.
+fn get_filter_value(
+    spreadsheet: &Spreadsheet,
+    ws: &XMLWorksheet,
+    col: u32,
+    row: u32,
+) -> FilterValue {
+    let cell = get_row(ws, row).and_then(|r| {
+        r.c.iter()
+            .find(|c| cell_name_to_coordinates(&c.r).map_or(false, |(x, _)| x == col))
+    });
+    let cell = match cell {
+        Some(cell) => cell,
+        None => return FilterValue::Blank,
+    };
+    let value = spreadsheet.get_value_from(cell);
+    if value.is_empty() {
+        return FilterValue::Blank;
+    }
+    match cell.t.as_deref() {
+        None | Some("n") => match value.parse::<f64>() {
+            Ok(n) => FilterValue::Number(n),
+            Err(_) => FilterValue::Text(value),
+        },
+        Some("b") => FilterValue::Text(String::from(match value.as_str() {
+            "1" => "TRUE",
+            _ => "FALSE",
+        })),
+        _ => FilterValue::Text(value),
+    }
+}
+
+// match_filter_column provides a function to evaluate the criteria of the
+// filter column for each value of the column.
+fn match_filter_column(
+    column: &CTFilterColumn,
+    values: &[FilterValue],
+    today: f64,
+) -> Result<Vec<bool>, ExcelizeError> {
+    if let Some(ref filters) = column.filters {
+        return Ok(values.iter().map(|v| match_filters(filters, v)).collect());
+    }
+    if let Some(ref custom_filters) = column.custom_filters {
+        return Ok(values
+            .iter()
+            .map(|v| match_custom_filters(custom_filters, v))
+            .collect());
+    }
+    if let Some(ref top10) = column.top10 {
+        return Ok(match_top10(top10, values));
+    }
+    if let Some(ref dynamic_filter) = column.dynamic_filter {
+        return match_dynamic_filter(dynamic_filter, values, today);
+    }
+    Ok(vec![true; values.len()])
+}
+
+// match_filters provides a function to check if the value matches one of the
+// discrete values or date groups.
+fn match_filters(filters: &CTFilters, value: &FilterValue) -> bool {
+    match value {
+        FilterValue::Blank => filters.blank.unwrap_or(false),
+        FilterValue::Number(n) => {
+            filters.filter.iter().any(|f| {
+                f.val.as_deref().map_or(false, |val| {
+                    val.parse::<f64>() == Ok(*n) || val == n.to_string()
+                })
+            }) || filters
+                .date_group_item
+                .iter()
+                .any(|item| match_date_group_item(item, *n))
+        }
+        FilterValue::Text(s) => {
+            let s = s.to_lowercase();
+            filters.filter.iter().any(|f| {
+                f.val
+                    .as_deref()
+                    .map_or(false, |val| val.to_lowercase() == s)
+            })
+        }
+    }
+}
+
+// match_date_group_item provides a function to check if the date serial
+// number is in the date group, all the parts of the date from the year to the
+// grouping level should be equal.
+fn match_date_group_item(item: &CTDateGroupItem, serial: f64) -> bool {
+    let depth = match item.date_time_grouping.as_str() {
+        "year" => 1,
+        "month" => 2,
+        "day" => 3,
+        "hour" => 4,
+        "minute" => 5,
+        "second" => 6,
+        _ => return false,
+    };
+    let datetime = match serial_to_datetime(serial) {
+        Some(datetime) => datetime,
+        None => return false,
+    };
+    let expected = [
+        Some(item.year),
+        item.month,
+        item.day,
+        item.hour,
+        item.minute,
+        item.second,
+    ];
+    datetime
+        .iter()
+        .zip(expected.iter())
+        .take(depth)
+        .all(|(v, e)| e.map_or(false, |e| *v == e as i64))
+}
+
+// match_custom_filters provides a function to check if the value matches the
+// custom filters, which are joined by 'and' or 'or'.
+fn match_custom_filters(custom_filters: &CTCustomFilters, value: &FilterValue) -> bool {
+    let mut results = custom_filters
+        .custom_filter
+        .iter()
+        .map(|f| match_custom_filter(f, value));
+    match custom_filters.and.unwrap_or(false) {
+        true => results.all(|m| m),
+        false => results.any(|m| m),
+    }
+}
+
+// match_custom_filter provides a function to check if the value matches the
+// custom filter. The wildcards are supported by the equal and notEqual
+// operators, the numbers are compared numerically and the texts are compared
+// case-insensitively.
+fn match_custom_filter(filter: &CTCustomFilter, value: &FilterValue) -> bool {
+    let operator = filter.operator.as_deref().unwrap_or("equal");
+    let val = filter.val.as_deref().unwrap_or_default();
+    match operator {
+        "equal" => match_pattern(value, val),
+        "notEqual" => !match_pattern(value, val),
+        _ => {
+            let ordering = match (value, val.parse::<f64>()) {
+                (FilterValue::Number(n), Ok(x)) => n.partial_cmp(&x),
+                (FilterValue::Text(s), Err(_)) => Some(s.to_lowercase().cmp(&val.to_lowercase())),
+                _ => None,
+            };
+            matches!(
+                (operator, ordering),
+                ("lessThan", Some(Ordering::Less))
+                    | ("lessThanOrEqual", Some(Ordering::Less | Ordering::Equal))
+                    | ("greaterThan", Some(Ordering::Greater))
+                    | (
+                        "greaterThanOrEqual",
+                        Some(Ordering::Greater | Ordering::Equal)
+                    )
+            )
+        }
+    }
+}
+
+// match_pattern provides a function to check if the value matches the
+// criteria value which may contain wildcards.
+fn match_pattern(value: &FilterValue, pattern: &str) -> bool {
+    match value {
+        FilterValue::Blank => pattern.is_empty(),
+        FilterValue::Number(n) => {
+            pattern.parse::<f64>() == Ok(*n) || wildcard_match(pattern, &n.to_string())
+        }
+        FilterValue::Text(s) => wildcard_match(&pattern.to_lowercase(), &s.to_lowercase()),
+    }
+}
+
+// wildcard_match provides a function to check if the text matches the
+// pattern, the asterisk matches any sequence of characters, the question mark
+// matches any single character and the tilde escapes the next character.
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    enum Token {
+        Any,
+        One,
+        Char(char),
+    }
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' => Token::Any,
+            '?' => Token::One,
+            '~' => Token::Char(chars.next().unwrap_or('~')),
+            _ => Token::Char(c),
+        });
+    }
+    let text: Vec<char> = text.chars().collect();
+    let mut matched = vec![false; text.len() + 1];
+    matched[0] = true;
+    for token in tokens {
+        let mut next = vec![false; text.len() + 1];
+        for i in 0..=text.len() {
+            next[i] = match token {
+                Token::Any => matched[i] || (i > 0 && next[i - 1]),
+                Token::One => i > 0 && matched[i - 1],
+                Token::Char(c) => i > 0 && matched[i - 1] && text[i - 1] == c,
+            };
+        }
+        matched = next;
+    }
+    matched[text.len()]
+}
+
+// match_top10 provides a function to check if the values are in the top or
+// bottom N items or percent of the numbers, the items equal to the boundary
+// value are included.
+fn match_top10(top10: &CTTop10, values: &[FilterValue]) -> Vec<bool> {
+    let top = top10.top.unwrap_or(true);
+    let mut numbers: Vec<f64> = values.iter().filter_map(FilterValue::number).collect();
+    numbers.sort_by(|a, b| match top {
+        true => b.partial_cmp(a).unwrap_or(Ordering::Equal),
+        false => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+    });
+    let count = match top10.percent.unwrap_or(false) {
+        true => (numbers.len() as f64 * top10.val / 100.0).floor().max(1.0) as usize,
+        false => top10.val as usize,
+    };
+    if numbers.is_empty() || count == 0 {
+        return vec![false; values.len()];
+    }
+    let boundary = numbers[count.min(numbers.len()) - 1];
+    values
+        .iter()
+        .map(|v| {
+            v.number().map_or(false, |n| match top {
+                true => n >= boundary,
+                false => n <= boundary,
+            })
+        })
+        .collect()
+}
+
+// match_dynamic_filter provides a function to check if the values match the
+// dynamic filter, the date based filters are evaluated with the given serial
+// number of today.
+fn match_dynamic_filter(
+    filter: &CTDynamicFilter,
+    values: &[FilterValue],
+    today: f64,
+) -> Result<Vec<bool>, ExcelizeError> {
+    let filter_type = filter.type_attr.as_str();
+    let numbers: Vec<f64> = values.iter().filter_map(FilterValue::number).collect();
+    let average = numbers.iter().sum::<f64>() / numbers.len().max(1) as f64;
+    let month_of = |n: f64| serial_to_datetime(n).map(|datetime| datetime[1]);
+    let interval = date_interval(filter_type, today);
+    let parse_index = |prefix: &str| {
+        filter_type
+            .strip_prefix(prefix)
+            .and_then(|i| i.parse::<i64>().ok())
+    };
+    let matcher: Box<dyn Fn(f64) -> bool> = match filter_type {
+        "null" => return Ok(vec![true; values.len()]),
+        "aboveAverage" => Box::new(move |n| n > average),
+        "belowAverage" => Box::new(move |n| n < average),
+        _ => match (parse_index("Q"), parse_index("M"), interval) {
+            (Some(q), _, _) if (1..=4).contains(&q) => {
+                Box::new(move |n| month_of(n).map_or(false, |m| (m - 1) / 3 + 1 == q))
+            }
+            (_, Some(i), _) if (1..=12).contains(&i) => Box::new(move |n| month_of(n) == Some(i)),
+            (_, _, Some((start, end))) => Box::new(move |n| n >= start && n < end),
+            _ => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "unsupported dynamic filter type {}",
+                    filter_type
+                )))
+            }
+        },
+    };
+    Ok(values
+        .iter()
+        .map(|v| v.number().map_or(false, &matcher))
+        .collect())
+}
+
+// date_interval provides a function to get the half-open interval of the date
+// serial numbers for the date based dynamic filter type, the weeks start from
+// Sunday.
+fn date_interval(filter_type: &str, today: f64) -> Option<(f64, f64)> {
+    let today = today.floor();
+    let [year, month, _, _, _, _] = serial_to_datetime(today)?;
+    let week = today - (days_from_serial(today) + 4).rem_euclid(7) as f64;
+    let quarter = (month - 1) / 3 * 3 + 1;
+    let months = |offset: i64, length: i64| {
+        (
+            month_serial(year, month + offset),
+            month_serial(year, month + offset + length),
+        )
+    };
+    let quarters = |offset: i64| {
+        (
+            month_serial(year, quarter + offset * 3),
+            month_serial(year, quarter + offset * 3 + 3),
+        )
+    };
+    let years = |offset: i64| {
+        (
+            month_serial(year + offset, 1),
+            month_serial(year + offset + 1, 1),
+        )
+    };
+    Some(match filter_type {
+        "yesterday" => (today - 1.0, today),
+        "today" => (today, today + 1.0),
+        "tomorrow" => (today + 1.0, today + 2.0),
+        "lastWeek" => (week - 7.0, week),
+        "thisWeek" => (week, week + 7.0),
+        "nextWeek" => (week + 7.0, week + 14.0),
+        "lastMonth" => months(-1, 1),
+        "thisMonth" => months(0, 1),
+        "nextMonth" => months(1, 1),
+        "lastQuarter" => quarters(-1),
+        "thisQuarter" => quarters(0),
+        "nextQuarter" => quarters(1),
+        "lastYear" => years(-1),
+        "thisYear" => years(0),
+        "nextYear" => years(1),
+        "yearToDate" => (month_serial(year, 1), today + 1.0),
+        _ => return None,
+    })
+}
+
+// today_serial provides a function to get the serial number of the current
+// date in UTC.
+pub(crate) fn today_serial() -> f64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    (secs / 86400) as f64 + 25569.0
+}
+
+// days_from_serial provides a function to convert the date serial number to
+// the number of days since the Unix epoch, the serial numbers before
+// 1900-03-01 are adjusted for the nonexistent date 1900-02-29.
+pub(crate) fn days_from_serial(serial: f64) -> i64 {
+    let days = serial.floor() as i64;
+    match days < 61 {
+        true => days - 25568,
+        false => days - 25569,
+    }
+}
+
+// month_serial provides a function to get the serial number of the first day
+// of the month, the month out of range 1 to 12 will be carried to the year.
+pub(crate) fn month_serial(year: i64, month: i64) -> f64 {
+    let months = year * 12 + month - 1;
+    (days_from_civil(months.div_euclid(12), months.rem_euclid(12) + 1, 1) + 25569) as f64
+}
+
+// serial_to_datetime provides a function to convert the date serial number to
+// the year, month, day, hour, minute and second, returns None if the serial
+// number is out of the range from 1900-01-01 to 9999-12-31.
+pub(crate) fn serial_to_datetime(serial: f64) -> Option<[i64; 6]> {
+    if !(1.0..MAX_DATE_SERIAL + 1.0).contains(&serial) {
+        return None;
+    }
+    let secs = (((serial - serial.floor()) * 86400.0).round() as i64).min(86399);
+    let (year, month, day) = civil_from_days(days_from_serial(serial));
+    Some([year, month, day, secs / 3600, secs % 3600 / 60, secs % 60])
+}
+
+// civil_from_days provides a function to convert the number of days since the
+// Unix epoch to the year, month and day in the proleptic Gregorian calendar.
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// days_from_civil provides a function to convert the year, month and day in
+// the proleptic Gregorian calendar to the number of days since the Unix epoch.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CTFilter, Table};
+
+    #[test]
+    fn test_apply_auto_filter() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert!(wb.get_filtered_rows("Sheet2").is_err());
+        let custom = |operator: &str, val: &str| CTCustomFilter {
+            operator: Some(String::from(operator)),
+            val: Some(String::from(val)),
+        };
+        wb.auto_filter(
+            "Sheet2",
+            "C1:D11",
+            vec![CTFilterColumn {
+                col_id: 1,
+                custom_filters: Some(CTCustomFilters {
+                    and: Some(true),
+                    custom_filter: vec![custom("greaterThan", "100"), custom("lessThan", "500")],
+                }),
+                ..Default::default()
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            wb.get_filtered_rows("Sheet2").unwrap(),
+            vec![2, 3, 4, 6, 7, 9]
+        );
+
+        wb.auto_filter(
+            "Sheet2",
+            "C1:D11",
+            vec![CTFilterColumn {
+                col_id: 1,
+                top10: Some(CTTop10 {
+                    top: None,
+                    percent: None,
+                    val: 3.0,
+                    filter_val: None,
+                }),
+                ..Default::default()
+            }],
+        )
+        .unwrap();
+        assert_eq!(wb.apply_auto_filter("Sheet2").unwrap(), vec![3, 5, 9]);
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        assert_eq!(get_row(ws, 2).unwrap().hidden, Some(true));
+        assert_eq!(get_row(ws, 3).unwrap().hidden, None);
+
+        wb.auto_filter(
+            "Sheet2",
+            "C1:D11",
+            vec![CTFilterColumn {
+                col_id: 1,
+                filters: Some(CTFilters {
+                    blank: None,
+                    calendar_type: None,
+                    filter: vec![CTFilter {
+                        val: Some(String::from("200")),
+                    }],
+                    date_group_item: Vec::new(),
+                }),
+                ..Default::default()
+            }],
+        )
+        .unwrap();
+        assert_eq!(wb.apply_auto_filter("Sheet2").unwrap(), vec![2, 4]);
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        assert_eq!(get_row(ws, 3).unwrap().hidden, Some(true));
+
+        // the serial number out of the date range doesn't match any month
+        let ws = wb.get_worksheet_mut("Sheet2").unwrap();
+        let row = &mut ws.sheet_data.row.as_mut().unwrap()[1];
+        let cell = row.c.iter_mut().find(|c| c.r == "D2").unwrap();
+        cell.v = Some(String::from("1E+300"));
+        wb.auto_filter(
+            "Sheet2",
+            "C1:D11",
+            vec![CTFilterColumn {
+                col_id: 1,
+                dynamic_filter: Some(CTDynamicFilter {
+                    type_attr: String::from("M1"),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+        )
+        .unwrap();
+        assert!(!wb.get_filtered_rows("Sheet2").unwrap().contains(&2));
+    }
+
+    #[test]
+    fn test_filter_criteria() {
+        assert!(wildcard_match("d*l", "dell"));
+        assert!(wildcard_match("?p", "hp"));
+        assert!(wildcard_match("~*", "*"));
+        assert!(!wildcard_match("~*", "a"));
+        assert!(!wildcard_match("d?l", "dell"));
+
+        // 2024-05-15 12:00:00
+        let serial = 45427.5;
+        assert_eq!(serial_to_datetime(serial), Some([2024, 5, 15, 12, 0, 0]));
+        assert_eq!(month_serial(2024, 5), 45413.0);
+        assert_eq!(month_serial(2024, 13), 45658.0);
+        assert_eq!(
+            serial_to_datetime(2958465.5),
+            Some([9999, 12, 31, 12, 0, 0])
+        );
+        assert_eq!(serial_to_datetime(2958466.0), None);
+        assert_eq!(serial_to_datetime(1E+300), None);
+        assert_eq!(serial_to_datetime(f64::NAN), None);
+        let item = CTDateGroupItem {
+            year: 2024,
+            month: Some(5),
+            day: None,
+            hour: None,
+            minute: None,
+            second: None,
+            date_time_grouping: String::from("month"),
+        };
+        assert!(match_date_group_item(&item, serial));
+        assert!(!match_date_group_item(&item, serial + 31.0));
+
+        let values: Vec<FilterValue> = [45426.0, 45427.2, 45432.0, 45444.0, 45300.0]
+            .iter()
+            .map(|n| FilterValue::Number(*n))
+            .collect();
+        let dynamic = |filter_type: &str| {
+            let filter = CTDynamicFilter {
+                type_attr: String::from(filter_type),
+                ..Default::default()
+            };
+            match_dynamic_filter(&filter, &values, serial).unwrap()
+        };
+        assert_eq!(dynamic("today"), vec![false, true, false, false, false]);
+        assert_eq!(dynamic("thisWeek"), vec![true, true, false, false, false]);
+        assert_eq!(dynamic("thisMonth"), vec![true, true, true, false, false]);
+        assert_eq!(dynamic("nextMonth"), vec![false, false, false, true, false]);
+        assert_eq!(dynamic("M1"), vec![false, false, false, false, true]);
+        assert_eq!(dynamic("Q2"), vec![true, true, true, true, false]);
+        assert_eq!(dynamic("yearToDate"), vec![true, true, false, false, true]);
+        assert_eq!(
+            dynamic("belowAverage"),
+            vec![false, false, false, false, true]
+        );
+    }
+}
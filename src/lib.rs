@@ -31,30 +31,80 @@
 //!     }
 //! }
 //! ```
+pub mod adjust;
+pub mod app;
+pub mod calc;
 pub mod cell;
+pub mod chart;
+pub mod col;
+pub mod comment;
+pub mod conditional_format;
+pub mod crypt;
+pub mod data_validation;
+pub mod defined_name;
 pub mod errors;
-pub mod app;
+pub mod filter;
+pub mod outline;
+pub mod pivot_table;
 pub mod rels;
+pub mod rows;
+pub mod sheetview;
+pub mod sort;
 pub mod sst;
+pub mod styles;
+pub mod table;
+pub mod threaded_comment;
 pub mod utils;
 pub mod workbook;
 pub mod worksheet;
+pub mod xml_chart;
+pub mod xml_comments;
 pub mod xml_content_types;
+pub mod xml_drawing;
+pub mod xml_pivot_cache;
+pub mod xml_pivot_table;
 pub mod xml_rels;
 pub mod xml_sst;
+pub mod xml_styles;
+pub mod xml_table;
+pub mod xml_threaded_comments;
 pub mod xml_workbook;
 pub mod xml_worksheet;
 
+pub use adjust::*;
+pub use app::*;
 pub use cell::*;
+pub use chart::*;
+pub use col::*;
+pub use comment::*;
+pub use conditional_format::*;
+pub use data_validation::*;
+pub use defined_name::*;
 pub use errors::*;
-pub use app::*;
+pub use filter::*;
+pub use outline::*;
+pub use pivot_table::*;
 pub use rels::*;
+pub use rows::*;
+pub use sheetview::*;
+pub use sort::*;
 pub use sst::*;
+pub use styles::*;
+pub use table::*;
+pub use threaded_comment::*;
 pub use utils::*;
 pub use workbook::*;
 pub use worksheet::*;
+pub use xml_chart::*;
+pub use xml_comments::*;
 pub use xml_content_types::*;
+pub use xml_drawing::*;
+pub use xml_pivot_cache::*;
+pub use xml_pivot_table::*;
 pub use xml_rels::*;
 pub use xml_sst::*;
+pub use xml_styles::*;
+pub use xml_table::*;
+pub use xml_threaded_comments::*;
 pub use xml_workbook::*;
 pub use xml_worksheet::*;
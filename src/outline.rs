@@ -0,0 +1,592 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    get_col_props, get_row, parse_col_range, prepare_row, prepare_rows, set_col_props, CTCol,
+    CTOutlinePr, CTSheetFormatPr, ExcelizeError, Spreadsheet, Worksheet, XMLWorksheet,
+    DEFAULT_ROW_HEIGHT, TOTAL_COLUMNS, TOTAL_ROWS,
+};
+use std::ops::RangeInclusive;
+
+pub trait Outline {
+    /// group_rows provides a function to group the rows by given worksheet
+    /// name and rows range, the outline level of each row in the range will
+    /// be increased by one. The outline level of the rows should be less than
+    /// or equal to 7. For example, group the rows from 5 to 20 in Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.group_rows("Sheet1", 5..=20)
+    /// ```
+    fn group_rows(&mut self, sheet: &str, rows: RangeInclusive<u32>) -> Result<(), ExcelizeError>;
+    /// ungroup_rows provides a function to ungroup the rows by given worksheet
+    /// name and rows range, the outline level of each grouped row in the range
+    /// will be decreased by one and the rows will be shown.
+    fn ungroup_rows(&mut self, sheet: &str, rows: RangeInclusive<u32>)
+        -> Result<(), ExcelizeError>;
+    /// collapse_row_group provides a function to collapse the rows group by
+    /// given worksheet name and rows range, the rows in the range will be
+    /// hidden and the summary row will be marked as collapsed.
+    fn collapse_row_group(
+        &mut self,
+        sheet: &str,
+        rows: RangeInclusive<u32>,
+    ) -> Result<(), ExcelizeError>;
+    /// expand_row_group provides a function to expand the rows group by given
+    /// worksheet name and rows range, the nested groups which are collapsed
+    /// will be kept hidden.
+    fn expand_row_group(
+        &mut self,
+        sheet: &str,
+        rows: RangeInclusive<u32>,
+    ) -> Result<(), ExcelizeError>;
+    /// group_cols provides a function to group the columns by given worksheet
+    /// name and columns range, such as "B:D". For example, group the columns
+    /// from B to D in Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.group_cols("Sheet1", "B:D")
+    /// ```
+    fn group_cols(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError>;
+    /// ungroup_cols provides a function to ungroup the columns by given
+    /// worksheet name and columns range.
+    fn ungroup_cols(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError>;
+    /// collapse_col_group provides a function to collapse the columns group by
+    /// given worksheet name and columns range.
+    fn collapse_col_group(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError>;
+    /// expand_col_group provides a function to expand the columns group by
+    /// given worksheet name and columns range.
+    fn expand_col_group(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError>;
+    /// set_outline_summary provides a function to set the position of the
+    /// summary rows and columns of the groups in the worksheet. The summary
+    /// rows are below the detail by default, and the summary columns are to
+    /// the right of the detail by default.
+    fn set_outline_summary(
+        &mut self,
+        sheet: &str,
+        summary_below: bool,
+        summary_right: bool,
+    ) -> Result<(), ExcelizeError>;
+}
+
+impl Outline for Spreadsheet {
+    fn group_rows(&mut self, sheet: &str, rows: RangeInclusive<u32>) -> Result<(), ExcelizeError> {
+        let (start, end) = check_rows_range(&rows)?;
+        group(&mut RowLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn ungroup_rows(
+        &mut self,
+        sheet: &str,
+        rows: RangeInclusive<u32>,
+    ) -> Result<(), ExcelizeError> {
+        let (start, end) = check_rows_range(&rows)?;
+        ungroup(&mut RowLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn collapse_row_group(
+        &mut self,
+        sheet: &str,
+        rows: RangeInclusive<u32>,
+    ) -> Result<(), ExcelizeError> {
+        let (start, end) = check_rows_range(&rows)?;
+        collapse(&mut RowLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn expand_row_group(
+        &mut self,
+        sheet: &str,
+        rows: RangeInclusive<u32>,
+    ) -> Result<(), ExcelizeError> {
+        let (start, end) = check_rows_range(&rows)?;
+        expand(&mut RowLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn group_cols(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError> {
+        let (start, end) = parse_col_range(columns)?;
+        group(&mut ColLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn ungroup_cols(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError> {
+        let (start, end) = parse_col_range(columns)?;
+        ungroup(&mut ColLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn collapse_col_group(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError> {
+        let (start, end) = parse_col_range(columns)?;
+        collapse(&mut ColLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn expand_col_group(&mut self, sheet: &str, columns: &str) -> Result<(), ExcelizeError> {
+        let (start, end) = parse_col_range(columns)?;
+        expand(&mut ColLines(self.get_worksheet_mut(sheet)?), start, end)
+    }
+
+    fn set_outline_summary(
+        &mut self,
+        sheet: &str,
+        summary_below: bool,
+        summary_right: bool,
+    ) -> Result<(), ExcelizeError> {
+        let ws = self.get_worksheet_mut(sheet)?;
+        let sheet_pr = ws.sheet_pr.get_or_insert_with(Default::default);
+        if sheet_pr.outline_pr.is_empty() {
+            sheet_pr.outline_pr.push(CTOutlinePr::default());
+        }
+        sheet_pr.outline_pr[0].summary_below = Some(summary_below);
+        sheet_pr.outline_pr[0].summary_right = Some(summary_right);
+        Ok(())
+    }
+}
+
+// check_rows_range provides a function to check the rows range and returns
+// the first and the last row numbers.
+fn check_rows_range(rows: &RangeInclusive<u32>) -> Result<(u32, u32), ExcelizeError> {
+    let (start, end) = (*rows.start(), *rows.end());
+    if start < 1 || start > end || end > TOTAL_ROWS {
+        return Err(ExcelizeError::CommonError(format!(
+            "invalid rows range {}..={}",
+            start, end
+        )));
+    }
+    Ok((start, end))
+}
+
+// update_outline_level_row provides a function to update the maximum outline
+// level of the rows in the sheet format properties.
+pub(crate) fn update_outline_level_row(ws: &mut XMLWorksheet) {
+    let level = ws.sheet_data.row.as_ref().map_or(0, |rows| {
+        rows.iter()
+            .filter_map(|r| r.outline_level)
+            .max()
+            .unwrap_or(0)
+    });
+    if let Some(format_pr) = sheet_format_pr(ws, level) {
+        format_pr.outline_level_row = (level > 0).then_some(level);
+    }
+}
+
+// update_outline_level_col provides a function to update the maximum outline
+// level of the columns in the sheet format properties.
+pub(crate) fn update_outline_level_col(ws: &mut XMLWorksheet) {
+    let level = ws.cols.as_ref().map_or(0, |cols| {
+        cols.col
+            .iter()
+            .filter_map(|c| c.outline_level)
+            .max()
+            .unwrap_or(0)
+    });
+    if let Some(format_pr) = sheet_format_pr(ws, level) {
+        format_pr.outline_level_col = (level > 0).then_some(level);
+    }
+}
+
+// sheet_format_pr provides a function to get the sheet format properties, it
+// will be created if it doesn't exist and the outline level isn't zero.
+fn sheet_format_pr(ws: &mut XMLWorksheet, level: u8) -> Option<&mut CTSheetFormatPr> {
+    if ws.sheet_format_pr.is_none() && level == 0 {
+        return None;
+    }
+    Some(ws.sheet_format_pr.get_or_insert_with(|| CTSheetFormatPr {
+        default_row_height: DEFAULT_ROW_HEIGHT,
+        ..Default::default()
+    }))
+}
+
+// Lines abstracts the outline properties of the rows or the columns of a
+// worksheet, so that the grouping operations could be shared between them.
+trait Lines {
+    fn max(&self) -> u32;
+    fn summary_after(&self) -> bool;
+    fn level(&self, i: u32) -> u8;
+    fn levels(&self, start: u32, end: u32) -> Vec<u8>;
+    fn shift_level(&mut self, start: u32, end: u32, grouped: bool);
+    fn set_hidden(&mut self, start: u32, end: u32, hidden: bool);
+    fn collapsed(&self, i: u32) -> bool;
+    fn set_collapsed(&mut self, i: u32, collapsed: bool);
+    fn update_outline_level(&mut self);
+}
+
+// shifted_level provides a function to get the outline level increased or
+// decreased by one.
+fn shifted_level(level: u8, grouped: bool) -> u8 {
+    if grouped {
+        level + 1
+    } else {
+        level.saturating_sub(1)
+    }
+}
+
+struct RowLines<'a>(&'a mut XMLWorksheet);
+
+impl Lines for RowLines<'_> {
+    fn max(&self) -> u32 {
+        TOTAL_ROWS
+    }
+
+    fn summary_after(&self) -> bool {
+        match self.0.sheet_pr {
+            Some(ref sheet_pr) => sheet_pr
+                .outline_pr
+                .first()
+                .map_or(true, |pr| pr.summary_below != Some(false)),
+            None => true,
+        }
+    }
+
+    fn level(&self, i: u32) -> u8 {
+        get_row(self.0, i)
+            .and_then(|r| r.outline_level)
+            .unwrap_or(0)
+    }
+
+    fn levels(&self, start: u32, end: u32) -> Vec<u8> {
+        let mut levels = vec![0; (end - start + 1) as usize];
+        for row in self.0.sheet_data.row.iter().flatten() {
+            match row.r {
+                Some(r) if r >= start && r <= end => {
+                    levels[(r - start) as usize] = row.outline_level.unwrap_or(0)
+                }
+                _ => {}
+            }
+        }
+        levels
+    }
+
+    fn shift_level(&mut self, start: u32, end: u32, grouped: bool) {
+        for row in prepare_rows(self.0, start, end, grouped) {
+            let level = shifted_level(row.outline_level.unwrap_or(0), grouped);
+            row.outline_level = (level > 0).then_some(level);
+        }
+    }
+
+    fn set_hidden(&mut self, start: u32, end: u32, hidden: bool) {
+        for row in prepare_rows(self.0, start, end, hidden) {
+            row.hidden = hidden.then_some(true);
+        }
+    }
+
+    fn collapsed(&self, i: u32) -> bool {
+        get_row(self.0, i).and_then(|r| r.collapsed) == Some(true)
+    }
+
+    fn set_collapsed(&mut self, i: u32, collapsed: bool) {
+        if collapsed || get_row(self.0, i).is_some() {
+            prepare_row(self.0, i).collapsed = collapsed.then_some(true);
+        }
+    }
+
+    fn update_outline_level(&mut self) {
+        update_outline_level_row(self.0)
+    }
+}
+
+struct ColLines<'a>(&'a mut XMLWorksheet);
+
+impl ColLines<'_> {
+    // remove_empty_cols provides a function to remove the columns spans
+    // without any properties, which are created for the columns without
+    // properties when clearing the properties of a range.
+    fn remove_empty_cols(&mut self) {
+        if let Some(ref mut cols) = self.0.cols {
+            cols.col.retain(|c| {
+                *c != CTCol {
+                    min: c.min,
+                    max: c.max,
+                    ..Default::default()
+                }
+            });
+            if cols.col.is_empty() {
+                self.0.cols = None;
+            }
+        }
+    }
+}
+
+impl Lines for ColLines<'_> {
+    fn max(&self) -> u32 {
+        TOTAL_COLUMNS
+    }
+
+    fn summary_after(&self) -> bool {
+        match self.0.sheet_pr {
+            Some(ref sheet_pr) => sheet_pr
+                .outline_pr
+                .first()
+                .map_or(true, |pr| pr.summary_right != Some(false)),
+            None => true,
+        }
+    }
+
+    fn level(&self, i: u32) -> u8 {
+        get_col_props(self.0, i)
+            .and_then(|c| c.outline_level)
+            .unwrap_or(0)
+    }
+
+    fn levels(&self, start: u32, end: u32) -> Vec<u8> {
+        let mut levels = vec![0; (end - start + 1) as usize];
+        for col in self.0.cols.iter().flat_map(|cols| cols.col.iter()) {
+            for i in col.min.max(start)..=col.max.min(end) {
+                levels[(i - start) as usize] = col.outline_level.unwrap_or(0);
+            }
+        }
+        levels
+    }
+
+    fn shift_level(&mut self, start: u32, end: u32, grouped: bool) {
+        set_col_props(self.0, start, end, |c| {
+            let level = shifted_level(c.outline_level.unwrap_or(0), grouped);
+            c.outline_level = (level > 0).then_some(level);
+        });
+        self.remove_empty_cols();
+    }
+
+    fn set_hidden(&mut self, start: u32, end: u32, hidden: bool) {
+        set_col_props(self.0, start, end, |c| c.hidden = hidden.then_some(true));
+        self.remove_empty_cols();
+    }
+
+    fn collapsed(&self, i: u32) -> bool {
+        get_col_props(self.0, i).and_then(|c| c.collapsed) == Some(true)
+    }
+
+    fn set_collapsed(&mut self, i: u32, collapsed: bool) {
+        if collapsed || get_col_props(self.0, i).is_some() {
+            set_col_props(self.0, i, i, |c| c.collapsed = collapsed.then_some(true));
+            self.remove_empty_cols();
+        }
+    }
+
+    fn update_outline_level(&mut self) {
+        update_outline_level_col(self.0)
+    }
+}
+
+// summary_line provides a function to get the summary row or column of the
+// group, returns None if the summary line is out of the worksheet.
+fn summary_line<L: Lines>(lines: &L, start: u32, end: u32) -> Option<u32> {
+    match lines.summary_after() {
+        true if end < lines.max() => Some(end + 1),
+        false if start > 1 => Some(start - 1),
+        _ => None,
+    }
+}
+
+// group_level provides a function to get the outline level of the group in
+// the range, which is the minimum outline level of the lines in it.
+fn group_level<L: Lines>(lines: &L, start: u32, end: u32) -> Result<u8, ExcelizeError> {
+    let level = lines.levels(start, end).into_iter().min().unwrap_or(0);
+    if level == 0 {
+        return Err(ExcelizeError::CommonError(String::from(
+            "the range is not grouped",
+        )));
+    }
+    Ok(level)
+}
+
+// collapsed_lines provides a function to get whether each line in the range
+// is inside a collapsed group, which should be kept hidden.
+fn collapsed_lines<L: Lines>(lines: &L, start: u32, end: u32) -> Vec<bool> {
+    let levels = lines.levels(start, end);
+    let mut collapsed = vec![false; levels.len()];
+    for level in 1..=levels.iter().copied().max().unwrap_or(0) {
+        let mut i = start;
+        while i <= end {
+            if levels[(i - start) as usize] < level {
+                i += 1;
+                continue;
+            }
+            let (mut group_start, mut group_end) = (i, i);
+            while group_start > 1 && lines.level(group_start - 1) >= level {
+                group_start -= 1;
+            }
+            while group_end < end && levels[(group_end + 1 - start) as usize] >= level {
+                group_end += 1;
+            }
+            while group_end >= end && group_end < lines.max() && lines.level(group_end + 1) >= level
+            {
+                group_end += 1;
+            }
+            if summary_line(lines, group_start, group_end)
+                .map_or(false, |summary| lines.collapsed(summary))
+            {
+                for j in i..=group_end.min(end) {
+                    collapsed[(j - start) as usize] = true;
+                }
+            }
+            i = group_end + 1;
+        }
+    }
+    collapsed
+}
+
+fn group<L: Lines>(lines: &mut L, start: u32, end: u32) -> Result<(), ExcelizeError> {
+    if lines.levels(start, end).into_iter().any(|level| level >= 7) {
+        return Err(ExcelizeError::CommonError(String::from(
+            "invalid outline level",
+        )));
+    }
+    lines.shift_level(start, end, true);
+    lines.update_outline_level();
+    Ok(())
+}
+
+fn ungroup<L: Lines>(lines: &mut L, start: u32, end: u32) -> Result<(), ExcelizeError> {
+    let grouped: Vec<bool> = lines
+        .levels(start, end)
+        .into_iter()
+        .map(|level| level > 0)
+        .collect();
+    if grouped.iter().all(|grouped| !grouped) {
+        return Err(ExcelizeError::CommonError(String::from(
+            "the range is not grouped",
+        )));
+    }
+    lines.shift_level(start, end, false);
+    if let Some(summary) = summary_line(lines, start, end) {
+        lines.set_collapsed(summary, false);
+    }
+    // show the ungrouped lines unless they are still inside a collapsed group
+    let collapsed = collapsed_lines(lines, start, end);
+    let visible = |i: u32| grouped[(i - start) as usize] && !collapsed[(i - start) as usize];
+    let mut i = start;
+    while i <= end {
+        if !visible(i) {
+            i += 1;
+            continue;
+        }
+        let run_start = i;
+        while i < end && visible(i + 1) {
+            i += 1;
+        }
+        lines.set_hidden(run_start, i, false);
+        i += 1;
+    }
+    lines.update_outline_level();
+    Ok(())
+}
+
+fn collapse<L: Lines>(lines: &mut L, start: u32, end: u32) -> Result<(), ExcelizeError> {
+    group_level(lines, start, end)?;
+    lines.set_hidden(start, end, true);
+    if let Some(summary) = summary_line(lines, start, end) {
+        lines.set_collapsed(summary, true);
+    }
+    Ok(())
+}
+
+fn expand<L: Lines>(lines: &mut L, start: u32, end: u32) -> Result<(), ExcelizeError> {
+    let level = group_level(lines, start, end)?;
+    let levels = lines.levels(start, end);
+    show_lines(lines, &levels, start, start, end, level);
+    if let Some(summary) = summary_line(lines, start, end) {
+        lines.set_collapsed(summary, false);
+    }
+    Ok(())
+}
+
+// show_lines provides a function to show the lines of the given outline level
+// in the range, the nested groups will be shown recursively unless they are
+// collapsed. The levels are the outline levels of the lines from the first.
+fn show_lines<L: Lines>(lines: &mut L, levels: &[u8], first: u32, start: u32, end: u32, level: u8) {
+    let level_of = |i: u32| levels[(i - first) as usize];
+    let mut i = start;
+    while i <= end {
+        if level_of(i) <= level {
+            let run_start = i;
+            while i < end && level_of(i + 1) <= level {
+                i += 1;
+            }
+            lines.set_hidden(run_start, i, false);
+            i += 1;
+            continue;
+        }
+        let nested_start = i;
+        while i < end && level_of(i + 1) > level {
+            i += 1;
+        }
+        let nested_collapsed =
+            summary_line(lines, nested_start, i).map_or(false, |summary| lines.collapsed(summary));
+        if !nested_collapsed {
+            show_lines(lines, levels, first, nested_start, i, level + 1);
+        }
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Col, Rows};
+
+    #[test]
+    fn test_group_rows() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        wb.group_rows("Sheet2", 2..=8).unwrap();
+        wb.group_rows("Sheet2", 3..=4).unwrap();
+        assert_eq!(wb.get_row_outline_level("Sheet2", 3).unwrap(), 2);
+        let format_pr = wb.worksheets["Sheet2"].sheet_format_pr.as_ref().unwrap();
+        assert_eq!(format_pr.outline_level_row, Some(2));
+
+        wb.collapse_row_group("Sheet2", 3..=4).unwrap();
+        wb.collapse_row_group("Sheet2", 2..=8).unwrap();
+        assert!(!wb.get_row_visible("Sheet2", 6).unwrap());
+        wb.expand_row_group("Sheet2", 2..=8).unwrap();
+        assert!(wb.get_row_visible("Sheet2", 2).unwrap());
+        assert!(wb.get_row_visible("Sheet2", 5).unwrap());
+        assert!(!wb.get_row_visible("Sheet2", 3).unwrap());
+
+        wb.ungroup_rows("Sheet2", 3..=4).unwrap();
+        assert!(wb.get_row_visible("Sheet2", 3).unwrap());
+        wb.ungroup_rows("Sheet2", 2..=8).unwrap();
+        let format_pr = wb.worksheets["Sheet2"].sheet_format_pr.as_ref().unwrap();
+        assert_eq!(format_pr.outline_level_row, None);
+        assert!(wb.ungroup_rows("Sheet2", 2..=8).is_err());
+        assert!(wb.collapse_row_group("Sheet2", 2..=8).is_err());
+        assert!(wb.group_rows("Sheet2", 0..=8).is_err());
+
+        // ungrouping a nested group keeps it hidden in the collapsed group
+        wb.group_rows("Sheet2", 2..=8).unwrap();
+        wb.group_rows("Sheet2", 3..=4).unwrap();
+        wb.collapse_row_group("Sheet2", 2..=8).unwrap();
+        wb.ungroup_rows("Sheet2", 3..=4).unwrap();
+        assert!(!wb.get_row_visible("Sheet2", 3).unwrap());
+        wb.expand_row_group("Sheet2", 2..=8).unwrap();
+        assert!(wb.get_row_visible("Sheet2", 3).unwrap());
+    }
+
+    #[test]
+    fn test_group_cols() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        wb.set_outline_summary("Sheet2", true, false).unwrap();
+        wb.group_cols("Sheet2", "B:D").unwrap();
+        assert_eq!(wb.get_col_outline_level("Sheet2", "C").unwrap(), 1);
+        wb.collapse_col_group("Sheet2", "B:D").unwrap();
+        assert!(!wb.get_col_visible("Sheet2", "D").unwrap());
+        assert_eq!(
+            get_col_props(&wb.worksheets["Sheet2"], 1)
+                .unwrap()
+                .collapsed,
+            Some(true)
+        );
+        wb.expand_col_group("Sheet2", "B:D").unwrap();
+        assert!(wb.get_col_visible("Sheet2", "D").unwrap());
+        wb.ungroup_cols("Sheet2", "B:D").unwrap();
+        assert_eq!(wb.get_col_outline_level("Sheet2", "C").unwrap(), 0);
+
+        wb.group_cols("Sheet2", "B:F").unwrap();
+        wb.group_cols("Sheet2", "C:D").unwrap();
+        wb.collapse_col_group("Sheet2", "B:F").unwrap();
+        wb.ungroup_cols("Sheet2", "C:D").unwrap();
+        assert!(!wb.get_col_visible("Sheet2", "D").unwrap());
+        wb.expand_col_group("Sheet2", "B:F").unwrap();
+        wb.ungroup_cols("Sheet2", "B:F").unwrap();
+        assert!(wb.get_col_visible("Sheet2", "D").unwrap());
+        let cols = &wb.worksheets["Sheet2"].cols.as_ref().unwrap().col;
+        assert_eq!(cols.len(), 3);
+        assert!(cols
+            .iter()
+            .all(|c| c.outline_level.is_none() && c.hidden.is_none()));
+    }
+}
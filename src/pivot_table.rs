@@ -0,0 +1,1089 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use quick_xml::de::from_str;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    add_content_type_override,
+    calc::{get_cell_formula_value, parse_reference, FormulaValue},
+    coordinates_to_range_ref, get_relative_target, get_rels_path, is_overlapped, next_part_index,
+    range_ref_to_coordinates, xml_pivot_cache, xml_pivot_table, CTCacheValue, DefinedNames,
+    ExcelizeError, Rels, Spreadsheet, Worksheet, XMLPivotCache, XMLPivotCaches,
+};
+
+pub(crate) static SOURCE_RELATIONSHIP_PIVOT_CACHE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheDefinition";
+pub(crate) static SOURCE_RELATIONSHIP_PIVOT_CACHE_RECORDS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheRecords";
+pub(crate) static SOURCE_RELATIONSHIP_PIVOT_TABLE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotTable";
+static CONTENT_TYPE_PIVOT_CACHE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheDefinition+xml";
+static CONTENT_TYPE_PIVOT_CACHE_RECORDS: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheRecords+xml";
+static CONTENT_TYPE_PIVOT_TABLE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotTable+xml";
+static PIVOT_TABLE_STYLES: [&str; 3] = ["PivotStyleLight", "PivotStyleMedium", "PivotStyleDark"];
+static PIVOT_TABLE_SUBTOTALS: [(&str, &str); 11] = [
+    ("sum", "Sum"),
+    ("count", "Count"),
+    ("average", "Average"),
+    ("max", "Max"),
+    ("min", "Min"),
+    ("product", "Product"),
+    ("countNums", "Count"),
+    ("stdDev", "StdDev"),
+    ("stdDevp", "StdDevp"),
+    ("var", "Var"),
+    ("varp", "Varp"),
+];
+
+/// PivotTableOptions directly maps the settings of the pivot table. The data
+/// range is the source data with the header row, such as Sheet1!A1:E31, or
+/// a defined name of the source data, and the pivot table range is the
+/// location of the pivot table, such as Sheet1!G2:M34. The pivot table will
+/// be named as PivotTableN if the name is empty, the grand totals, the drill
+/// indicators and the headers are shown by default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PivotTableOptions {
+    pub data_range: String,
+    pub pivot_table_range: String,
+    pub name: String,
+    pub rows: Vec<PivotTableField>,
+    pub columns: Vec<PivotTableField>,
+    pub data: Vec<PivotTableField>,
+    pub filter: Vec<PivotTableField>,
+    pub row_grand_totals: Option<bool>,
+    pub col_grand_totals: Option<bool>,
+    pub show_drill: Option<bool>,
+    pub show_row_headers: Option<bool>,
+    pub show_col_headers: Option<bool>,
+    pub show_row_stripes: bool,
+    pub show_col_stripes: bool,
+    pub show_last_column: bool,
+    pub pivot_table_style_name: String,
+}
+
+/// PivotCache directly maps the cached data of the pivot cache. The data
+/// range is the source data of the pivot cache, the fields are the names of
+/// the cache fields, and each record is the typed values in the order of the
+/// fields, the references of the shared items have been resolved to the
+/// values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PivotCache {
+    pub cache_id: u32,
+    pub data_range: String,
+    pub fields: Vec<String>,
+    pub records: Vec<Vec<CTCacheValue>>,
+}
+
+/// PivotTableField directly maps the field of the pivot table. The data is
+/// the header of the column in the data range. For the data fields, the name
+/// is the caption of the field, which defaults to such as "Sum of Sales", and
+/// the subtotal is one of Average, Count, CountNums, Max, Min, Product,
+/// StdDev, StdDevp, Sum, Var and Varp, defaults to Sum. For the row and
+/// column fields, the default subtotal specifies if the subtotal items are
+/// shown.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PivotTableField {
+    pub data: String,
+    pub name: String,
+    pub subtotal: String,
+    pub default_subtotal: bool,
+    pub num_fmt: u32,
+}
+
+pub trait PivotTables {
+    fn pivot_cache_definitions_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    fn pivot_cache_records_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    fn pivot_tables_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    /// add_pivot_table provides a function to add the pivot table by given
+    /// pivot table settings. The pivot cache of the source data will be
+    /// created, and each field can be used only once in the rows, columns
+    /// and filter of the pivot table. For example, create the pivot table on
+    /// Sheet1 which summarizes the sales by the region and the year:
+    ///
+    /// ```ignore
+    /// spreadsheet.add_pivot_table(&PivotTableOptions {
+    ///     data_range: String::from("Sheet1!A1:E31"),
+    ///     pivot_table_range: String::from("Sheet1!G2:M34"),
+    ///     rows: vec![PivotTableField {
+    ///         data: String::from("Region"),
+    ///         ..Default::default()
+    ///     }],
+    ///     columns: vec![PivotTableField {
+    ///         data: String::from("Year"),
+    ///         ..Default::default()
+    ///     }],
+    ///     data: vec![PivotTableField {
+    ///         data: String::from("Sales"),
+    ///         subtotal: String::from("Sum"),
+    ///         ..Default::default()
+    ///     }],
+    ///     pivot_table_style_name: String::from("PivotStyleLight16"),
+    ///     ..Default::default()
+    /// })
+    /// ```
+    fn add_pivot_table(&mut self, opts: &PivotTableOptions) -> Result<(), ExcelizeError>;
+    /// get_pivot_caches provides a function to get the cached data of the
+    /// pivot caches in the workbook, the data can be read even if the source
+    /// data of the pivot cache is not in the workbook. The records will be
+    /// empty if the pivot cache doesn't save the records.
+    fn get_pivot_caches(&self) -> Result<Vec<PivotCache>, ExcelizeError>;
+    /// get_pivot_tables provides a function to get the settings of the pivot
+    /// tables in a worksheet by given worksheet name, the fields of the pivot
+    /// table are named by the names of the cache fields.
+    fn get_pivot_tables(&self, sheet: &str) -> Result<Vec<PivotTableOptions>, ExcelizeError>;
+}
+
+impl PivotTables for Spreadsheet {
+    fn pivot_cache_definitions_reader(&mut self, path: &str) -> Result<(), ExcelizeError> {
+        if !self.pivot_cache_definitions.contains_key(path) {
+            let definition = read_part(self, path)?;
+            self.pivot_cache_definitions
+                .insert(String::from(path), definition);
+        }
+        Ok(())
+    }
+
+    fn pivot_cache_records_reader(&mut self, path: &str) -> Result<(), ExcelizeError> {
+        if !self.pivot_cache_records.contains_key(path) {
+            let records = read_part(self, path)?;
+            self.pivot_cache_records.insert(String::from(path), records);
+        }
+        Ok(())
+    }
+
+    fn pivot_tables_reader(&mut self, path: &str) -> Result<(), ExcelizeError> {
+        if !self.pivot_tables.contains_key(path) {
+            let pivot_table = read_part(self, path)?;
+            self.pivot_tables.insert(String::from(path), pivot_table);
+        }
+        Ok(())
+    }
+
+    fn add_pivot_table(&mut self, opts: &PivotTableOptions) -> Result<(), ExcelizeError> {
+        let (data_sheet, data_coordinates, data_name) = parse_pivot_data_range(self, opts)?;
+        let (sheet, coordinates) = match parse_reference(&opts.pivot_table_range) {
+            Some((Some(sheet), coordinates)) => (sheet, coordinates),
+            _ => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid pivot table range {}",
+                    opts.pivot_table_range
+                )))
+            }
+        };
+        let sheet_path = self.get_sheet_xml_path(&sheet)?;
+        if sheet == data_sheet && is_overlapped(&coordinates, &data_coordinates) {
+            return Err(ExcelizeError::CommonError(format!(
+                "the pivot table range {} overlaps with the data range",
+                opts.pivot_table_range
+            )));
+        }
+        check_pivot_table_style(&opts.pivot_table_style_name)?;
+
+        let ws = self.get_worksheet(&data_sheet)?;
+        let mut headers = Vec::new();
+        for col in data_coordinates[0]..=data_coordinates[2] {
+            let header = get_cell_formula_value(self, ws, col, data_coordinates[1])
+                .to_text()
+                .unwrap_or_default();
+            if header.is_empty() {
+                return Err(ExcelizeError::CommonError(format!(
+                    "the header of the column {} in the data range is empty",
+                    col - data_coordinates[0] + 1
+                )));
+            }
+            headers.push(header);
+        }
+        let columns: Vec<Vec<CTCacheValue>> = (data_coordinates[0]..=data_coordinates[2])
+            .map(|col| {
+                (data_coordinates[1] + 1..=data_coordinates[3])
+                    .map(|row| cache_value(get_cell_formula_value(self, ws, col, row)))
+                    .collect()
+            })
+            .collect();
+        let field_index = |field: &PivotTableField| {
+            headers
+                .iter()
+                .position(|h| *h == field.data)
+                .ok_or_else(|| {
+                    ExcelizeError::CommonError(format!(
+                        "field {} is not exist in the data range",
+                        field.data
+                    ))
+                })
+        };
+        let mut axes: Vec<Option<(&str, &PivotTableField)>> = vec![None; headers.len()];
+        let mut row_fields = Vec::new();
+        let mut col_fields = Vec::new();
+        let mut page_fields = Vec::new();
+        for (axis, fields) in [
+            ("axisRow", &opts.rows),
+            ("axisCol", &opts.columns),
+            ("axisPage", &opts.filter),
+        ] {
+            for field in fields {
+                let i = field_index(field)?;
+                if axes[i].is_some() {
+                    return Err(ExcelizeError::CommonError(format!(
+                        "field {} is used more than once in the pivot table",
+                        field.data
+                    )));
+                }
+                axes[i] = Some((axis, field));
+                match axis {
+                    "axisRow" => row_fields.push(xml_pivot_table::CTField { x: i as i32 }),
+                    "axisCol" => col_fields.push(xml_pivot_table::CTField { x: i as i32 }),
+                    _ => page_fields.push(xml_pivot_table::CTPageField {
+                        fld: i as i32,
+                        hier: Some(-1),
+                        ..Default::default()
+                    }),
+                }
+            }
+        }
+        let mut data_fields = Vec::new();
+        for field in &opts.data {
+            let i = field_index(field)?;
+            let (subtotal, caption) = match field.subtotal.as_str() {
+                "" => PIVOT_TABLE_SUBTOTALS[0],
+                subtotal => *PIVOT_TABLE_SUBTOTALS
+                    .iter()
+                    .find(|(s, _)| s.eq_ignore_ascii_case(subtotal))
+                    .ok_or_else(|| {
+                        ExcelizeError::CommonError(format!(
+                            "invalid subtotal function {}",
+                            subtotal
+                        ))
+                    })?,
+            };
+            data_fields.push(xml_pivot_table::CTDataField {
+                name: Some(match field.name.as_str() {
+                    "" => format!("{} of {}", caption, field.data),
+                    name => String::from(name),
+                }),
+                fld: i as u32,
+                subtotal: Some(String::from(subtotal)),
+                base_field: Some(0),
+                base_item: Some(0),
+                num_fmt_id: (field.num_fmt != 0).then_some(field.num_fmt),
+            });
+        }
+        if data_fields.len() > 1 {
+            col_fields.push(xml_pivot_table::CTField { x: -2 });
+        }
+
+        let mut cache_fields = Vec::new();
+        let mut pivot_fields = Vec::new();
+        let mut records = vec![Vec::new(); columns.first().map_or(0, |c| c.len())];
+        for (i, (header, values)) in headers.iter().zip(&columns).enumerate() {
+            let mut shared_items = shared_items_attributes(values);
+            let mut pivot_field = xml_pivot_table::CTPivotField {
+                data_field: data_fields
+                    .iter()
+                    .any(|d| d.fld == i as u32)
+                    .then_some(true),
+                show_all: Some(false),
+                ..Default::default()
+            };
+            match axes[i] {
+                Some((axis, field)) => {
+                    for (record, value) in records.iter_mut().zip(values) {
+                        let x = match shared_items.items.iter().position(|v| v == value) {
+                            Some(x) => x,
+                            None => {
+                                shared_items.items.push(value.clone());
+                                shared_items.items.len() - 1
+                            }
+                        };
+                        record.push(CTCacheValue::X { v: x as u32 });
+                    }
+                    shared_items.count = Some(shared_items.items.len() as u32);
+                    let mut items: Vec<xml_pivot_table::CTItem> = (0..shared_items.items.len())
+                        .map(|x| xml_pivot_table::CTItem {
+                            x: Some(x as u32),
+                            ..Default::default()
+                        })
+                        .collect();
+                    if field.default_subtotal {
+                        items.push(xml_pivot_table::CTItem {
+                            t: Some(String::from("default")),
+                            ..Default::default()
+                        });
+                    }
+                    pivot_field.axis = Some(String::from(axis));
+                    pivot_field.default_subtotal = (!field.default_subtotal).then_some(false);
+                    pivot_field.items = Some(xml_pivot_table::CTItems {
+                        count: Some(items.len() as u32),
+                        item: items,
+                    });
+                }
+                None => {
+                    for (record, value) in records.iter_mut().zip(values) {
+                        record.push(value.clone());
+                    }
+                }
+            }
+            cache_fields.push(xml_pivot_cache::CTCacheField {
+                name: header.clone(),
+                num_fmt_id: Some(0),
+                shared_items: Some(shared_items),
+            });
+            pivot_fields.push(pivot_field);
+        }
+
+        let pivot_table_paths = get_sheet_pivot_table_paths(self, &sheet_path);
+        let name = match opts.name.as_str() {
+            "" => format!("PivotTable{}", pivot_table_paths.len() + 1),
+            name => String::from(name),
+        };
+        if pivot_table_paths
+            .iter()
+            .filter_map(|path| self.pivot_tables.get(path))
+            .any(|p| p.name.eq_ignore_ascii_case(&name))
+        {
+            return Err(ExcelizeError::CommonError(format!(
+                "the pivot table {} already exists on the worksheet",
+                name
+            )));
+        }
+        let cache_id = self
+            .workbook
+            .iter()
+            .flat_map(|wb| wb.pivot_caches.iter())
+            .flat_map(|p| p.pivot_cache.iter())
+            .map(|p| p.cache_id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let show_page = !page_fields.is_empty();
+        let fields = |fields: Vec<xml_pivot_table::CTField>| {
+            (!fields.is_empty()).then_some(xml_pivot_table::CTFields {
+                count: Some(fields.len() as u32),
+                field: fields,
+            })
+        };
+        let pivot_table = xml_pivot_table::XMLPivotTableDefinition {
+            name,
+            cache_id,
+            data_caption: String::from("Values"),
+            apply_number_formats: Some(false),
+            apply_border_formats: Some(false),
+            apply_font_formats: Some(false),
+            apply_pattern_formats: Some(false),
+            apply_alignment_formats: Some(false),
+            apply_width_height_formats: Some(true),
+            show_drill: Some(opts.show_drill.unwrap_or(true)),
+            use_auto_formatting: Some(true),
+            item_print_titles: Some(true),
+            created_version: Some(3),
+            indent: Some(0),
+            outline: Some(true),
+            outline_data: Some(true),
+            multiple_field_filters: Some(false),
+            row_grand_totals: Some(opts.row_grand_totals.unwrap_or(true)),
+            col_grand_totals: Some(opts.col_grand_totals.unwrap_or(true)),
+            location: xml_pivot_table::CTLocation {
+                ref_attr: coordinates_to_range_ref(&coordinates)?,
+                first_header_row: 1,
+                first_data_row: 1,
+                first_data_col: 1,
+                row_page_count: show_page.then_some(1),
+                col_page_count: show_page.then_some(1),
+            },
+            pivot_fields: Some(xml_pivot_table::CTPivotFields {
+                count: Some(pivot_fields.len() as u32),
+                pivot_field: pivot_fields,
+            }),
+            row_fields: fields(row_fields),
+            col_fields: fields(col_fields),
+            page_fields: show_page.then_some(xml_pivot_table::CTPageFields {
+                count: Some(page_fields.len() as u32),
+                page_field: page_fields,
+            }),
+            data_fields: (!data_fields.is_empty()).then_some(xml_pivot_table::CTDataFields {
+                count: Some(data_fields.len() as u32),
+                data_field: data_fields,
+            }),
+            pivot_table_style_info: Some(xml_pivot_table::CTPivotTableStyleInfo {
+                name: (!opts.pivot_table_style_name.is_empty())
+                    .then(|| opts.pivot_table_style_name.clone()),
+                show_row_headers: Some(opts.show_row_headers.unwrap_or(true)),
+                show_col_headers: Some(opts.show_col_headers.unwrap_or(true)),
+                show_row_stripes: Some(opts.show_row_stripes),
+                show_col_stripes: Some(opts.show_col_stripes),
+                show_last_column: Some(opts.show_last_column),
+            }),
+            ..Default::default()
+        };
+        let mut definition = xml_pivot_cache::XMLPivotCacheDefinition {
+            rid: None,
+            refresh_on_load: Some(true),
+            created_version: Some(3),
+            refreshed_version: Some(3),
+            min_refreshable_version: Some(3),
+            record_count: Some(records.len() as u32),
+            cache_source: xml_pivot_cache::CTCacheSource {
+                type_attr: String::from("worksheet"),
+                worksheet_source: Some(match data_name {
+                    Some(name) => xml_pivot_cache::CTWorksheetSource {
+                        name: Some(name),
+                        ..Default::default()
+                    },
+                    None => xml_pivot_cache::CTWorksheetSource {
+                        ref_attr: Some(coordinates_to_range_ref(&data_coordinates)?),
+                        name: None,
+                        sheet: Some(data_sheet),
+                    },
+                }),
+            },
+            cache_fields: xml_pivot_cache::CTCacheFields {
+                count: Some(cache_fields.len() as u32),
+                cache_field: cache_fields,
+            },
+        };
+        let cache_records = xml_pivot_cache::XMLPivotCacheRecords {
+            count: Some(records.len() as u32),
+            r: records
+                .into_iter()
+                .map(|values| xml_pivot_cache::CTRecord { values })
+                .collect(),
+        };
+
+        let definition_path = format!(
+            "xl/pivotCache/pivotCacheDefinition{}.xml",
+            next_part_index(self, "xl/pivotCache/pivotCacheDefinition", ".xml")
+        );
+        let records_path = format!(
+            "xl/pivotCache/pivotCacheRecords{}.xml",
+            next_part_index(self, "xl/pivotCache/pivotCacheRecords", ".xml")
+        );
+        let pivot_table_path = format!(
+            "xl/pivotTables/pivotTable{}.xml",
+            next_part_index(self, "xl/pivotTables/pivotTable", ".xml")
+        );
+        definition.rid = Some(self.add_rels(
+            &get_rels_path(&definition_path),
+            SOURCE_RELATIONSHIP_PIVOT_CACHE_RECORDS,
+            &get_relative_target(&definition_path, &records_path),
+            None,
+        ));
+        let rid = self.add_rels(
+            &get_rels_path("xl/workbook.xml"),
+            SOURCE_RELATIONSHIP_PIVOT_CACHE,
+            &get_relative_target("xl/workbook.xml", &definition_path),
+            None,
+        );
+        self.add_rels(
+            &get_rels_path(&sheet_path),
+            SOURCE_RELATIONSHIP_PIVOT_TABLE,
+            &get_relative_target(&sheet_path, &pivot_table_path),
+            None,
+        );
+        self.add_rels(
+            &get_rels_path(&pivot_table_path),
+            SOURCE_RELATIONSHIP_PIVOT_CACHE,
+            &get_relative_target(&pivot_table_path, &definition_path),
+            None,
+        );
+        if let Some(ref mut types) = self.content_type {
+            add_content_type_override(types, &definition_path, CONTENT_TYPE_PIVOT_CACHE);
+            add_content_type_override(types, &records_path, CONTENT_TYPE_PIVOT_CACHE_RECORDS);
+            add_content_type_override(types, &pivot_table_path, CONTENT_TYPE_PIVOT_TABLE);
+        }
+        if let Some(ref mut wb) = self.workbook {
+            wb.pivot_caches
+                .get_or_insert_with(|| XMLPivotCaches {
+                    pivot_cache: Vec::new(),
+                })
+                .pivot_cache
+                .push(XMLPivotCache { cache_id, rid });
+        }
+        self.pivot_cache_definitions
+            .insert(definition_path, definition);
+        self.pivot_cache_records.insert(records_path, cache_records);
+        self.pivot_tables.insert(pivot_table_path, pivot_table);
+        Ok(())
+    }
+
+    fn get_pivot_caches(&self) -> Result<Vec<PivotCache>, ExcelizeError> {
+        let mut pivot_caches = Vec::new();
+        for pivot_cache in self
+            .workbook
+            .iter()
+            .flat_map(|wb| wb.pivot_caches.iter())
+            .flat_map(|p| p.pivot_cache.iter())
+        {
+            let path = self
+                .get_rels_target_path("xl/workbook.xml", &pivot_cache.rid)
+                .ok_or_else(|| {
+                    ExcelizeError::CommonError(format!(
+                        "pivot cache {} is not exist",
+                        pivot_cache.cache_id
+                    ))
+                })?;
+            let definition = self.pivot_cache_definitions.get(&path).ok_or_else(|| {
+                ExcelizeError::CommonError(format!("pivot cache {} is not exist", path))
+            })?;
+            let fields = &definition.cache_fields.cache_field;
+            let mut records = Vec::new();
+            if let Some(cache_records) = get_pivot_cache_records_path(self, &path)
+                .and_then(|path| self.pivot_cache_records.get(&path))
+            {
+                for record in &cache_records.r {
+                    let mut values = Vec::new();
+                    for (field, value) in fields.iter().zip(&record.values) {
+                        values.push(match value {
+                            CTCacheValue::X { v } => field
+                                .shared_items
+                                .as_ref()
+                                .and_then(|s| s.items.get(*v as usize))
+                                .cloned()
+                                .ok_or_else(|| {
+                                    ExcelizeError::CommonError(format!(
+                                        "invalid shared item index {} of the cache field {}",
+                                        v, field.name
+                                    ))
+                                })?,
+                            value => value.clone(),
+                        });
+                    }
+                    records.push(values);
+                }
+            }
+            pivot_caches.push(PivotCache {
+                cache_id: pivot_cache.cache_id,
+                data_range: pivot_cache_data_range(definition),
+                fields: fields.iter().map(|f| f.name.clone()).collect(),
+                records,
+            });
+        }
+        Ok(pivot_caches)
+    }
+
+    fn get_pivot_tables(&self, sheet: &str) -> Result<Vec<PivotTableOptions>, ExcelizeError> {
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        let mut pivot_tables = Vec::new();
+        for path in get_sheet_pivot_table_paths(self, &sheet_path) {
+            let pivot_table = match self.pivot_tables.get(&path) {
+                Some(pivot_table) => pivot_table,
+                None => continue,
+            };
+            let definition = self
+                .get_rels_by_type(&path, SOURCE_RELATIONSHIP_PIVOT_CACHE)
+                .into_iter()
+                .find_map(|(_, path)| self.pivot_cache_definitions.get(&path));
+            let cache_fields = definition
+                .map(|d| d.cache_fields.cache_field.as_slice())
+                .unwrap_or_default();
+            let pivot_fields = pivot_table
+                .pivot_fields
+                .as_ref()
+                .map(|p| p.pivot_field.as_slice())
+                .unwrap_or_default();
+            let field = |x: usize| {
+                let pivot_field = pivot_fields.get(x);
+                PivotTableField {
+                    data: cache_fields
+                        .get(x)
+                        .map(|f| f.name.clone())
+                        .unwrap_or_default(),
+                    name: pivot_field.and_then(|p| p.name.clone()).unwrap_or_default(),
+                    default_subtotal: pivot_field
+                        .map_or(true, |p| p.default_subtotal != Some(false)),
+                    ..Default::default()
+                }
+            };
+            let fields = |fields: &Option<xml_pivot_table::CTFields>| {
+                fields
+                    .iter()
+                    .flat_map(|f| f.field.iter())
+                    .filter(|f| f.x >= 0)
+                    .map(|f| field(f.x as usize))
+                    .collect()
+            };
+            let style = pivot_table.pivot_table_style_info.as_ref();
+            let flag = |f: fn(&xml_pivot_table::CTPivotTableStyleInfo) -> Option<bool>| {
+                style.and_then(f).unwrap_or(false)
+            };
+            pivot_tables.push(PivotTableOptions {
+                data_range: definition.map(pivot_cache_data_range).unwrap_or_default(),
+                pivot_table_range: format!(
+                    "{}!{}",
+                    quote_sheet_name(sheet),
+                    pivot_table.location.ref_attr
+                ),
+                name: pivot_table.name.clone(),
+                rows: fields(&pivot_table.row_fields),
+                columns: fields(&pivot_table.col_fields),
+                data: pivot_table
+                    .data_fields
+                    .iter()
+                    .flat_map(|d| d.data_field.iter())
+                    .map(|d| {
+                        let subtotal = d.subtotal.as_deref().unwrap_or("sum");
+                        PivotTableField {
+                            name: d.name.clone().unwrap_or_default(),
+                            subtotal: capitalize(subtotal),
+                            num_fmt: d.num_fmt_id.unwrap_or(0),
+                            ..field(d.fld as usize)
+                        }
+                    })
+                    .collect(),
+                filter: pivot_table
+                    .page_fields
+                    .iter()
+                    .flat_map(|p| p.page_field.iter())
+                    .filter(|p| p.fld >= 0)
+                    .map(|p| field(p.fld as usize))
+                    .collect(),
+                row_grand_totals: Some(pivot_table.row_grand_totals.unwrap_or(true)),
+                col_grand_totals: Some(pivot_table.col_grand_totals.unwrap_or(true)),
+                show_drill: Some(pivot_table.show_drill.unwrap_or(true)),
+                show_row_headers: Some(flag(|s| s.show_row_headers)),
+                show_col_headers: Some(flag(|s| s.show_col_headers)),
+                show_row_stripes: flag(|s| s.show_row_stripes),
+                show_col_stripes: flag(|s| s.show_col_stripes),
+                show_last_column: flag(|s| s.show_last_column),
+                pivot_table_style_name: style.and_then(|s| s.name.clone()).unwrap_or_default(),
+            });
+        }
+        Ok(pivot_tables)
+    }
+}
+
+// read_part provides a function to deserialize the part in the package by
+// given part path.
+fn read_part<T: DeserializeOwned>(
+    spreadsheet: &Spreadsheet,
+    path: &str,
+) -> Result<T, ExcelizeError> {
+    let buf = spreadsheet
+        .file
+        .get(path)
+        .ok_or_else(|| ExcelizeError::CommonError(format!("part {} is none", path)))?;
+    let s = std::str::from_utf8(buf).map_err(|e| ExcelizeError::CommonError(e.to_string()))?;
+    from_str(s).map_err(|e| ExcelizeError::CommonError(e.to_string()))
+}
+
+// get_pivot_cache_paths provides a function to get the paths of the pivot
+// cache definition parts in the workbook.
+pub(crate) fn get_pivot_cache_paths(spreadsheet: &Spreadsheet) -> Vec<String> {
+    spreadsheet
+        .get_rels_by_type("xl/workbook.xml", SOURCE_RELATIONSHIP_PIVOT_CACHE)
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect()
+}
+
+// get_pivot_cache_records_path provides a function to get the path of the
+// pivot cache records part by given pivot cache definition part path.
+pub(crate) fn get_pivot_cache_records_path(
+    spreadsheet: &Spreadsheet,
+    definition_path: &str,
+) -> Option<String> {
+    let rid = spreadsheet
+        .pivot_cache_definitions
+        .get(definition_path)?
+        .rid
+        .as_ref()?;
+    spreadsheet.get_rels_target_path(definition_path, rid)
+}
+
+// get_sheet_pivot_table_paths provides a function to get the paths of the
+// pivot table parts of the worksheet by given worksheet part path.
+pub(crate) fn get_sheet_pivot_table_paths(
+    spreadsheet: &Spreadsheet,
+    sheet_path: &str,
+) -> Vec<String> {
+    spreadsheet
+        .get_rels_by_type(sheet_path, SOURCE_RELATIONSHIP_PIVOT_TABLE)
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect()
+}
+
+// pivot_cache_data_range provides a function to get the data range of the
+// pivot cache, which is the defined name or the range reference with the
+// worksheet name.
+fn pivot_cache_data_range(definition: &xml_pivot_cache::XMLPivotCacheDefinition) -> String {
+    match &definition.cache_source.worksheet_source {
+        Some(xml_pivot_cache::CTWorksheetSource {
+            name: Some(name), ..
+        }) => name.clone(),
+        Some(xml_pivot_cache::CTWorksheetSource {
+            ref_attr: Some(ref_attr),
+            sheet: Some(sheet),
+            ..
+        }) => format!("{}!{}", quote_sheet_name(sheet), ref_attr),
+        _ => String::new(),
+    }
+}
+
+// quote_sheet_name provides a function to quote the worksheet name in the
+// reference if the name contains the characters other than the letters, the
+// digits and the underscore.
+fn quote_sheet_name(sheet: &str) -> String {
+    match sheet.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        true => String::from(sheet),
+        false => format!("'{}'", sheet.replace('\'', "''")),
+    }
+}
+
+// parse_pivot_data_range provides a function to get the worksheet name, the
+// coordinates and the defined name of the source data by given pivot table
+// settings, the data range should contain the header row and at least one
+// data row.
+fn parse_pivot_data_range(
+    spreadsheet: &Spreadsheet,
+    opts: &PivotTableOptions,
+) -> Result<(String, [u32; 4], Option<String>), ExcelizeError> {
+    let (sheet, coordinates, name) = match parse_reference(&opts.data_range) {
+        Some((Some(sheet), coordinates)) => (sheet, coordinates, None),
+        _ => match spreadsheet
+            .resolve_defined_name(&opts.data_range)
+            .ok()
+            .and_then(|ranges| ranges.into_iter().next())
+        {
+            Some((sheet, range)) => (
+                sheet,
+                range_ref_to_coordinates(&range)?,
+                Some(opts.data_range.clone()),
+            ),
+            None => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid pivot table data range {}",
+                    opts.data_range
+                )))
+            }
+        },
+    };
+    spreadsheet.get_sheet_index(&sheet)?;
+    if coordinates[1] == coordinates[3] {
+        return Err(ExcelizeError::CommonError(format!(
+            "the pivot table data range {} should contain at least one data row",
+            opts.data_range
+        )));
+    }
+    Ok((sheet, coordinates, name))
+}
+
+// cache_value provides a function to convert the cell value to the value of
+// the pivot cache.
+fn cache_value(value: FormulaValue) -> CTCacheValue {
+    match value {
+        FormulaValue::Number(v) => CTCacheValue::N { v },
+        FormulaValue::Text(v) => CTCacheValue::S { v },
+        FormulaValue::Bool(v) => CTCacheValue::B { v },
+        FormulaValue::Error(v) => CTCacheValue::E { v },
+        _ => CTCacheValue::M {},
+    }
+}
+
+// shared_items_attributes provides a function to get the shared items of the
+// cache field with the attributes which describe the data types of the
+// values, the items are not included.
+fn shared_items_attributes(values: &[CTCacheValue]) -> xml_pivot_cache::CTSharedItems {
+    let numbers: Vec<f64> = values
+        .iter()
+        .filter_map(|v| match v {
+            CTCacheValue::N { v } => Some(*v),
+            _ => None,
+        })
+        .collect();
+    let has_blank = values.iter().any(|v| matches!(v, CTCacheValue::M {}));
+    let has_string = values
+        .iter()
+        .any(|v| !matches!(v, CTCacheValue::N { .. } | CTCacheValue::M {}));
+    let has_number = !numbers.is_empty();
+    xml_pivot_cache::CTSharedItems {
+        contains_semi_mixed_types: (!has_string && !has_blank).then_some(false),
+        contains_string: (!has_string).then_some(false),
+        contains_number: has_number.then_some(true),
+        contains_integer: (has_number && numbers.iter().all(|n| n.fract() == 0.0)).then_some(true),
+        contains_blank: has_blank.then_some(true),
+        min_value: numbers.iter().copied().reduce(f64::min),
+        max_value: numbers.iter().copied().reduce(f64::max),
+        ..Default::default()
+    }
+}
+
+// check_pivot_table_style provides a function to check if the style name is
+// one of the built-in pivot table styles, the empty style name means no
+// style.
+fn check_pivot_table_style(style_name: &str) -> Result<(), ExcelizeError> {
+    if style_name.is_empty()
+        || PIVOT_TABLE_STYLES.iter().any(|prefix| {
+            style_name
+                .strip_prefix(prefix)
+                .and_then(|n| n.parse::<u32>().ok())
+                .map_or(false, |n| (1..=28).contains(&n))
+        })
+    {
+        return Ok(());
+    }
+    Err(ExcelizeError::CommonError(format!(
+        "invalid pivot table style name {}",
+        style_name
+    )))
+}
+
+// capitalize provides a function to convert the first character of the text
+// to uppercase, such as the subtotal function name "sum" to "Sum".
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_pivot_table() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let ws = wb.get_worksheet_mut("Sheet2").unwrap();
+        let row = &mut ws.sheet_data.row.as_mut().unwrap()[0];
+        let cell = row.c.iter_mut().find(|c| c.r == "D1").unwrap();
+        cell.t = Some(String::from("str"));
+        cell.v = Some(String::from("Sales"));
+        let field = |data: &str| PivotTableField {
+            data: String::from(data),
+            ..Default::default()
+        };
+        let opts = PivotTableOptions {
+            data_range: String::from("Sheet2!C1:D11"),
+            pivot_table_range: String::from("Sheet2!F1:G12"),
+            rows: vec![field("Brand")],
+            data: vec![PivotTableField {
+                name: String::from("Total"),
+                ..field("Sales")
+            }],
+            pivot_table_style_name: String::from("PivotStyleLight16"),
+            ..Default::default()
+        };
+        assert!(wb
+            .add_pivot_table(&PivotTableOptions {
+                data_range: String::from("Sheet2!B1:D11"),
+                ..opts.clone()
+            })
+            .is_err());
+        assert!(wb
+            .add_pivot_table(&PivotTableOptions {
+                pivot_table_range: String::from("Sheet2!D5:E6"),
+                ..opts.clone()
+            })
+            .is_err());
+        assert!(wb
+            .add_pivot_table(&PivotTableOptions {
+                columns: vec![field("Brand")],
+                ..opts.clone()
+            })
+            .is_err());
+        assert!(wb
+            .add_pivot_table(&PivotTableOptions {
+                pivot_table_style_name: String::from("PivotStyleLight29"),
+                ..opts.clone()
+            })
+            .is_err());
+        assert!(wb
+            .add_pivot_table(&PivotTableOptions {
+                data: vec![PivotTableField {
+                    subtotal: String::from("Median"),
+                    ..field("Brand")
+                }],
+                ..opts.clone()
+            })
+            .is_err());
+        wb.add_pivot_table(&opts).unwrap();
+        assert!(wb
+            .add_pivot_table(&PivotTableOptions {
+                name: String::from("pivotTable1"),
+                ..opts.clone()
+            })
+            .is_err());
+
+        let definition = &wb.pivot_cache_definitions["xl/pivotCache/pivotCacheDefinition1.xml"];
+        assert_eq!(definition.record_count, Some(10));
+        let source = definition.cache_source.worksheet_source.as_ref().unwrap();
+        assert_eq!(source.ref_attr.as_deref(), Some("C1:D11"));
+        assert_eq!(source.sheet.as_deref(), Some("Sheet2"));
+        let fields = &definition.cache_fields.cache_field;
+        assert_eq!(fields[0].name, "Brand");
+        assert_eq!(fields[0].shared_items.as_ref().unwrap().count, Some(10));
+        let shared_items = fields[1].shared_items.as_ref().unwrap();
+        assert_eq!(shared_items.contains_integer, Some(true));
+        assert_eq!(shared_items.min_value, Some(37.0));
+        assert_eq!(shared_items.max_value, Some(510.0));
+        assert!(shared_items.items.is_empty());
+        let records = &wb.pivot_cache_records["xl/pivotCache/pivotCacheRecords1.xml"];
+        assert_eq!(
+            records.r[1].values,
+            vec![CTCacheValue::X { v: 1 }, CTCacheValue::N { v: 450.0 }]
+        );
+
+        let pivot_table = &wb.pivot_tables["xl/pivotTables/pivotTable1.xml"];
+        assert_eq!(pivot_table.name, "PivotTable1");
+        assert_eq!(pivot_table.cache_id, 1);
+        assert_eq!(pivot_table.location.ref_attr, "F1:G12");
+        let pivot_fields = &pivot_table.pivot_fields.as_ref().unwrap().pivot_field;
+        assert_eq!(pivot_fields[0].axis.as_deref(), Some("axisRow"));
+        assert_eq!(pivot_fields[1].data_field, Some(true));
+        assert_eq!(pivot_fields[0].items.as_ref().unwrap().count, Some(10));
+        let data_fields = &pivot_table.data_fields.as_ref().unwrap().data_field;
+        assert_eq!(data_fields[0].name.as_deref(), Some("Total"));
+        assert_eq!(data_fields[0].subtotal.as_deref(), Some("sum"));
+        assert!(pivot_table.col_fields.is_none());
+        let pivot_caches = wb.workbook.as_ref().unwrap().pivot_caches.as_ref();
+        assert_eq!(pivot_caches.unwrap().pivot_cache[0].cache_id, 1);
+
+        wb.add_pivot_table(&PivotTableOptions {
+            pivot_table_range: String::from("Sheet1!A1:C12"),
+            name: String::new(),
+            rows: Vec::new(),
+            columns: vec![field("Brand")],
+            data: vec![
+                PivotTableField {
+                    subtotal: String::from("average"),
+                    ..field("Sales")
+                },
+                PivotTableField {
+                    subtotal: String::from("countNums"),
+                    ..field("Sales")
+                },
+            ],
+            ..opts
+        })
+        .unwrap();
+        let pivot_table = &wb.pivot_tables["xl/pivotTables/pivotTable2.xml"];
+        assert_eq!(pivot_table.cache_id, 2);
+        let col_fields = &pivot_table.col_fields.as_ref().unwrap().field;
+        assert_eq!(col_fields[1].x, -2);
+        let data_fields = &pivot_table.data_fields.as_ref().unwrap().data_field;
+        assert_eq!(data_fields[0].name.as_deref(), Some("Average of Sales"));
+        assert_eq!(data_fields[1].name.as_deref(), Some("Count of Sales"));
+        assert_eq!(data_fields[1].subtotal.as_deref(), Some("countNums"));
+    }
+
+    #[test]
+    fn test_get_pivot_tables() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert!(wb.get_pivot_caches().unwrap().is_empty());
+        wb.file.insert(
+            String::from("xl/pivotCache/pivotCacheDefinition9.xml"),
+            Vec::from(
+                r#"<pivotCacheDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="rId1" recordCount="2"><cacheSource type="worksheet"><worksheetSource name="Source"/></cacheSource><cacheFields count="2"><cacheField name="Region" numFmtId="0"><sharedItems count="2"><s v="East"/><s v="West"/></sharedItems></cacheField><cacheField name="Sales" numFmtId="0"><sharedItems containsSemiMixedTypes="0" containsString="0" containsNumber="1" minValue="1.5" maxValue="3"/></cacheField></cacheFields></pivotCacheDefinition>"#,
+            ),
+        );
+        wb.file.insert(
+            String::from("xl/pivotCache/pivotCacheRecords9.xml"),
+            Vec::from(
+                r#"<pivotCacheRecords xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="2"><r><x v="1"/><n v="1.5"/></r><r><x v="0"/><m/></r></pivotCacheRecords>"#,
+            ),
+        );
+        wb.pivot_cache_definitions_reader("xl/pivotCache/pivotCacheDefinition9.xml")
+            .unwrap();
+        wb.pivot_cache_records_reader("xl/pivotCache/pivotCacheRecords9.xml")
+            .unwrap();
+        assert!(wb
+            .pivot_tables_reader("xl/pivotTables/pivotTable9.xml")
+            .is_err());
+        let definition = &wb.pivot_cache_definitions["xl/pivotCache/pivotCacheDefinition9.xml"];
+        assert_eq!(pivot_cache_data_range(definition), "Source");
+        let shared_items = definition.cache_fields.cache_field[0].shared_items.as_ref();
+        assert_eq!(
+            shared_items.unwrap().items[1],
+            CTCacheValue::S {
+                v: String::from("West")
+            }
+        );
+        let records = &wb.pivot_cache_records["xl/pivotCache/pivotCacheRecords9.xml"];
+        assert_eq!(records.r[1].values[1], CTCacheValue::M {});
+
+        let ws = wb.get_worksheet_mut("Sheet2").unwrap();
+        let row = &mut ws.sheet_data.row.as_mut().unwrap()[0];
+        let cell = row.c.iter_mut().find(|c| c.r == "D1").unwrap();
+        cell.t = Some(String::from("str"));
+        cell.v = Some(String::from("Sales"));
+        let opts = PivotTableOptions {
+            data_range: String::from("Sheet2!C1:D11"),
+            pivot_table_range: String::from("Sheet2!F1:G12"),
+            filter: vec![PivotTableField {
+                data: String::from("Brand"),
+                ..Default::default()
+            }],
+            data: vec![PivotTableField {
+                data: String::from("Sales"),
+                subtotal: String::from("max"),
+                ..Default::default()
+            }],
+            show_row_headers: Some(false),
+            ..Default::default()
+        };
+        wb.add_pivot_table(&opts).unwrap();
+        let pivot_caches = wb.get_pivot_caches().unwrap();
+        assert_eq!(pivot_caches.len(), 1);
+        assert_eq!(pivot_caches[0].data_range, "Sheet2!C1:D11");
+        assert_eq!(pivot_caches[0].fields, vec!["Brand", "Sales"]);
+        assert_eq!(pivot_caches[0].records.len(), 10);
+        assert_eq!(
+            pivot_caches[0].records[3],
+            vec![
+                CTCacheValue::S {
+                    v: String::from("SONY")
+                },
+                CTCacheValue::N { v: 510.0 }
+            ]
+        );
+
+        let pivot_tables = wb.get_pivot_tables("Sheet2").unwrap();
+        assert_eq!(pivot_tables.len(), 1);
+        assert_eq!(
+            pivot_tables[0],
+            PivotTableOptions {
+                name: String::from("PivotTable1"),
+                data: vec![PivotTableField {
+                    name: String::from("Max of Sales"),
+                    subtotal: String::from("Max"),
+                    default_subtotal: true,
+                    ..opts.data[0].clone()
+                }],
+                row_grand_totals: Some(true),
+                col_grand_totals: Some(true),
+                show_drill: Some(true),
+                show_col_headers: Some(true),
+                ..opts.clone()
+            }
+        );
+        assert!(wb.get_pivot_tables("Sheet1").unwrap().is_empty());
+        assert_eq!(quote_sheet_name("Sales 2024"), "'Sales 2024'");
+
+        // the subtotal read from the part may be empty or not ASCII
+        let pivot_table = wb.pivot_tables.values_mut().next().unwrap();
+        let data_fields = pivot_table.data_fields.as_mut().unwrap();
+        data_fields.data_field[0].subtotal = Some(String::new());
+        assert_eq!(
+            wb.get_pivot_tables("Sheet2").unwrap()[0].data[0].subtotal,
+            ""
+        );
+        let pivot_table = wb.pivot_tables.values_mut().next().unwrap();
+        let data_fields = pivot_table.data_fields.as_mut().unwrap();
+        data_fields.data_field[0].subtotal = Some(String::from("ésum"));
+        assert_eq!(
+            wb.get_pivot_tables("Sheet2").unwrap()[0].data[0].subtotal,
+            "Ésum"
+        );
+    }
+}
@@ -6,10 +6,40 @@ use quick_xml::de::from_str;
 
 use crate::{xml_rels, ExcelizeError, Spreadsheet};
 
+pub(crate) static SOURCE_RELATIONSHIP_COMMENTS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments";
+pub(crate) static SOURCE_RELATIONSHIP_DRAWING: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing";
+pub(crate) static SOURCE_RELATIONSHIP_DRAWING_VML: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/vmlDrawing";
+pub(crate) static SOURCE_RELATIONSHIP_HYPERLINK: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink";
+
 pub trait Rels {
     fn rels_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
     where
         Self: std::marker::Sized;
+    /// add_rels provides a function to add a relationship into the
+    /// relationships part by given path, relationship type, target and target
+    /// mode, the part will be created if it doesn't exist. Returns the
+    /// relationship ID.
+    fn add_rels(
+        &mut self,
+        path: &str,
+        rel_type: &str,
+        target: &str,
+        target_mode: Option<&str>,
+    ) -> String;
+    /// delete_rels provides a function to delete the relationship by given
+    /// path of the relationships part and relationship ID.
+    fn delete_rels(&mut self, path: &str, rid: &str);
+    /// get_rels_target_path provides a function to get the path of the target
+    /// part in the package by given source part path and relationship ID.
+    fn get_rels_target_path(&self, part: &str, rid: &str) -> Option<String>;
+    /// get_rels_by_type provides a function to get the relationship IDs and
+    /// the paths of the target parts by given source part path and
+    /// relationship type.
+    fn get_rels_by_type(&self, part: &str, rel_type: &str) -> Vec<(String, String)>;
 }
 
 impl Rels for Spreadsheet {
@@ -19,7 +49,7 @@ impl Rels for Spreadsheet {
             Some(_) => Ok(()),
             None => {
                 if let Some(buf) = self.file.get_key_value(path) {
-                    let s = match std::str::from_utf8(&buf.1) {
+                    let s = match std::str::from_utf8(buf.1) {
                         Ok(v) => v,
                         Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
                     };
@@ -35,4 +65,109 @@ impl Rels for Spreadsheet {
             }
         }
     }
+
+    fn add_rels(
+        &mut self,
+        path: &str,
+        rel_type: &str,
+        target: &str,
+        target_mode: Option<&str>,
+    ) -> String {
+        self.rels_reader(path).ok();
+        let rels =
+            self.rels
+                .entry(String::from(path))
+                .or_insert_with(|| xml_rels::XMLRelationships {
+                    relationship: Vec::new(),
+                });
+        let id = rels
+            .relationship
+            .iter()
+            .filter_map(|rel| rel.id.strip_prefix("rId")?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let rid = format!("rId{}", id);
+        rels.relationship.push(xml_rels::XMLRelationship {
+            id: rid.clone(),
+            rel_type: String::from(rel_type),
+            target: String::from(target),
+            target_mode: target_mode.map(String::from),
+        });
+        rid
+    }
+
+    fn delete_rels(&mut self, path: &str, rid: &str) {
+        if let Some(rels) = self.rels.get_mut(path) {
+            rels.relationship.retain(|rel| rel.id != rid);
+        }
+    }
+
+    fn get_rels_target_path(&self, part: &str, rid: &str) -> Option<String> {
+        self.rels
+            .get(&get_rels_path(part))?
+            .relationship
+            .iter()
+            .find(|rel| rel.id == rid)
+            .map(|rel| get_target_path(part, &rel.target))
+    }
+
+    fn get_rels_by_type(&self, part: &str, rel_type: &str) -> Vec<(String, String)> {
+        self.rels
+            .get(&get_rels_path(part))
+            .iter()
+            .flat_map(|rels| rels.relationship.iter())
+            .filter(|rel| rel.rel_type == rel_type)
+            .map(|rel| (rel.id.clone(), get_target_path(part, &rel.target)))
+            .collect()
+    }
+}
+
+// get_target_path provides a function to resolve the target of the
+// relationship to the part path in the package by given source part path, for
+// example ../comments1.xml of the xl/worksheets/sheet1.xml is resolved to
+// xl/comments1.xml.
+pub(crate) fn get_target_path(part: &str, target: &str) -> String {
+    if let Some(target) = target.strip_prefix('/') {
+        return String::from(target);
+    }
+    let mut segments: Vec<&str> = part.split('/').collect();
+    segments.pop();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+// get_relative_target provides a function to get the relative target of the
+// relationship from the source part to the target part, for example
+// ../comments1.xml from the xl/worksheets/sheet1.xml to xl/comments1.xml.
+pub(crate) fn get_relative_target(part: &str, path: &str) -> String {
+    let base: Vec<&str> = part.split('/').collect();
+    let base = &base[..base.len() - 1];
+    let target: Vec<&str> = path.split('/').collect();
+    let common = base
+        .iter()
+        .zip(target.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut segments = vec![".."; base.len() - common];
+    segments.extend(&target[common..]);
+    segments.join("/")
+}
+
+// get_rels_path provides a function to get the path of the relationships part
+// by given part path, for example xl/worksheets/_rels/sheet1.xml.rels for the
+// xl/worksheets/sheet1.xml.
+pub(crate) fn get_rels_path(part: &str) -> String {
+    match part.rsplit_once('/') {
+        Some((dir, name)) => format!("{}/_rels/{}.rels", dir, name),
+        None => format!("_rels/{}.rels", part),
+    }
 }
@@ -0,0 +1,361 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    update_outline_level_row, Adjust, AdjustDirection, CTRow, ExcelizeError, Spreadsheet,
+    Worksheet, XMLWorksheet, MAX_ROW_HEIGHT, TOTAL_ROWS,
+};
+
+// DEFAULT_ROW_HEIGHT specifies the default row height in points, used when
+// neither the row nor the sheet format properties defines the height.
+pub(crate) static DEFAULT_ROW_HEIGHT: f64 = 15.0;
+
+pub trait Rows {
+    /// insert_rows provides a function to insert new rows before the given
+    /// Excel row number starting from 1 and number of rows. The references of
+    /// the cells, merged cells, hyperlinks, auto filter, data validations,
+    /// conditional formats, defined names and formulas across the workbook
+    /// will be adjusted. For example, create two rows before row 3 in Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.insert_rows("Sheet1", 3, 2)
+    /// ```
+    fn insert_rows(&mut self, sheet: &str, row: u32, n: u32) -> Result<(), ExcelizeError>;
+    /// remove_row provides a function to remove single row by given worksheet
+    /// name and Excel row number, the rows below are moved up and the
+    /// references to the removed cells in formulas become #REF!.
+    fn remove_row(&mut self, sheet: &str, row: u32) -> Result<(), ExcelizeError>;
+    /// set_row_height provides a function to set the height of a single row
+    /// by given worksheet name and Excel row number. The height is measured in
+    /// points and should be less than or equal to 409. For example, set the
+    /// height of the first row in Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.set_row_height("Sheet1", 1, 50.0)
+    /// ```
+    fn set_row_height(&mut self, sheet: &str, row: u32, height: f64) -> Result<(), ExcelizeError>;
+    /// get_row_height provides a function to get the height of a single row by
+    /// given worksheet name and Excel row number, the default height of the
+    /// worksheet will be returned if the row height isn't set.
+    fn get_row_height(&self, sheet: &str, row: u32) -> Result<f64, ExcelizeError>;
+    /// set_row_visible provides a function to set visible of a single row by
+    /// given worksheet name and Excel row number.
+    fn set_row_visible(
+        &mut self,
+        sheet: &str,
+        row: u32,
+        visible: bool,
+    ) -> Result<(), ExcelizeError>;
+    /// get_row_visible provides a function to get visible of a single row by
+    /// given worksheet name and Excel row number.
+    fn get_row_visible(&self, sheet: &str, row: u32) -> Result<bool, ExcelizeError>;
+    /// set_row_outline_level provides a function to set outline level of a
+    /// single row by given worksheet name and Excel row number. The value of
+    /// the level should be in the range from 1 to 7.
+    fn set_row_outline_level(
+        &mut self,
+        sheet: &str,
+        row: u32,
+        level: u8,
+    ) -> Result<(), ExcelizeError>;
+    /// get_row_outline_level provides a function to get outline level of a
+    /// single row by given worksheet name and Excel row number.
+    fn get_row_outline_level(&self, sheet: &str, row: u32) -> Result<u8, ExcelizeError>;
+    /// set_row_style provides a function to set the style of the rows by given
+    /// worksheet name, rows range and style ID, the style of the existing cells
+    /// in the rows will be updated as well.
+    fn set_row_style(
+        &mut self,
+        sheet: &str,
+        start: u32,
+        end: u32,
+        style: u32,
+    ) -> Result<(), ExcelizeError>;
+}
+
+impl Rows for Spreadsheet {
+    fn insert_rows(&mut self, sheet: &str, row: u32, n: u32) -> Result<(), ExcelizeError> {
+        if row < 1 || row > TOTAL_ROWS {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid row number {}",
+                row
+            )));
+        }
+        if n < 1 || n > TOTAL_ROWS {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid number of rows {}",
+                n
+            )));
+        }
+        self.adjust_helper(sheet, AdjustDirection::Rows, row, n as i32)
+    }
+
+    fn remove_row(&mut self, sheet: &str, row: u32) -> Result<(), ExcelizeError> {
+        if row < 1 || row > TOTAL_ROWS {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid row number {}",
+                row
+            )));
+        }
+        self.adjust_helper(sheet, AdjustDirection::Rows, row, -1)
+    }
+
+    fn set_row_height(&mut self, sheet: &str, row: u32, height: f64) -> Result<(), ExcelizeError> {
+        check_row(row)?;
+        if !height.is_finite() {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid height {} of the row",
+                height
+            )));
+        }
+        if height > MAX_ROW_HEIGHT as f64 {
+            return Err(ExcelizeError::CommonError(format!(
+                "the height of the row must be less than or equal to {} points",
+                MAX_ROW_HEIGHT
+            )));
+        }
+        if height < 0.0 {
+            return Err(ExcelizeError::CommonError(String::from(
+                "the height of the row must be greater than or equal to 0",
+            )));
+        }
+        let row = prepare_row(self.get_worksheet_mut(sheet)?, row);
+        row.ht = Some(height);
+        row.custom_height = Some(true);
+        Ok(())
+    }
+
+    fn get_row_height(&self, sheet: &str, row: u32) -> Result<f64, ExcelizeError> {
+        check_row(row)?;
+        let ws = self.get_worksheet(sheet)?;
+        if let Some(height) = get_row(ws, row).and_then(|r| r.ht) {
+            return Ok(height);
+        }
+        match ws.sheet_format_pr {
+            Some(ref format_pr) if format_pr.default_row_height > 0.0 => {
+                Ok(format_pr.default_row_height)
+            }
+            _ => Ok(DEFAULT_ROW_HEIGHT),
+        }
+    }
+
+    fn set_row_visible(
+        &mut self,
+        sheet: &str,
+        row: u32,
+        visible: bool,
+    ) -> Result<(), ExcelizeError> {
+        check_row(row)?;
+        let row = prepare_row(self.get_worksheet_mut(sheet)?, row);
+        row.hidden = if visible { None } else { Some(true) };
+        Ok(())
+    }
+
+    fn get_row_visible(&self, sheet: &str, row: u32) -> Result<bool, ExcelizeError> {
+        check_row(row)?;
+        let ws = self.get_worksheet(sheet)?;
+        Ok(get_row(ws, row).and_then(|r| r.hidden) != Some(true))
+    }
+
+    fn set_row_outline_level(
+        &mut self,
+        sheet: &str,
+        row: u32,
+        level: u8,
+    ) -> Result<(), ExcelizeError> {
+        check_row(row)?;
+        if !(1..=7).contains(&level) {
+            return Err(ExcelizeError::CommonError(String::from(
+                "invalid outline level",
+            )));
+        }
+        let ws = self.get_worksheet_mut(sheet)?;
+        prepare_row(ws, row).outline_level = Some(level);
+        update_outline_level_row(ws);
+        Ok(())
+    }
+
+    fn get_row_outline_level(&self, sheet: &str, row: u32) -> Result<u8, ExcelizeError> {
+        check_row(row)?;
+        let ws = self.get_worksheet(sheet)?;
+        Ok(get_row(ws, row).and_then(|r| r.outline_level).unwrap_or(0))
+    }
+
+    fn set_row_style(
+        &mut self,
+        sheet: &str,
+        start: u32,
+        end: u32,
+        style: u32,
+    ) -> Result<(), ExcelizeError> {
+        check_row(start)?;
+        check_row(end)?;
+        let ws = self.get_worksheet_mut(sheet)?;
+        for r in start.min(end)..=start.max(end) {
+            let row = prepare_row(ws, r);
+            row.s = Some(style);
+            row.custom_format = Some(true);
+            for c in row.c.iter_mut() {
+                c.s = Some(style);
+            }
+        }
+        Ok(())
+    }
+}
+
+// check_row provides a function to check if the row number is in the range of
+// the worksheet rows.
+fn check_row(row: u32) -> Result<(), ExcelizeError> {
+    if row < 1 || row > TOTAL_ROWS {
+        return Err(ExcelizeError::CommonError(format!(
+            "invalid row number {}",
+            row
+        )));
+    }
+    Ok(())
+}
+
+// get_row provides a function to get the row by given Excel row number.
+pub(crate) fn get_row(ws: &XMLWorksheet, row: u32) -> Option<&CTRow> {
+    ws.sheet_data
+        .row
+        .as_ref()
+        .and_then(|rows| rows.iter().find(|r| r.r == Some(row)))
+}
+
+// prepare_row provides a function to get the row by given Excel row number,
+// an empty row will be created in the sheet data in order if it doesn't
+// exist.
+pub(crate) fn prepare_row(ws: &mut XMLWorksheet, row: u32) -> &mut CTRow {
+    let rows = ws.sheet_data.row.get_or_insert_with(Vec::new);
+    let i = match rows.iter().position(|r| r.r.map_or(false, |r| r >= row)) {
+        Some(i) if rows[i].r == Some(row) => i,
+        Some(i) => {
+            rows.insert(i, new_row(row));
+            i
+        }
+        None => {
+            rows.push(new_row(row));
+            rows.len() - 1
+        }
+    };
+    &mut rows[i]
+}
+
+// prepare_rows provides a function to get the rows in the range [start, end]
+// of the sheet data by walking the rows once, the missing rows in the range
+// will be created in order if create is true.
+pub(crate) fn prepare_rows(
+    ws: &mut XMLWorksheet,
+    start: u32,
+    end: u32,
+    create: bool,
+) -> &mut [CTRow] {
+    let rows = ws.sheet_data.row.get_or_insert_with(Vec::new);
+    let from = rows
+        .iter()
+        .position(|r| r.r.map_or(false, |r| r >= start))
+        .unwrap_or(rows.len());
+    let to = rows[from..]
+        .iter()
+        .position(|r| r.r.map_or(false, |r| r > end))
+        .map_or(rows.len(), |i| from + i);
+    if !create {
+        return &mut rows[from..to];
+    }
+    let mut existing = rows.drain(from..to).peekable();
+    let mut span = Vec::with_capacity((end - start + 1) as usize);
+    for r in start..=end {
+        match existing.peek() {
+            Some(row) if row.r == Some(r) => span.extend(existing.next()),
+            _ => span.push(new_row(r)),
+        }
+    }
+    span.extend(existing);
+    let len = span.len();
+    rows.splice(from..from, span);
+    &mut rows[from..from + len]
+}
+
+fn new_row(row: u32) -> CTRow {
+    CTRow {
+        r: Some(row),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cell;
+
+    #[test]
+    fn test_insert_and_remove_rows() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        wb.insert_rows("Sheet2", 2, 3).unwrap();
+        assert_eq!(wb.get_cell_value("Sheet2", 5, 4).unwrap(), "200");
+        assert_eq!(wb.get_cell_value("Sheet2", 2, 4).unwrap(), "");
+        let ws = &wb.worksheets["Sheet2"];
+        assert_eq!(ws.dimension.as_ref().unwrap().ref_attr, "A1:D14");
+        let sheet1 = &wb.worksheets["Sheet1"];
+        let f = sheet1.sheet_data.row.as_ref().unwrap()[0].c[1]
+            .f
+            .as_ref()
+            .unwrap();
+        assert_eq!(f.content.as_deref(), Some("SUM(Sheet2!D5,Sheet2!D14)"));
+
+        wb.remove_row("Sheet2", 5).unwrap();
+        assert_eq!(wb.get_cell_value("Sheet2", 5, 4).unwrap(), "450");
+        let sheet1 = &wb.worksheets["Sheet1"];
+        let f = sheet1.sheet_data.row.as_ref().unwrap()[0].c[1]
+            .f
+            .as_ref()
+            .unwrap();
+        assert_eq!(f.content.as_deref(), Some("SUM(Sheet2!#REF!,Sheet2!D13)"));
+
+        assert!(wb.insert_rows("Sheet2", 0, 1).is_err());
+        assert!(wb.insert_rows("Sheet2", 1, 1048576).is_err());
+        assert!(wb.remove_row("SheetN", 1).is_err());
+    }
+
+    #[test]
+    fn test_row_props() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert_eq!(wb.get_row_height("Sheet1", 22).unwrap(), 25.0);
+        assert_eq!(wb.get_row_height("Sheet1", 1).unwrap(), 15.75);
+        wb.set_row_height("Sheet1", 20, 30.0).unwrap();
+        assert_eq!(wb.get_row_height("Sheet1", 20).unwrap(), 30.0);
+        let rows: Vec<Option<u32>> = wb.worksheets["Sheet1"]
+            .sheet_data
+            .row
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|r| r.r)
+            .collect();
+        assert_eq!(rows, vec![Some(19), Some(20), Some(21), Some(22)]);
+        assert!(wb.set_row_height("Sheet1", 1, 409.5).is_err());
+        assert!(wb.set_row_height("Sheet1", 0, 20.0).is_err());
+        assert!(wb.set_row_height("Sheet1", 1, f64::NAN).is_err());
+        assert!(wb.set_row_height("Sheet1", 1, f64::INFINITY).is_err());
+        assert_eq!(wb.get_row_height("Sheet1", 1).unwrap(), 15.75);
+
+        assert_eq!(wb.get_row_height("Sheet2", 9).unwrap(), 0.0);
+        wb.set_row_visible("Sheet2", 3, false).unwrap();
+        assert!(!wb.get_row_visible("Sheet2", 3).unwrap());
+        wb.set_row_visible("Sheet2", 3, true).unwrap();
+        assert!(wb.get_row_visible("Sheet2", 3).unwrap());
+
+        wb.set_row_outline_level("Sheet2", 30, 3).unwrap();
+        assert_eq!(wb.get_row_outline_level("Sheet2", 30).unwrap(), 3);
+        assert_eq!(wb.get_row_outline_level("Sheet2", 31).unwrap(), 0);
+        assert!(wb.set_row_outline_level("Sheet2", 30, 0).is_err());
+
+        wb.set_row_style("Sheet2", 2, 1, 4).unwrap();
+        let rows = wb.worksheets["Sheet2"].sheet_data.row.as_ref().unwrap();
+        assert_eq!(rows[0].s, Some(4));
+        assert_eq!(rows[1].c[2].s, Some(4));
+        assert!(wb.set_row_style("SheetN", 1, 1, 4).is_err());
+    }
+}
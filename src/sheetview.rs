@@ -0,0 +1,444 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    cell_name_to_coordinates, CTPane, CTSelection, CTSheetView, CTSheetViews, ExcelizeError,
+    Spreadsheet, Worksheet, XMLWorksheet,
+};
+
+/// Panes directly maps the settings of the panes. The freeze and split can't
+/// be set at the same time. When freezing panes, the x_split and y_split are
+/// the number of the columns and rows in the top-left pane, and when splitting
+/// panes, they are the positions of the split measured in 1/20th of a point,
+/// which may be fractional.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Panes {
+    pub freeze: bool,
+    pub split: bool,
+    pub x_split: f64,
+    pub y_split: f64,
+    pub top_left_cell: String,
+    pub active_pane: String,
+    pub selections: Vec<Selection>,
+}
+
+/// Selection directly maps the settings of the worksheet selection, the pane
+/// is one of "bottomLeft", "bottomRight", "topLeft" and "topRight".
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selection {
+    pub sqref: String,
+    pub active_cell: String,
+    pub pane: String,
+}
+
+/// ViewOptions directly maps the settings of sheet view. The options which are
+/// None will be left unchanged when setting the sheet view. The view is one of
+/// "normal", "pageLayout" and "pageBreakPreview", and the zoom scale should be
+/// in the range from 10 to 400.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ViewOptions {
+    pub default_grid_color: Option<bool>,
+    pub right_to_left: Option<bool>,
+    pub show_formulas: Option<bool>,
+    pub show_grid_lines: Option<bool>,
+    pub show_row_col_headers: Option<bool>,
+    pub show_ruler: Option<bool>,
+    pub show_zeros: Option<bool>,
+    pub top_left_cell: Option<String>,
+    pub view: Option<String>,
+    pub zoom_scale: Option<u32>,
+}
+
+static VIEW_TYPES: [&str; 3] = ["normal", "pageLayout", "pageBreakPreview"];
+
+static PANE_TYPES: [&str; 4] = ["bottomLeft", "bottomRight", "topLeft", "topRight"];
+
+pub trait SheetView {
+    /// set_panes provides a function to create and remove freeze panes and
+    /// split panes by given worksheet name and panes options. For example,
+    /// freeze the first row and the first column of Sheet1:
+    ///
+    /// ```ignore
+    /// spreadsheet.set_panes(
+    ///     "Sheet1",
+    ///     &Panes {
+    ///         freeze: true,
+    ///         x_split: 1.0,
+    ///         y_split: 1.0,
+    ///         top_left_cell: String::from("B2"),
+    ///         active_pane: String::from("bottomRight"),
+    ///         selections: vec![Selection {
+    ///             sqref: String::from("B2"),
+    ///             active_cell: String::from("B2"),
+    ///             pane: String::from("bottomRight"),
+    ///         }],
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// ```
+    ///
+    /// The panes will be removed if neither freeze nor split is set.
+    fn set_panes(&mut self, sheet: &str, panes: &Panes) -> Result<(), ExcelizeError>;
+    /// get_panes provides a function to get freeze panes, split panes, and
+    /// worksheet views by given worksheet name.
+    fn get_panes(&self, sheet: &str) -> Result<Panes, ExcelizeError>;
+    /// set_sheet_view provides a function to set the sheet view options by
+    /// given worksheet name and view index. The view index may be negative, in
+    /// which case it counts backward, -1 is the last view. For example, show
+    /// Sheet1 from right to left in page layout view:
+    ///
+    /// ```ignore
+    /// spreadsheet.set_sheet_view(
+    ///     "Sheet1",
+    ///     -1,
+    ///     &ViewOptions {
+    ///         right_to_left: Some(true),
+    ///         view: Some(String::from("pageLayout")),
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// ```
+    fn set_sheet_view(
+        &mut self,
+        sheet: &str,
+        view_index: i32,
+        opts: &ViewOptions,
+    ) -> Result<(), ExcelizeError>;
+    /// get_sheet_view provides a function to get the sheet view options by
+    /// given worksheet name and view index, the options which are not set in
+    /// the sheet view will be returned with the default values.
+    fn get_sheet_view(&self, sheet: &str, view_index: i32) -> Result<ViewOptions, ExcelizeError>;
+}
+
+impl SheetView for Spreadsheet {
+    fn set_panes(&mut self, sheet: &str, panes: &Panes) -> Result<(), ExcelizeError> {
+        if panes.freeze && panes.split {
+            return Err(ExcelizeError::CommonError(String::from(
+                "freeze and split panes can't be set at the same time",
+            )));
+        }
+        for split in [panes.x_split, panes.y_split] {
+            if !split.is_finite() || split < 0.0 || (panes.freeze && split.fract() != 0.0) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid split position {} of the panes",
+                    split
+                )));
+            }
+        }
+        check_pane_type(&panes.active_pane)?;
+        if !panes.top_left_cell.is_empty() {
+            cell_name_to_coordinates(&panes.top_left_cell)?;
+        }
+        for selection in &panes.selections {
+            check_pane_type(&selection.pane)?;
+        }
+        let view = prepare_sheet_view(self.get_worksheet_mut(sheet)?);
+        view.pane = match (panes.freeze, panes.split) {
+            (false, false) => None,
+            (freeze, _) => Some(CTPane {
+                x_split: (panes.x_split > 0.0).then_some(panes.x_split),
+                y_split: (panes.y_split > 0.0).then_some(panes.y_split),
+                top_left_cell: none_if_empty(&panes.top_left_cell),
+                active_pane: none_if_empty(&panes.active_pane),
+                state: Some(String::from(if freeze { "frozen" } else { "split" })),
+            }),
+        };
+        view.selection = match panes.selections.is_empty() {
+            true => None,
+            false => Some(
+                panes
+                    .selections
+                    .iter()
+                    .map(|s| CTSelection {
+                        pane: none_if_empty(&s.pane),
+                        active_cell: none_if_empty(&s.active_cell),
+                        active_cell_id: None,
+                        sqref: none_if_empty(&s.sqref),
+                    })
+                    .collect(),
+            ),
+        };
+        Ok(())
+    }
+
+    fn get_panes(&self, sheet: &str) -> Result<Panes, ExcelizeError> {
+        let ws = self.get_worksheet(sheet)?;
+        let mut panes = Panes::default();
+        let view = match ws.sheet_views {
+            Some(ref views) => match views.sheet_view.last() {
+                Some(view) => view,
+                None => return Ok(panes),
+            },
+            None => return Ok(panes),
+        };
+        if let Some(ref pane) = view.pane {
+            let state = pane.state.as_deref().unwrap_or("split");
+            panes.freeze = state == "frozen" || state == "frozenSplit";
+            panes.split = state == "split";
+            panes.x_split = pane.x_split.unwrap_or(0.0);
+            panes.y_split = pane.y_split.unwrap_or(0.0);
+            panes.top_left_cell = pane.top_left_cell.clone().unwrap_or_default();
+            panes.active_pane = pane.active_pane.clone().unwrap_or_default();
+        }
+        if let Some(ref selections) = view.selection {
+            panes.selections = selections
+                .iter()
+                .map(|s| Selection {
+                    sqref: s.sqref.clone().unwrap_or_default(),
+                    active_cell: s.active_cell.clone().unwrap_or_default(),
+                    pane: s.pane.clone().unwrap_or_default(),
+                })
+                .collect();
+        }
+        Ok(panes)
+    }
+
+    fn set_sheet_view(
+        &mut self,
+        sheet: &str,
+        view_index: i32,
+        opts: &ViewOptions,
+    ) -> Result<(), ExcelizeError> {
+        if let Some(ref view) = opts.view {
+            if !VIEW_TYPES.contains(&view.as_str()) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid sheet view type {:?}",
+                    view
+                )));
+            }
+        }
+        if let Some(zoom_scale) = opts.zoom_scale {
+            if !(10..=400).contains(&zoom_scale) {
+                return Err(ExcelizeError::CommonError(String::from(
+                    "the zoom scale must be in the range from 10 to 400",
+                )));
+            }
+        }
+        if let Some(ref cell) = opts.top_left_cell {
+            cell_name_to_coordinates(cell)?;
+        }
+        let ws = self.get_worksheet_mut(sheet)?;
+        prepare_sheet_view(ws);
+        let view = get_sheet_view_mut(ws, view_index)?;
+        let bools = [
+            (&mut view.default_grid_color, opts.default_grid_color),
+            (&mut view.right_to_left, opts.right_to_left),
+            (&mut view.show_formulas, opts.show_formulas),
+            (&mut view.show_grid_lines, opts.show_grid_lines),
+            (&mut view.show_row_col_headers, opts.show_row_col_headers),
+            (&mut view.show_ruler, opts.show_ruler),
+            (&mut view.show_zeros, opts.show_zeros),
+        ];
+        for (field, value) in bools {
+            if value.is_some() {
+                *field = value;
+            }
+        }
+        if opts.top_left_cell.is_some() {
+            view.top_left_cell = opts.top_left_cell.clone();
+        }
+        if opts.view.is_some() {
+            view.view = opts.view.clone();
+        }
+        if opts.zoom_scale.is_some() {
+            view.zoom_scale = opts.zoom_scale;
+        }
+        Ok(())
+    }
+
+    fn get_sheet_view(&self, sheet: &str, view_index: i32) -> Result<ViewOptions, ExcelizeError> {
+        let ws = self.get_worksheet(sheet)?;
+        let default_view = CTSheetView::default();
+        let view = match ws.sheet_views {
+            Some(ref views) if !views.sheet_view.is_empty() => {
+                let i = view_index_to_position(views.sheet_view.len(), view_index)?;
+                &views.sheet_view[i]
+            }
+            _ => {
+                view_index_to_position(1, view_index)?;
+                &default_view
+            }
+        };
+        Ok(ViewOptions {
+            default_grid_color: Some(view.default_grid_color.unwrap_or(true)),
+            right_to_left: Some(view.right_to_left.unwrap_or(false)),
+            show_formulas: Some(view.show_formulas.unwrap_or(false)),
+            show_grid_lines: Some(view.show_grid_lines.unwrap_or(true)),
+            show_row_col_headers: Some(view.show_row_col_headers.unwrap_or(true)),
+            show_ruler: Some(view.show_ruler.unwrap_or(true)),
+            show_zeros: Some(view.show_zeros.unwrap_or(true)),
+            top_left_cell: Some(view.top_left_cell.clone().unwrap_or_default()),
+            view: Some(view.view.clone().unwrap_or_else(|| String::from("normal"))),
+            zoom_scale: Some(view.zoom_scale.unwrap_or(100)),
+        })
+    }
+}
+
+// view_index_to_position provides a function to convert the view index, which
+// may count backward from the last view, to the position in the sheet views.
+fn view_index_to_position(len: usize, view_index: i32) -> Result<usize, ExcelizeError> {
+    let i = match view_index < 0 {
+        true => len as i64 + view_index as i64,
+        false => view_index as i64,
+    };
+    if i < 0 || i >= len as i64 {
+        return Err(ExcelizeError::CommonError(format!(
+            "view index {} out of range",
+            view_index
+        )));
+    }
+    Ok(i as usize)
+}
+
+fn get_sheet_view_mut(
+    ws: &mut XMLWorksheet,
+    view_index: i32,
+) -> Result<&mut CTSheetView, ExcelizeError> {
+    let views = match ws.sheet_views {
+        Some(ref mut views) => &mut views.sheet_view,
+        None => {
+            return Err(ExcelizeError::CommonError(String::from(
+                "sheet view is none",
+            )))
+        }
+    };
+    let i = view_index_to_position(views.len(), view_index)?;
+    Ok(&mut views[i])
+}
+
+// check_pane_type provides a function to check if the pane type is valid, the
+// empty pane type is allowed.
+fn check_pane_type(pane: &str) -> Result<(), ExcelizeError> {
+    if pane.is_empty() || PANE_TYPES.contains(&pane) {
+        return Ok(());
+    }
+    Err(ExcelizeError::CommonError(format!(
+        "invalid pane type {:?}",
+        pane
+    )))
+}
+
+fn none_if_empty(s: &str) -> Option<String> {
+    (!s.is_empty()).then(|| String::from(s))
+}
+
+// prepare_sheet_view provides a function to get the last sheet view of the
+// worksheet, which is the one loaded by the spreadsheet application. A default
+// sheet view will be created if the worksheet doesn't have any.
+pub(crate) fn prepare_sheet_view(ws: &mut XMLWorksheet) -> &mut CTSheetView {
+    let views = ws.sheet_views.get_or_insert_with(|| CTSheetViews {
+        sheet_view: Vec::new(),
+        ext_lst: None,
+    });
+    if views.sheet_view.is_empty() {
+        views.sheet_view.push(CTSheetView::default());
+    }
+    let last = views.sheet_view.len() - 1;
+    &mut views.sheet_view[last]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panes() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let panes = wb.get_panes("Sheet1").unwrap();
+        assert!(!panes.freeze && !panes.split);
+        assert_eq!(panes.selections[0].active_cell, "A18");
+
+        let panes = Panes {
+            freeze: true,
+            x_split: 1.0,
+            y_split: 1.0,
+            top_left_cell: String::from("B2"),
+            active_pane: String::from("bottomRight"),
+            selections: vec![Selection {
+                sqref: String::from("B2"),
+                active_cell: String::from("B2"),
+                pane: String::from("bottomRight"),
+            }],
+            ..Default::default()
+        };
+        wb.set_panes("Sheet1", &panes).unwrap();
+        assert_eq!(wb.get_panes("Sheet1").unwrap(), panes);
+
+        let split = Panes {
+            split: true,
+            x_split: 1234.5,
+            y_split: 2000.0,
+            ..Default::default()
+        };
+        wb.set_panes("Sheet1", &split).unwrap();
+        assert_eq!(wb.get_panes("Sheet1").unwrap().x_split, 1234.5);
+        assert!(wb
+            .set_panes(
+                "Sheet1",
+                &Panes {
+                    x_split: 1.5,
+                    ..panes.clone()
+                }
+            )
+            .is_err());
+        assert!(wb
+            .set_panes(
+                "Sheet1",
+                &Panes {
+                    y_split: f64::NAN,
+                    ..split
+                }
+            )
+            .is_err());
+
+        wb.set_panes("Sheet1", &Panes::default()).unwrap();
+        assert_eq!(wb.get_panes("Sheet1").unwrap(), Panes::default());
+
+        let invalid = Panes {
+            split: true,
+            active_pane: String::from("left"),
+            ..Default::default()
+        };
+        assert!(wb.set_panes("Sheet1", &invalid).is_err());
+        assert!(wb.get_panes("SheetN").is_err());
+    }
+
+    #[test]
+    fn test_sheet_view() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let opts = wb.get_sheet_view("Sheet1", 0).unwrap();
+        assert_eq!(opts.zoom_scale, Some(115));
+        assert_eq!(opts.show_grid_lines, Some(true));
+        assert_eq!(opts.view.as_deref(), Some("normal"));
+
+        let opts = ViewOptions {
+            right_to_left: Some(true),
+            show_grid_lines: Some(false),
+            view: Some(String::from("pageLayout")),
+            top_left_cell: Some(String::from("B2")),
+            ..Default::default()
+        };
+        wb.set_sheet_view("Sheet1", -1, &opts).unwrap();
+        let opts = wb.get_sheet_view("Sheet1", -1).unwrap();
+        assert_eq!(opts.right_to_left, Some(true));
+        assert_eq!(opts.show_grid_lines, Some(false));
+        assert_eq!(opts.view.as_deref(), Some("pageLayout"));
+        assert_eq!(opts.zoom_scale, Some(115));
+
+        let invalid = ViewOptions {
+            zoom_scale: Some(401),
+            ..Default::default()
+        };
+        assert!(wb.set_sheet_view("Sheet1", 0, &invalid).is_err());
+        let invalid = ViewOptions {
+            view: Some(String::from("fullScreen")),
+            ..Default::default()
+        };
+        assert!(wb.set_sheet_view("Sheet1", 0, &invalid).is_err());
+        assert!(wb
+            .set_sheet_view("Sheet1", 1, &ViewOptions::default())
+            .is_err());
+        assert!(wb.get_sheet_view("Sheet1", -2).is_err());
+    }
+}
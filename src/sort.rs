@@ -0,0 +1,686 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{
+    cell_name_to_coordinates, coordinates_to_cell_name, coordinates_to_range_ref,
+    get_cell_fill_color, get_cell_font_color, get_dxf, get_row, is_same_color, move_comments,
+    offset_formula, prepare_row, range_ref_to_coordinates, CTCell, CTCellFormula, CTColor,
+    CTSortCondition, CTSortState, Cell, ExcelizeError, Spreadsheet, Worksheet, XMLWorksheet,
+};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// SortOptions directly maps the settings of sorting a range. The rows of the
+/// range are sorted by default, and the columns will be sorted when the
+/// column_sort is true. The texts are compared case-insensitively unless the
+/// case_sensitive is true.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SortOptions {
+    pub column_sort: bool,
+    pub case_sensitive: bool,
+}
+
+pub trait Sort {
+    /// sort_range provides a function to sort the cells in the range by given
+    /// worksheet name, range reference, sort conditions and options. The ref
+    /// of each sort condition specifies the key column, or the key row when
+    /// sorting the columns. The condition sorts by value by default, in the
+    /// order of the custom list if it's specified, and sorts the cells with
+    /// the fill or font color of the differential format on top when the
+    /// sort_by is "cellColor" or "fontColor". The relative references in the
+    /// formulas of the moved cells will be adjusted, and the sort state will
+    /// be saved in the worksheet. The comments and the hyperlinks of the
+    /// single cells are moved with the cells, while the data validations,
+    /// conditional formats and the hyperlinks of the ranges are kept at their
+    /// addresses. The shared formulas in the range should have the master
+    /// cell. For example, sort the rows in the range
+    /// A2:D11 on Sheet1 by the values of the column D in descending order:
+    ///
+    /// ```ignore
+    /// spreadsheet.sort_range(
+    ///     "Sheet1",
+    ///     "A2:D11",
+    ///     vec![CTSortCondition {
+    ///         descending: Some(true),
+    ///         ref_attr: String::from("D2:D11"),
+    ///         ..Default::default()
+    ///     }],
+    ///     &SortOptions::default(),
+    /// )
+    /// ```
+    fn sort_range(
+        &mut self,
+        sheet: &str,
+        range_ref: &str,
+        conditions: Vec<CTSortCondition>,
+        opts: &SortOptions,
+    ) -> Result<(), ExcelizeError>;
+}
+
+impl Sort for Spreadsheet {
+    fn sort_range(
+        &mut self,
+        sheet: &str,
+        range_ref: &str,
+        conditions: Vec<CTSortCondition>,
+        opts: &SortOptions,
+    ) -> Result<(), ExcelizeError> {
+        let coordinates = range_ref_to_coordinates(range_ref)?;
+        if conditions.is_empty() {
+            return Err(ExcelizeError::CommonError(String::from(
+                "the sort conditions can't be empty",
+            )));
+        }
+        let ws = self.get_worksheet(sheet)?;
+        if let Some(ref merge_cells) = ws.merge_cells {
+            for merge_cell in &merge_cells.merge_cell {
+                if is_overlapped(
+                    &range_ref_to_coordinates(&merge_cell.ref_attr)?,
+                    &coordinates,
+                ) {
+                    return Err(ExcelizeError::CommonError(format!(
+                        "cannot sort the range {} which contains merged cells",
+                        range_ref
+                    )));
+                }
+            }
+        }
+        let lines = match opts.column_sort {
+            true => coordinates[0]..=coordinates[2],
+            false => coordinates[1]..=coordinates[3],
+        };
+        let mut keys = Vec::with_capacity(conditions.len());
+        for condition in &conditions {
+            keys.push(get_sort_keys(self, ws, &coordinates, condition, opts)?);
+        }
+        let mut order: Vec<usize> = (0..lines.clone().count()).collect();
+        order.sort_by(|a, b| {
+            conditions
+                .iter()
+                .zip(keys.iter())
+                .map(|(condition, keys)| {
+                    compare_sort_keys(&keys[*a], &keys[*b], condition, opts.case_sensitive)
+                })
+                .find(|ord| *ord != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+        let mut position = vec![0; order.len()];
+        for (i, line) in order.iter().enumerate() {
+            position[*line] = i as u32;
+        }
+
+        let ws = self.get_worksheet_mut(sheet)?;
+        unshare_formulas(ws, &coordinates)?;
+        let mut cells = Vec::new();
+        for row in ws.sheet_data.row.iter_mut().flatten() {
+            match row.r {
+                Some(r) if r >= coordinates[1] && r <= coordinates[3] => {}
+                _ => continue,
+            }
+            let mut rest = Vec::with_capacity(row.c.len());
+            for cell in row.c.drain(..) {
+                let (col, r) = cell_name_to_coordinates(&cell.r)?;
+                match col >= coordinates[0] && col <= coordinates[2] {
+                    true => cells.push((col, r, cell)),
+                    false => rest.push(cell),
+                }
+            }
+            row.c = rest;
+        }
+        let move_cell = |col: u32, row: u32| {
+            if col < coordinates[0]
+                || col > coordinates[2]
+                || row < coordinates[1]
+                || row > coordinates[3]
+            {
+                return (col, row);
+            }
+            match opts.column_sort {
+                true => (
+                    coordinates[0] + position[(col - coordinates[0]) as usize],
+                    row,
+                ),
+                false => (
+                    col,
+                    coordinates[1] + position[(row - coordinates[1]) as usize],
+                ),
+            }
+        };
+        for (col, row, mut cell) in cells {
+            let (new_col, new_row) = move_cell(col, row);
+            cell.r = coordinates_to_cell_name(new_col, new_row)?;
+            if let Some(ref mut f) = cell.f {
+                let (cols, rows) = (new_col as i64 - col as i64, new_row as i64 - row as i64);
+                if let Some(ref mut content) = f.content {
+                    *content = offset_formula(content, cols, rows);
+                }
+                if let Some(ref mut ref_attr) = f.ref_attr {
+                    *ref_attr = offset_formula(ref_attr, cols, rows);
+                }
+            }
+            prepare_row(ws, new_row).c.push(cell);
+        }
+        for row in ws.sheet_data.row.iter_mut().flatten() {
+            row.c
+                .sort_by_key(|c| cell_name_to_coordinates(&c.r).map_or(0, |(col, _)| col));
+        }
+        for hyperlink in ws
+            .hyperlinks
+            .iter_mut()
+            .flat_map(|h| h.hyperlink.iter_mut())
+        {
+            if let Ok((col, row)) = cell_name_to_coordinates(&hyperlink.ref_attr) {
+                let (col, row) = move_cell(col, row);
+                hyperlink.ref_attr = coordinates_to_cell_name(col, row)?;
+            }
+        }
+
+        let sort_state = CTSortState {
+            column_sort: opts.column_sort.then_some(true),
+            case_sensitive: opts.case_sensitive.then_some(true),
+            ref_attr: coordinates_to_range_ref(&coordinates)?,
+            sort_condition: conditions,
+            ..Default::default()
+        };
+        let auto_filter = ws.auto_filter.as_mut().filter(|auto_filter| {
+            auto_filter
+                .ref_attr
+                .as_deref()
+                .and_then(|r| range_ref_to_coordinates(r).ok())
+                .map_or(false, |r| {
+                    r[0] <= coordinates[0]
+                        && r[1] <= coordinates[1]
+                        && r[2] >= coordinates[2]
+                        && r[3] >= coordinates[3]
+                })
+        });
+        match auto_filter {
+            Some(auto_filter) => auto_filter.sort_state = Some(sort_state),
+            None => ws.sort_state = Some(sort_state),
+        }
+        move_comments(self, sheet, |col, row| Some(move_cell(col, row)))
+    }
+}
+
+// SortValue defined the value of a cell to be compared in sorting, the order
+// of the variants is the ascending order of the value types.
+#[derive(Debug, Clone, PartialEq)]
+enum SortValue {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Error(String),
+    Blank,
+}
+
+// SortKey defined the key of a line to be compared by a sort condition, which
+// is the value of the cell or whether the cell has the color of the
+// condition.
+#[derive(Debug, Clone, PartialEq)]
+enum SortKey {
+    Value(SortValue),
+    Color(bool),
+}
+
+// is_overlapped checks if the two ranges are overlapped.
+pub(crate) fn is_overlapped(a: &[u32; 4], b: &[u32; 4]) -> bool {
+    a[0] <= b[2] && b[0] <= a[2] && a[1] <= b[3] && b[1] <= a[3]
+}
+
+// get_sort_keys provides a function to get the sort keys of each line in the
+// range by given sort condition.
+fn get_sort_keys(
+    spreadsheet: &Spreadsheet,
+    ws: &XMLWorksheet,
+    coordinates: &[u32; 4],
+    condition: &CTSortCondition,
+    opts: &SortOptions,
+) -> Result<Vec<SortKey>, ExcelizeError> {
+    let key = range_ref_to_coordinates(&condition.ref_attr)?;
+    let (key, lines) = match opts.column_sort {
+        true => (key[1], coordinates[0]..=coordinates[2]),
+        false => (key[0], coordinates[1]..=coordinates[3]),
+    };
+    let (lo, hi) = match opts.column_sort {
+        true => (coordinates[1], coordinates[3]),
+        false => (coordinates[0], coordinates[2]),
+    };
+    if key < lo || key > hi {
+        return Err(ExcelizeError::CommonError(format!(
+            "the sort condition {} is out of the range",
+            condition.ref_attr
+        )));
+    }
+    let color = match condition.sort_by.as_deref().unwrap_or("value") {
+        "value" => None,
+        sort_by @ ("cellColor" | "fontColor") => {
+            let dxf = spreadsheet
+                .styles
+                .as_ref()
+                .zip(condition.dxf_id)
+                .and_then(|(styles, dxf_id)| get_dxf(styles, dxf_id));
+            let color = dxf.and_then(|dxf| match sort_by {
+                "cellColor" => dxf.fill.as_ref().and_then(|fill| {
+                    let pattern_fill = fill.pattern_fill.as_ref()?;
+                    pattern_fill
+                        .bg_color
+                        .as_ref()
+                        .or(pattern_fill.fg_color.as_ref())
+                }),
+                _ => dxf.font.as_ref().and_then(|font| font.color.as_ref()),
+            });
+            match color {
+                Some(color) => Some((sort_by == "cellColor", color)),
+                None => {
+                    return Err(ExcelizeError::CommonError(format!(
+                        "the sort condition {} has no {} differential format",
+                        condition.ref_attr, sort_by
+                    )))
+                }
+            }
+        }
+        sort_by => {
+            return Err(ExcelizeError::CommonError(format!(
+                "unsupported sort by {}",
+                sort_by
+            )))
+        }
+    };
+    Ok(lines
+        .map(|line| {
+            let (col, row) = match opts.column_sort {
+                true => (line, key),
+                false => (key, line),
+            };
+            let cell = get_row(ws, row).and_then(|r| {
+                r.c.iter()
+                    .find(|c| cell_name_to_coordinates(&c.r).map_or(false, |(x, _)| x == col))
+            });
+            match color {
+                Some((fill, color)) => SortKey::Color(cell.map_or(false, |cell| {
+                    get_cell_color(spreadsheet, cell, fill)
+                        .map_or(false, |c| is_same_color(c, color))
+                })),
+                None => SortKey::Value(
+                    cell.map_or(SortValue::Blank, |cell| get_sort_value(spreadsheet, cell)),
+                ),
+            }
+        })
+        .collect())
+}
+
+// get_cell_color provides a function to get the fill or font color of the cell.
+fn get_cell_color<'a>(
+    spreadsheet: &'a Spreadsheet,
+    cell: &CTCell,
+    fill: bool,
+) -> Option<&'a CTColor> {
+    let styles = spreadsheet.styles.as_ref()?;
+    let style = cell.s.unwrap_or(0);
+    match fill {
+        true => get_cell_fill_color(styles, style),
+        false => get_cell_font_color(styles, style),
+    }
+}
+
+// get_sort_value provides a function to get the typed value of the cell.
+fn get_sort_value(spreadsheet: &Spreadsheet, cell: &CTCell) -> SortValue {
+    let value = spreadsheet.get_value_from(cell);
+    if value.is_empty() {
+        return SortValue::Blank;
+    }
+    match cell.t.as_deref() {
+        None | Some("n") => match value.parse::<f64>() {
+            Ok(n) => SortValue::Number(n),
+            Err(_) => SortValue::Text(value),
+        },
+        Some("b") => SortValue::Bool(value == "1"),
+        Some("e") => SortValue::Error(value),
+        _ => SortValue::Text(value),
+    }
+}
+
+// compare_sort_keys provides a function to compare the keys of two lines by
+// given sort condition, the blank cells are always sorted to the end.
+fn compare_sort_keys(
+    a: &SortKey,
+    b: &SortKey,
+    condition: &CTSortCondition,
+    case_sensitive: bool,
+) -> Ordering {
+    let ord = match (a, b) {
+        (SortKey::Color(a), SortKey::Color(b)) => b.cmp(a),
+        (SortKey::Value(a), SortKey::Value(b)) => match (a, b) {
+            (SortValue::Blank, SortValue::Blank) => return Ordering::Equal,
+            (SortValue::Blank, _) => return Ordering::Greater,
+            (_, SortValue::Blank) => return Ordering::Less,
+            _ => match condition.custom_list.as_deref() {
+                Some(list) => match (list_index(list, a), list_index(list, b)) {
+                    (Some(x), Some(y)) => x.cmp(&y),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => compare_sort_values(a, b, case_sensitive),
+                },
+                None => compare_sort_values(a, b, case_sensitive),
+            },
+        },
+        _ => Ordering::Equal,
+    };
+    match condition.descending.unwrap_or(false) {
+        true => ord.reverse(),
+        false => ord,
+    }
+}
+
+// compare_sort_values provides a function to compare two values in ascending
+// order: numbers, texts, logical values and errors. The lowercase letters are
+// sorted before the uppercase letters when the comparison is case-sensitive.
+fn compare_sort_values(a: &SortValue, b: &SortValue, case_sensitive: bool) -> Ordering {
+    let rank = |v: &SortValue| match v {
+        SortValue::Number(_) => 0,
+        SortValue::Text(_) => 1,
+        SortValue::Bool(_) => 2,
+        SortValue::Error(_) => 3,
+        SortValue::Blank => 4,
+    };
+    match (a, b) {
+        (SortValue::Number(a), SortValue::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (SortValue::Text(a), SortValue::Text(b)) => {
+            let ord = a.to_lowercase().cmp(&b.to_lowercase());
+            let swap_case = |s: &str| -> String {
+                s.chars()
+                    .map(|c| match c.is_lowercase() {
+                        true => c.to_uppercase().next().unwrap_or(c),
+                        false => c.to_lowercase().next().unwrap_or(c),
+                    })
+                    .collect()
+            };
+            match (ord, case_sensitive) {
+                (Ordering::Equal, true) => swap_case(a).cmp(&swap_case(b)),
+                _ => ord,
+            }
+        }
+        (SortValue::Bool(a), SortValue::Bool(b)) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+// list_index provides a function to get the position of the value in the
+// comma-separated custom list, the items are compared case-insensitively.
+fn list_index(list: &str, value: &SortValue) -> Option<usize> {
+    let text = match value {
+        SortValue::Number(n) => n.to_string(),
+        SortValue::Text(s) | SortValue::Error(s) => s.clone(),
+        SortValue::Bool(b) => String::from(if *b { "TRUE" } else { "FALSE" }),
+        SortValue::Blank => return None,
+    }
+    .to_lowercase();
+    list.split(',')
+        .position(|item| item.trim().to_lowercase() == text)
+}
+
+// unshare_formulas provides a function to convert the shared formulas which
+// have any cell in the range to the normal formulas, so that the cells could
+// be moved individually.
+fn unshare_formulas(ws: &mut XMLWorksheet, coordinates: &[u32; 4]) -> Result<(), ExcelizeError> {
+    let is_shared = |cell: &CTCell| {
+        cell.f
+            .as_ref()
+            .filter(|f| f.t.as_deref() == Some("shared"))
+            .and_then(|f| f.si)
+    };
+    let mut masters: HashMap<u32, Option<(u32, u32, String)>> = HashMap::new();
+    for row in ws.sheet_data.row.iter().flatten() {
+        for cell in &row.c {
+            let (col, r) = cell_name_to_coordinates(&cell.r)?;
+            if col < coordinates[0] || col > coordinates[2] {
+                continue;
+            }
+            if r < coordinates[1] || r > coordinates[3] {
+                continue;
+            }
+            if let Some(si) = is_shared(cell) {
+                masters.insert(si, None);
+            }
+        }
+    }
+    if masters.is_empty() {
+        return Ok(());
+    }
+    for row in ws.sheet_data.row.iter().flatten() {
+        for cell in &row.c {
+            let formula = cell.f.as_ref().and_then(|f| f.content.as_ref());
+            if let (Some(si), Some(formula)) = (is_shared(cell), formula) {
+                if let Some(master) = masters.get_mut(&si).filter(|_| !formula.is_empty()) {
+                    let (col, r) = cell_name_to_coordinates(&cell.r)?;
+                    *master = Some((col, r, formula.clone()));
+                }
+            }
+        }
+    }
+    if let Some((si, _)) = masters.iter().find(|(_, master)| master.is_none()) {
+        return Err(ExcelizeError::CommonError(format!(
+            "the shared formula {} doesn't have the master cell",
+            si
+        )));
+    }
+    for row in ws.sheet_data.row.iter_mut().flatten() {
+        for cell in row.c.iter_mut() {
+            let master = match is_shared(cell).and_then(|si| masters.get(&si)) {
+                Some(Some(master)) => master,
+                _ => continue,
+            };
+            let (col, r) = cell_name_to_coordinates(&cell.r)?;
+            let (cols, rows) = (col as i64 - master.0 as i64, r as i64 - master.1 as i64);
+            cell.f = Some(CTCellFormula {
+                t: None,
+                ref_attr: None,
+                si: None,
+                content: Some(offset_formula(&master.2, cols, rows)),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CTDxf, CTDxfs, CTFill, CTPatternFill, Comment, Comments, DataValidation, DataValidations,
+    };
+
+    fn column_values(wb: &Spreadsheet, col: u32) -> Vec<String> {
+        (2..=11)
+            .map(|row| wb.get_cell_value("Sheet2", row, col).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_range() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let condition = |ref_attr: &str| CTSortCondition {
+            ref_attr: String::from(ref_attr),
+            ..Default::default()
+        };
+        assert!(wb
+            .sort_range(
+                "Sheet2",
+                "C2:D11",
+                vec![condition("E2:E11")],
+                &SortOptions::default()
+            )
+            .is_err());
+        assert!(wb
+            .sort_range(
+                "Sheet2",
+                "A6:D11",
+                vec![condition("D6")],
+                &SortOptions::default()
+            )
+            .is_err());
+
+        wb.sort_range(
+            "Sheet2",
+            "D11:C2",
+            vec![CTSortCondition {
+                descending: Some(true),
+                ..condition("D2:D11")
+            }],
+            &SortOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            column_values(&wb, 4),
+            vec!["510", "450", "348", "315", "200", "200", "127", "89", "53", "37"]
+        );
+        assert_eq!(wb.get_cell_value("Sheet2", 11, 3).unwrap(), "Other");
+        let sort_state = wb.get_worksheet("Sheet2").unwrap().sort_state.as_ref();
+        assert_eq!(sort_state.unwrap().ref_attr, "C2:D11");
+
+        wb.sort_range(
+            "Sheet2",
+            "C2:D11",
+            vec![CTSortCondition {
+                custom_list: Some(String::from("Other,HP")),
+                ..condition("C2:C11")
+            }],
+            &SortOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(&column_values(&wb, 3)[..2], &["Other", "HP"]);
+
+        // Sort the cells with the solid fill of theme color 0 on bottom
+        wb.styles.as_mut().unwrap().dxfs = Some(CTDxfs {
+            count: Some(1),
+            dxf: vec![CTDxf {
+                fill: Some(CTFill {
+                    pattern_fill: Some(CTPatternFill {
+                        pattern_type: None,
+                        fg_color: None,
+                        bg_color: Some(CTColor {
+                            theme: Some(0),
+                            ..Default::default()
+                        }),
+                    }),
+                }),
+                ..Default::default()
+            }],
+        });
+        wb.sort_range(
+            "Sheet2",
+            "C2:D11",
+            vec![CTSortCondition {
+                descending: Some(true),
+                sort_by: Some(String::from("cellColor")),
+                dxf_id: Some(0),
+                ..condition("C2:C11")
+            }],
+            &SortOptions::default(),
+        )
+        .unwrap();
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        let styles: Vec<Option<u32>> = (2..=11)
+            .map(|row| {
+                get_row(ws, row)
+                    .unwrap()
+                    .c
+                    .iter()
+                    .find(|c| c.r == format!("C{}", row))
+                    .unwrap()
+                    .s
+            })
+            .collect();
+        assert_eq!(&styles[..4], &[Some(4); 4]);
+        assert_eq!(&styles[4..], &[Some(3); 6]);
+    }
+
+    #[test]
+    fn test_sort_range_moves_annotations() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let value = wb.get_cell_value("Sheet2", 2, 4).unwrap();
+        wb.set_cell_hyperlink("Sheet2", "D2", "Sheet1!A1", "Location", None, None)
+            .unwrap();
+        wb.add_comment(
+            "Sheet2",
+            &Comment {
+                author: String::from("Excelize"),
+                cell: String::from("D2"),
+                text: String::from("Note"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mut dv = DataValidation::new("D2", true);
+        dv.set_range("0", "1000", "whole", "between").unwrap();
+        wb.add_data_validation("Sheet2", &dv).unwrap();
+        wb.sort_range(
+            "Sheet2",
+            "C2:D11",
+            vec![CTSortCondition {
+                ref_attr: String::from("D2:D11"),
+                ..Default::default()
+            }],
+            &SortOptions::default(),
+        )
+        .unwrap();
+        let row = (2..=11)
+            .find(|row| wb.get_cell_value("Sheet2", *row, 4).unwrap() == value)
+            .unwrap();
+        assert_ne!(row, 2);
+        let cell = format!("D{}", row);
+        assert!(wb.get_cell_hyperlink("Sheet2", &cell).unwrap().is_some());
+        assert!(wb.get_cell_hyperlink("Sheet2", "D2").unwrap().is_none());
+        assert_eq!(wb.get_comments("Sheet2").unwrap()[0].cell, cell);
+        // the data validations are kept at their addresses
+        assert_eq!(wb.get_data_validations("Sheet2").unwrap()[0].sqref, "D2");
+
+        // the shared formula without the master cell can't be sorted
+        let ws = wb.get_worksheet_mut("Sheet2").unwrap();
+        let cell = ws.sheet_data.row.as_mut().unwrap()[2]
+            .c
+            .iter_mut()
+            .find(|c| c.r == "D3")
+            .unwrap();
+        cell.f = Some(CTCellFormula {
+            t: Some(String::from("shared")),
+            ref_attr: None,
+            si: Some(9),
+            content: None,
+        });
+        assert!(wb
+            .sort_range(
+                "Sheet2",
+                "C2:D11",
+                vec![CTSortCondition {
+                    ref_attr: String::from("D2:D11"),
+                    ..Default::default()
+                }],
+                &SortOptions::default(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_sort_values() {
+        let text = |s: &str| SortValue::Text(String::from(s));
+        assert_eq!(
+            compare_sort_values(&SortValue::Number(9.0), &text("1"), false),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_sort_values(&text("b"), &text("B"), false),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_sort_values(&text("b"), &text("B"), true),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_sort_values(&text("B"), &text("a"), true),
+            Ordering::Greater
+        );
+        assert_eq!(list_index("Low, Medium, High", &text("high")), Some(2));
+    }
+}
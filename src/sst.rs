@@ -4,7 +4,17 @@
 
 use quick_xml::de::from_str;
 
-use crate::{xml_sst, ExcelizeError, Spreadsheet};
+use crate::{
+    add_content_type_override, get_relative_target, get_rels_path, xml_sst, CTRst, ExcelizeError,
+    Rels, Spreadsheet,
+};
+
+static SOURCE_RELATIONSHIP_SHARED_STRINGS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings";
+static CONTENT_TYPE_SHARED_STRINGS: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml";
+static DEFAULT_SST_PATH: &str = "xl/sharedStrings.xml";
+
 pub trait SST {
     fn get_sst(&mut self) -> Result<(), ExcelizeError>
     where
@@ -18,7 +28,7 @@ impl SST for Spreadsheet {
             Some(_) => Ok(()),
             None => {
                 if let Some(buf) = self.file.get_key_value("xl/sharedStrings.xml") {
-                    let s = match std::str::from_utf8(&buf.1) {
+                    let s = match std::str::from_utf8(buf.1) {
                         Ok(v) => v,
                         Err(e) => {
                             return Err(ExcelizeError::CommonError(e.to_string()));
@@ -38,3 +48,49 @@ impl SST for Spreadsheet {
         }
     }
 }
+
+// set_shared_string provides a function to add the string into the shared
+// strings table and returns the index of the string item, the existing item of
+// the same plain string will be reused. The shared strings part will be
+// created if it doesn't exist.
+pub(crate) fn set_shared_string(spreadsheet: &mut Spreadsheet, value: &str) -> usize {
+    if spreadsheet.sst.is_none()
+        && spreadsheet
+            .get_rels_by_type("xl/workbook.xml", SOURCE_RELATIONSHIP_SHARED_STRINGS)
+            .is_empty()
+    {
+        let target = get_relative_target("xl/workbook.xml", DEFAULT_SST_PATH);
+        spreadsheet.add_rels(
+            &get_rels_path("xl/workbook.xml"),
+            SOURCE_RELATIONSHIP_SHARED_STRINGS,
+            &target,
+            None,
+        );
+        if let Some(ref mut types) = spreadsheet.content_type {
+            add_content_type_override(types, DEFAULT_SST_PATH, CONTENT_TYPE_SHARED_STRINGS);
+        }
+    }
+    let sst = spreadsheet.sst.get_or_insert_with(|| xml_sst::CTSST {
+        count: Some(0),
+        unique_count: Some(0),
+        si: Vec::new(),
+        ext_lst: None,
+    });
+    let index = match sst
+        .si
+        .iter()
+        .position(|si| si.r.is_none() && matches!(si.t.as_deref(), Some([t]) if t == value))
+    {
+        Some(index) => index,
+        None => {
+            sst.si.push(CTRst {
+                t: Some(vec![String::from(value)]),
+                ..Default::default()
+            });
+            sst.unique_count = Some(sst.si.len() as i32);
+            sst.si.len() - 1
+        }
+    };
+    sst.count = Some(sst.count.unwrap_or(0) + 1);
+    index
+}
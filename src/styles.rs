@@ -0,0 +1,124 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use quick_xml::de::from_str;
+
+use crate::{xml_styles, CTColor, ExcelizeError, Spreadsheet};
+pub trait Styles {
+    fn get_styles(&mut self) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    /// new_conditional_style provides a function to create the differential
+    /// format for the conditional formatting by given format settings, and
+    /// returns the index of the format which can be used as the format of
+    /// the conditional formatting rules.
+    fn new_conditional_style(&mut self, dxf: &xml_styles::CTDxf) -> Result<u32, ExcelizeError>;
+}
+
+impl Styles for Spreadsheet {
+    fn get_styles(&mut self) -> Result<(), ExcelizeError> {
+        let styles: xml_styles::XMLStyleSheet;
+        match self.styles {
+            Some(_) => Ok(()),
+            None => {
+                if let Some(buf) = self.file.get_key_value("xl/styles.xml") {
+                    let s = match std::str::from_utf8(buf.1) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            return Err(ExcelizeError::CommonError(e.to_string()));
+                        }
+                    };
+                    match from_str(s) {
+                        Ok(o) => styles = o,
+                        Err(e) => {
+                            return Err(ExcelizeError::CommonError(e.to_string()));
+                        }
+                    }
+                    self.styles.replace(styles);
+                    return Ok(());
+                }
+                Err(ExcelizeError::CommonError(String::from("styles is none")))
+            }
+        }
+    }
+
+    fn new_conditional_style(&mut self, dxf: &xml_styles::CTDxf) -> Result<u32, ExcelizeError> {
+        let styles = self.styles.get_or_insert_with(Default::default);
+        let dxfs = styles.dxfs.get_or_insert_with(Default::default);
+        dxfs.dxf.push(dxf.clone());
+        dxfs.count = Some(dxfs.dxf.len() as u32);
+        Ok(dxfs.dxf.len() as u32 - 1)
+    }
+}
+
+// get_cell_xf provides a function to get the cell format by given style index.
+pub(crate) fn get_cell_xf(
+    styles: &xml_styles::XMLStyleSheet,
+    style: u32,
+) -> Option<&xml_styles::CTXf> {
+    styles
+        .cell_xfs
+        .as_ref()
+        .and_then(|xfs| xfs.xf.get(style as usize))
+}
+
+// get_cell_fill_color provides a function to get the fill color of the cell
+// by given style index, the foreground color is used by the solid fill and
+// the background color is used by the pattern fill.
+pub(crate) fn get_cell_fill_color(
+    styles: &xml_styles::XMLStyleSheet,
+    style: u32,
+) -> Option<&CTColor> {
+    let fill_id = get_cell_xf(styles, style)?.fill_id?;
+    let fill = styles.fills.as_ref()?.fill.get(fill_id as usize)?;
+    get_fill_color(fill)
+}
+
+// get_cell_font_color provides a function to get the font color of the cell
+// by given style index.
+pub(crate) fn get_cell_font_color(
+    styles: &xml_styles::XMLStyleSheet,
+    style: u32,
+) -> Option<&CTColor> {
+    let font_id = get_cell_xf(styles, style)?.font_id?;
+    styles
+        .fonts
+        .as_ref()?
+        .font
+        .get(font_id as usize)?
+        .color
+        .as_ref()
+}
+
+// get_fill_color provides a function to get the color of the fill.
+pub(crate) fn get_fill_color(fill: &xml_styles::CTFill) -> Option<&CTColor> {
+    let pattern_fill = fill.pattern_fill.as_ref()?;
+    match pattern_fill.pattern_type.as_deref() {
+        Some("none") => None,
+        Some("solid") => pattern_fill.fg_color.as_ref(),
+        _ => pattern_fill
+            .bg_color
+            .as_ref()
+            .or(pattern_fill.fg_color.as_ref()),
+    }
+}
+
+// get_dxf provides a function to get the differential format by given index.
+pub(crate) fn get_dxf(
+    styles: &xml_styles::XMLStyleSheet,
+    dxf_id: u32,
+) -> Option<&xml_styles::CTDxf> {
+    styles.dxfs.as_ref()?.dxf.get(dxf_id as usize)
+}
+
+// is_same_color provides a function to check if the two colors are the same,
+// the RGB values are compared case-insensitively.
+pub(crate) fn is_same_color(a: &CTColor, b: &CTColor) -> bool {
+    let rgb = |c: &CTColor| c.rgb.as_ref().map(|rgb| rgb.to_uppercase());
+    rgb(a) == rgb(b)
+        && a.theme == b.theme
+        && a.indexed == b.indexed
+        && a.auto == b.auto
+        && (a.tint.unwrap_or(0.0) - b.tint.unwrap_or(0.0)).abs() < 1e-9
+}
@@ -0,0 +1,686 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use quick_xml::de::from_str;
+use std::collections::HashMap;
+
+use crate::{
+    add_content_type_override, calc::get_cell_formula_value, check_defined_name,
+    column_number_to_name, coordinates_to_cell_name, coordinates_to_range_ref,
+    delete_content_type_override, get_relative_target, get_rels_path, is_overlapped,
+    next_part_index, range_ref_to_coordinates, xml_table, CTAutoFilter, CTFilterColumn,
+    CTTablePart, Cell, ExcelizeError, Rels, Spreadsheet, Worksheet, XMLDefinedName,
+    XMLDefinedNames,
+};
+
+// FILTER_DATABASE defined the built-in defined name of the AutoFilter range.
+pub(crate) static FILTER_DATABASE: &str = "_xlnm._FilterDatabase";
+pub(crate) static SOURCE_RELATIONSHIP_TABLE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/table";
+static CONTENT_TYPE_TABLE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.table+xml";
+static TABLE_STYLES: [(&str, u32); 3] = [
+    ("TableStyleLight", 21),
+    ("TableStyleMedium", 28),
+    ("TableStyleDark", 11),
+];
+
+/// TableOptions directly maps the settings of the table. The table will be
+/// named as TableN if the name is empty, and the style name is one of the
+/// built-in table styles, such as TableStyleMedium2. The header row and the
+/// banded rows are shown by default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableOptions {
+    pub name: String,
+    pub style_name: String,
+    pub show_header_row: Option<bool>,
+    pub show_totals_row: bool,
+    pub show_first_column: bool,
+    pub show_last_column: bool,
+    pub show_row_stripes: Option<bool>,
+    pub show_column_stripes: bool,
+}
+
+pub trait Table {
+    fn tables_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    /// add_table provides a function to add the table in a worksheet by given
+    /// worksheet name, range reference and the table settings. The names of
+    /// the columns are the values of the header cells, and the empty or
+    /// duplicate names will be replaced by the unique names. The table can't
+    /// overlap with the other tables or the merged cells. For example, create
+    /// the table of the range A1:D5 on Sheet1 with the totals row:
+    ///
+    /// ```ignore
+    /// spreadsheet.add_table(
+    ///     "Sheet1",
+    ///     "A1:D5",
+    ///     &TableOptions {
+    ///         name: String::from("Sales"),
+    ///         style_name: String::from("TableStyleMedium2"),
+    ///         show_totals_row: true,
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// ```
+    fn add_table(
+        &mut self,
+        sheet: &str,
+        range_ref: &str,
+        opts: &TableOptions,
+    ) -> Result<(), ExcelizeError>;
+    /// get_tables provides a function to get the range references and the
+    /// settings of the tables in a worksheet by given worksheet name.
+    fn get_tables(&self, sheet: &str) -> Result<Vec<(String, TableOptions)>, ExcelizeError>;
+    /// delete_table provides a function to delete the table by given table
+    /// name, the cell values of the table will be kept.
+    fn delete_table(&mut self, name: &str) -> Result<(), ExcelizeError>;
+    /// read_table provides a function to get the data rows of the table by
+    /// given table name, each row is a record of the cell values keyed by the
+    /// column names, the header row and the totals row are excluded.
+    fn read_table(&self, name: &str) -> Result<Vec<HashMap<String, String>>, ExcelizeError>;
+    /// auto_filter provides a function to set the AutoFilter in a worksheet
+    /// by given worksheet name, range reference and the filter columns. The
+    /// col_id of each filter column is the zero-based index of the column in
+    /// the range, and the hidden built-in defined name _xlnm._FilterDatabase
+    /// will be created for the range. For example, filter the rows in the
+    /// range A1:F500 on Sheet1 which value of the column B greater than 10:
+    ///
+    /// ```ignore
+    /// spreadsheet.auto_filter(
+    ///     "Sheet1",
+    ///     "A1:F500",
+    ///     vec![CTFilterColumn {
+    ///         col_id: 1,
+    ///         custom_filters: Some(CTCustomFilters {
+    ///             and: None,
+    ///             custom_filter: vec![CTCustomFilter {
+    ///                 operator: Some(String::from("greaterThan")),
+    ///                 val: Some(String::from("10")),
+    ///             }],
+    ///         }),
+    ///         ..Default::default()
+    ///     }],
+    /// )
+    /// ```
+    fn auto_filter(
+        &mut self,
+        sheet: &str,
+        range_ref: &str,
+        columns: Vec<CTFilterColumn>,
+    ) -> Result<(), ExcelizeError>;
+}
+
+impl Table for Spreadsheet {
+    fn tables_reader(&mut self, path: &str) -> Result<(), ExcelizeError> {
+        let table: xml_table::XMLTable;
+        match self.tables.get_key_value(path) {
+            Some(_) => Ok(()),
+            None => {
+                if let Some(buf) = self.file.get_key_value(path) {
+                    let s = match std::str::from_utf8(buf.1) {
+                        Ok(v) => v,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    };
+                    match from_str(s) {
+                        Ok(o) => table = o,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    }
+                    self.tables.insert(String::from(path), table);
+                    return Ok(());
+                }
+                Err(ExcelizeError::CommonError(String::from("table is none")))
+            }
+        }
+    }
+
+    fn add_table(
+        &mut self,
+        sheet: &str,
+        range_ref: &str,
+        opts: &TableOptions,
+    ) -> Result<(), ExcelizeError> {
+        let mut coordinates = range_ref_to_coordinates(range_ref)?;
+        let show_header = opts.show_header_row.unwrap_or(true);
+        let min_rows = 1 + u32::from(show_header) + u32::from(opts.show_totals_row);
+        coordinates[3] = coordinates[3].max(coordinates[1] + min_rows - 1);
+        check_table_style(&opts.style_name)?;
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        for (table_ref, table) in self.get_tables(sheet)? {
+            if is_overlapped(&coordinates, &range_ref_to_coordinates(&table_ref)?) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "the range {} overlaps with the table {}",
+                    range_ref, table.name
+                )));
+            }
+        }
+        let ws = self.get_worksheet(sheet)?;
+        for merge_cell in ws.merge_cells.iter().flat_map(|m| m.merge_cell.iter()) {
+            if is_overlapped(
+                &coordinates,
+                &range_ref_to_coordinates(&merge_cell.ref_attr)?,
+            ) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "the range {} overlaps with the merged cells {}",
+                    range_ref, merge_cell.ref_attr
+                )));
+            }
+        }
+        let id = self.tables.values().map(|t| t.id).max().unwrap_or(0) + 1;
+        let name = match opts.name.as_str() {
+            "" => format!("Table{}", id),
+            name if name.starts_with("_xlnm.") => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "invalid table name {:?}",
+                    name
+                )))
+            }
+            name => {
+                check_defined_name(name)?;
+                String::from(name)
+            }
+        };
+        let defined_names = self
+            .workbook
+            .iter()
+            .flat_map(|wb| wb.defined_names.iter())
+            .flat_map(|d| d.defined_name.iter())
+            .map(|d| &d.name);
+        if self
+            .tables
+            .values()
+            .map(|t| &t.display_name)
+            .chain(defined_names)
+            .any(|n| n.eq_ignore_ascii_case(&name))
+        {
+            return Err(ExcelizeError::CommonError(format!(
+                "the same name {} already exists",
+                name
+            )));
+        }
+
+        let mut columns: Vec<xml_table::CTTableColumn> = Vec::new();
+        let mut headers = Vec::new();
+        for (i, col) in (coordinates[0]..=coordinates[2]).enumerate() {
+            let text = match show_header {
+                true => get_cell_formula_value(self, ws, col, coordinates[1])
+                    .to_text()
+                    .unwrap_or_default(),
+                false => String::new(),
+            };
+            let header = match text.trim() {
+                "" => format!("Column{}", i + 1),
+                header => String::from(header),
+            };
+            let mut column_name = header.clone();
+            let mut suffix = 2;
+            while columns
+                .iter()
+                .any(|c| c.name.eq_ignore_ascii_case(&column_name))
+            {
+                column_name = format!("{}{}", header, suffix);
+                suffix += 1;
+            }
+            if show_header && column_name != text.trim() {
+                headers.push((col, column_name.clone()));
+            }
+            columns.push(xml_table::CTTableColumn {
+                id: i as u32 + 1,
+                name: column_name,
+                ..Default::default()
+            });
+        }
+        if opts.show_totals_row {
+            columns[0].totals_row_label = Some(String::from("Total"));
+            if let Some(column) = columns.iter_mut().skip(1).last() {
+                column.totals_row_function = Some(String::from("sum"));
+            }
+        }
+        let mut filter_coordinates = coordinates;
+        filter_coordinates[3] -= u32::from(opts.show_totals_row);
+        let table = xml_table::XMLTable {
+            id,
+            name: Some(name.clone()),
+            display_name: name,
+            ref_attr: coordinates_to_range_ref(&coordinates)?,
+            header_row_count: (!show_header).then_some(0),
+            totals_row_count: opts.show_totals_row.then_some(1),
+            totals_row_shown: Some(opts.show_totals_row),
+            auto_filter: match show_header {
+                true => Some(CTAutoFilter {
+                    ref_attr: Some(coordinates_to_range_ref(&filter_coordinates)?),
+                    ..Default::default()
+                }),
+                false => None,
+            },
+            table_columns: xml_table::CTTableColumns {
+                count: Some(columns.len() as u32),
+                table_column: columns,
+            },
+            table_style_info: Some(xml_table::CTTableStyleInfo {
+                name: (!opts.style_name.is_empty()).then(|| opts.style_name.clone()),
+                show_first_column: Some(opts.show_first_column),
+                show_last_column: Some(opts.show_last_column),
+                show_row_stripes: Some(opts.show_row_stripes.unwrap_or(true)),
+                show_column_stripes: Some(opts.show_column_stripes),
+            }),
+        };
+
+        let path = format!(
+            "xl/tables/table{}.xml",
+            next_part_index(self, "xl/tables/table", ".xml")
+        );
+        let rid = self.add_rels(
+            &get_rels_path(&sheet_path),
+            SOURCE_RELATIONSHIP_TABLE,
+            &get_relative_target(&sheet_path, &path),
+            None,
+        );
+        if let Some(ref mut types) = self.content_type {
+            add_content_type_override(types, &path, CONTENT_TYPE_TABLE);
+        }
+        let table_parts = self
+            .get_worksheet_mut(sheet)?
+            .table_parts
+            .get_or_insert_with(Default::default);
+        table_parts.table_part.push(CTTablePart { rid });
+        table_parts.count = Some(table_parts.table_part.len() as u32);
+        self.tables.insert(path, table);
+        for (col, header) in headers {
+            let cell = coordinates_to_cell_name(col, coordinates[1])?;
+            self.set_cell_str(sheet, &cell, &header)?;
+        }
+        Ok(())
+    }
+
+    fn get_tables(&self, sheet: &str) -> Result<Vec<(String, TableOptions)>, ExcelizeError> {
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        Ok(get_sheet_table_paths(self, &sheet_path)
+            .iter()
+            .filter_map(|path| self.tables.get(path))
+            .map(|table| {
+                let style = table.table_style_info.as_ref();
+                let flag = |f: fn(&xml_table::CTTableStyleInfo) -> Option<bool>| {
+                    style.and_then(f).unwrap_or(false)
+                };
+                (
+                    table.ref_attr.clone(),
+                    TableOptions {
+                        name: table.display_name.clone(),
+                        style_name: style.and_then(|s| s.name.clone()).unwrap_or_default(),
+                        show_header_row: Some(table.header_row_count != Some(0)),
+                        show_totals_row: table.totals_row_count.unwrap_or(0) > 0,
+                        show_first_column: flag(|s| s.show_first_column),
+                        show_last_column: flag(|s| s.show_last_column),
+                        show_row_stripes: Some(flag(|s| s.show_row_stripes)),
+                        show_column_stripes: flag(|s| s.show_column_stripes),
+                    },
+                )
+            })
+            .collect())
+    }
+
+    fn delete_table(&mut self, name: &str) -> Result<(), ExcelizeError> {
+        let (sheet, rid, path) = find_table(self, name)?;
+        let sheet_path = self.get_sheet_xml_path(&sheet)?;
+        self.delete_rels(&get_rels_path(&sheet_path), &rid);
+        let ws = self.get_worksheet_mut(&sheet)?;
+        if let Some(ref mut table_parts) = ws.table_parts {
+            table_parts.table_part.retain(|part| part.rid != rid);
+            table_parts.count = Some(table_parts.table_part.len() as u32);
+            if table_parts.table_part.is_empty() {
+                ws.table_parts = None;
+            }
+        }
+        if let Some(ref mut types) = self.content_type {
+            delete_content_type_override(types, &path);
+        }
+        self.tables.remove(&path);
+        self.file.remove(&path);
+        Ok(())
+    }
+
+    fn read_table(&self, name: &str) -> Result<Vec<HashMap<String, String>>, ExcelizeError> {
+        let (sheet, _, path) = find_table(self, name)?;
+        let table = &self.tables[&path];
+        let coordinates = range_ref_to_coordinates(&table.ref_attr)?;
+        let first_row = coordinates[1] + u32::from(table.header_row_count != Some(0));
+        let last_row = coordinates[3].saturating_sub(table.totals_row_count.unwrap_or(0));
+        let mut records = Vec::new();
+        for row in first_row..=last_row {
+            let mut record = HashMap::new();
+            for (col, column) in
+                (coordinates[0]..=coordinates[2]).zip(&table.table_columns.table_column)
+            {
+                record.insert(column.name.clone(), self.get_cell_value(&sheet, row, col)?);
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    fn auto_filter(
+        &mut self,
+        sheet: &str,
+        range_ref: &str,
+        mut columns: Vec<CTFilterColumn>,
+    ) -> Result<(), ExcelizeError> {
+        let coordinates = range_ref_to_coordinates(range_ref)?;
+        let width = coordinates[2] - coordinates[0] + 1;
+        for (i, column) in columns.iter().enumerate() {
+            if column.col_id >= width {
+                return Err(ExcelizeError::CommonError(format!(
+                    "incorrect index of column {} in the range {}",
+                    column.col_id, range_ref
+                )));
+            }
+            if columns[..i].iter().any(|c| c.col_id == column.col_id) {
+                return Err(ExcelizeError::CommonError(format!(
+                    "duplicate filter column {}",
+                    column.col_id
+                )));
+            }
+        }
+        columns.sort_by_key(|c| c.col_id);
+        let index = self.get_sheet_index(sheet)? as u32;
+        let ws = self.get_worksheet_mut(sheet)?;
+        let filter_mode = !columns.is_empty();
+        ws.auto_filter = Some(CTAutoFilter {
+            ref_attr: Some(coordinates_to_range_ref(&coordinates)?),
+            filter_column: columns,
+            sort_state: None,
+        });
+        if filter_mode {
+            ws.sheet_pr.get_or_insert_with(Default::default).filter_mode = Some(true);
+        } else if let Some(ref mut sheet_pr) = ws.sheet_pr {
+            sheet_pr.filter_mode = None;
+        }
+
+        let data = format!(
+            "'{}'!{}",
+            sheet.replace('\'', "''"),
+            absolute_range_ref(&coordinates)?
+        );
+        if let Some(ref mut wb) = self.workbook {
+            let defined_names = wb.defined_names.get_or_insert_with(|| XMLDefinedNames {
+                defined_name: Vec::new(),
+            });
+            match defined_names
+                .defined_name
+                .iter_mut()
+                .find(|d| d.name == FILTER_DATABASE && d.local_sheet_id == Some(index))
+            {
+                Some(defined_name) => defined_name.data = data,
+                None => defined_names.defined_name.push(XMLDefinedName {
+                    name: String::from(FILTER_DATABASE),
+                    comment: None,
+                    local_sheet_id: Some(index),
+                    hidden: Some(true),
+                    data,
+                }),
+            }
+        }
+        Ok(())
+    }
+}
+
+// absolute_range_ref provides a function to convert the coordinates to the
+// absolute range reference, for example [1, 1, 6, 500] to $A$1:$F$500.
+pub(crate) fn absolute_range_ref(coordinates: &[u32; 4]) -> Result<String, ExcelizeError> {
+    Ok(format!(
+        "${}${}:${}${}",
+        column_number_to_name(coordinates[0])?,
+        coordinates[1],
+        column_number_to_name(coordinates[2])?,
+        coordinates[3]
+    ))
+}
+
+// get_sheet_table_paths provides a function to get the paths of the table
+// parts of the worksheet by given worksheet part path.
+pub(crate) fn get_sheet_table_paths(spreadsheet: &Spreadsheet, sheet_path: &str) -> Vec<String> {
+    spreadsheet
+        .get_rels_by_type(sheet_path, SOURCE_RELATIONSHIP_TABLE)
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect()
+}
+
+// find_table provides a function to get the worksheet name, the relationship
+// ID and the part path of the table by given table name.
+fn find_table(
+    spreadsheet: &Spreadsheet,
+    name: &str,
+) -> Result<(String, String, String), ExcelizeError> {
+    for sheet in spreadsheet
+        .workbook
+        .iter()
+        .flat_map(|wb| wb.sheets.sheet.iter())
+    {
+        let sheet_path = spreadsheet.get_sheet_xml_path(&sheet.name)?;
+        if let Some((rid, path)) = spreadsheet
+            .get_rels_by_type(&sheet_path, SOURCE_RELATIONSHIP_TABLE)
+            .into_iter()
+            .find(|(_, path)| {
+                spreadsheet
+                    .tables
+                    .get(path)
+                    .map_or(false, |t| t.display_name.eq_ignore_ascii_case(name))
+            })
+        {
+            return Ok((sheet.name.clone(), rid, path));
+        }
+    }
+    Err(ExcelizeError::CommonError(format!(
+        "table {} is not exist",
+        name
+    )))
+}
+
+// check_table_style provides a function to check if the style name is one of
+// the built-in table styles, the empty style name means no style.
+fn check_table_style(style_name: &str) -> Result<(), ExcelizeError> {
+    if style_name.is_empty()
+        || TABLE_STYLES.iter().any(|(prefix, count)| {
+            style_name
+                .strip_prefix(prefix)
+                .and_then(|n| n.parse::<u32>().ok())
+                .map_or(false, |n| (1..=*count).contains(&n))
+        })
+    {
+        return Ok(());
+    }
+    Err(ExcelizeError::CommonError(format!(
+        "invalid table style name {}",
+        style_name
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CTFilter, CTFilters, Col, Rows};
+
+    #[test]
+    fn test_auto_filter() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let column = |col_id: u32| CTFilterColumn {
+            col_id,
+            filters: Some(CTFilters {
+                blank: None,
+                calendar_type: None,
+                filter: vec![CTFilter {
+                    val: Some(String::from("1")),
+                }],
+                date_group_item: Vec::new(),
+            }),
+            ..Default::default()
+        };
+        assert!(wb.auto_filter("Sheet1", "A1:C10", vec![column(3)]).is_err());
+        assert!(wb
+            .auto_filter("Sheet1", "A1:C10", vec![column(1), column(1)])
+            .is_err());
+        wb.auto_filter("Sheet1", "C10:A1", vec![column(2), column(0)])
+            .unwrap();
+        let ws = wb.get_worksheet("Sheet1").unwrap();
+        let auto_filter = ws.auto_filter.as_ref().unwrap();
+        assert_eq!(auto_filter.ref_attr.as_deref(), Some("A1:C10"));
+        assert_eq!(auto_filter.filter_column[0].col_id, 0);
+        assert_eq!(ws.sheet_pr.as_ref().unwrap().filter_mode, Some(true));
+
+        wb.auto_filter("Sheet1", "A1:D20", Vec::new()).unwrap();
+        let ws = wb.get_worksheet("Sheet1").unwrap();
+        assert_eq!(ws.sheet_pr.as_ref().unwrap().filter_mode, None);
+        let defined_names = &wb.workbook.as_ref().unwrap().defined_names;
+        let filter_database: Vec<&XMLDefinedName> = defined_names
+            .iter()
+            .flat_map(|d| d.defined_name.iter())
+            .filter(|d| d.name == FILTER_DATABASE)
+            .collect();
+        assert_eq!(filter_database.len(), 1);
+        assert_eq!(filter_database[0].data, "'Sheet1'!$A$1:$D$20");
+        assert_eq!(filter_database[0].hidden, Some(true));
+
+        let sheet_pr = wb.get_worksheet("Sheet2").unwrap().sheet_pr.is_some();
+        wb.auto_filter("Sheet2", "C1:D11", Vec::new()).unwrap();
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        assert_eq!(ws.sheet_pr.is_some(), sheet_pr);
+    }
+
+    #[test]
+    fn test_add_table() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let tables = wb.get_tables("Sheet1").unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].0, "C21:D26");
+        assert_eq!(tables[0].1.name, "Table1");
+        assert_eq!(tables[0].1.style_name, "TableStyleMedium9");
+        assert_eq!(tables[0].1.show_row_stripes, Some(true));
+
+        let opts = |name: &str| TableOptions {
+            name: String::from(name),
+            style_name: String::from("TableStyleLight9"),
+            show_totals_row: true,
+            ..Default::default()
+        };
+        assert!(wb.add_table("Sheet2", "A1:D11", &opts("Sales")).is_err());
+        assert!(wb.add_table("Sheet1", "A20:C21", &opts("Sales")).is_err());
+        assert!(wb.add_table("Sheet2", "B1:D11", &opts("table1")).is_err());
+        assert!(wb.add_table("Sheet2", "B1:D11", &opts("1st")).is_err());
+        assert!(wb
+            .add_table(
+                "Sheet2",
+                "B1:D11",
+                &TableOptions {
+                    style_name: String::from("TableStyleLight22"),
+                    ..opts("Sales")
+                }
+            )
+            .is_err());
+        wb.add_table("Sheet2", "D11:B1", &opts("Sales")).unwrap();
+        assert!(wb.add_table("Sheet2", "C5:E12", &opts("Other")).is_err());
+        assert!(wb.add_table("Sheet2", "F1:G3", &opts("sales")).is_err());
+        wb.add_table(
+            "Sheet2",
+            "F1:G1",
+            &TableOptions {
+                show_header_row: Some(false),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let table = &wb.tables["xl/tables/table2.xml"];
+        assert_eq!(table.id, 2);
+        assert_eq!(table.ref_attr, "B1:D11");
+        assert_eq!(table.totals_row_count, Some(1));
+        assert_eq!(
+            table.auto_filter.as_ref().unwrap().ref_attr.as_deref(),
+            Some("B1:D10")
+        );
+        let columns: Vec<&str> = table
+            .table_columns
+            .table_column
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(columns, vec!["Column1", "Brand", "Column3"]);
+        assert_eq!(wb.get_cell_value("Sheet2", 1, 2).unwrap(), "Column1");
+        assert_eq!(wb.get_cell_value("Sheet2", 1, 4).unwrap(), "Column3");
+        assert_eq!(
+            table.table_columns.table_column[2]
+                .totals_row_function
+                .as_deref(),
+            Some("sum")
+        );
+        let tables = wb.get_tables("Sheet2").unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[1].0, "F1:G1");
+        assert_eq!(tables[1].1.name, "Table3");
+        assert_eq!(tables[1].1.show_header_row, Some(false));
+        assert!(wb.tables["xl/tables/table3.xml"].auto_filter.is_none());
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        assert_eq!(ws.table_parts.as_ref().unwrap().count, Some(2));
+
+        wb.delete_table("SALES").unwrap();
+        assert!(wb.delete_table("Sales").is_err());
+        assert!(!wb.tables.contains_key("xl/tables/table2.xml"));
+        assert_eq!(wb.get_tables("Sheet2").unwrap().len(), 1);
+        let ws = wb.get_worksheet("Sheet2").unwrap();
+        assert_eq!(ws.table_parts.as_ref().unwrap().count, Some(1));
+    }
+
+    #[test]
+    fn test_read_table() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let records = wb.read_table("table1").unwrap();
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[0]["Column2"], "");
+        wb.add_table(
+            "Sheet2",
+            "B1:D11",
+            &TableOptions {
+                name: String::from("Sales"),
+                show_totals_row: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let records = wb.read_table("Sales").unwrap();
+        assert_eq!(records.len(), 9);
+        assert_eq!(records[0]["Column1"], "19");
+        assert_eq!(records[0]["Brand"], "HP");
+        assert_eq!(records[0]["Column3"], "200");
+        assert_eq!(records[8]["Column1"], "");
+        assert_eq!(records[8]["Brand"], "SAMSUNG");
+        assert_eq!(records[8].len(), 3);
+        assert!(wb.read_table("Other").is_err());
+
+        wb.insert_rows("Sheet2", 1, 2).unwrap();
+        wb.insert_cols("Sheet2", "C", 1).unwrap();
+        let table = &wb.tables["xl/tables/table2.xml"];
+        assert_eq!(table.ref_attr, "B3:E13");
+        assert_eq!(
+            table.auto_filter.as_ref().unwrap().ref_attr.as_deref(),
+            Some("B3:E12")
+        );
+        let records = wb.read_table("Sales").unwrap();
+        assert_eq!(records[0]["Column2"], "");
+        assert_eq!(records[0]["Brand"], "HP");
+        assert_eq!(wb.get_cell_value("Sheet2", 3, 3).unwrap(), "Column2");
+        wb.remove_col("Sheet2", "C").unwrap();
+        wb.remove_row("Sheet2", 13).unwrap();
+        let table = &wb.tables["xl/tables/table2.xml"];
+        assert_eq!(table.ref_attr, "B3:D12");
+        assert_eq!(table.table_columns.count, Some(3));
+        assert_eq!(table.totals_row_count, None);
+        let records = wb.read_table("Sales").unwrap();
+        assert_eq!(records.len(), 9);
+        assert_eq!(records[0]["Column3"], "200");
+        wb.remove_row("Sheet2", 3).unwrap();
+        assert!(wb.read_table("Sales").is_err());
+        assert!(wb.get_tables("Sheet2").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,560 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use quick_xml::de::from_str;
+use rand::RngCore;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    add_content_type_override, cell_name_to_coordinates, civil_from_days, coordinates_to_cell_name,
+    get_relative_target, get_rels_path, next_part_index, xml_threaded_comments, Comment, Comments,
+    ExcelizeError, Rels, Spreadsheet, Worksheet, TOTAL_CELL_CHARS,
+};
+
+pub(crate) static SOURCE_RELATIONSHIP_THREADED_COMMENT: &str =
+    "http://schemas.microsoft.com/office/2017/10/relationships/threadedComment";
+pub(crate) static SOURCE_RELATIONSHIP_PERSON: &str =
+    "http://schemas.microsoft.com/office/2017/10/relationships/person";
+static CONTENT_TYPE_THREADED_COMMENTS: &str = "application/vnd.ms-excel.threadedcomments+xml";
+static CONTENT_TYPE_PERSONS: &str = "application/vnd.ms-excel.person+xml";
+static DEFAULT_PERSONS_PATH: &str = "xl/persons/person.xml";
+static THREADED_COMMENT_NOTE_HEADER: &str = "[Threaded comment]\n\nYour version of Excel allows you to read this threaded comment; however, any edits to it will get removed if the file is opened in a newer version of Excel. Learn more: https://go.microsoft.com/fwlink/?linkid=870924\n\nComment:\n    ";
+
+/// ThreadedComment directly maps the settings of a threaded comment. The top
+/// comment of the thread holds the replies, and the done indicates whether
+/// the thread has been resolved.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThreadedComment {
+    pub id: String,
+    pub cell: String,
+    pub author: String,
+    pub date_time: Option<String>,
+    pub text: String,
+    pub done: bool,
+    pub replies: Vec<ThreadedComment>,
+}
+
+pub trait ThreadedComments {
+    fn threaded_comments_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    fn persons_reader(&mut self, path: &str) -> Result<(), ExcelizeError>
+    where
+        Self: std::marker::Sized;
+    /// add_threaded_comment provides a function to start a threaded comment
+    /// on the cell by given worksheet name, cell reference, author and text.
+    /// The legacy note which is shown by the versions of Excel that don't
+    /// support the threaded comments will be created as well. Returns the ID
+    /// of the thread. For example, start a thread in Sheet1!A1:
+    ///
+    /// ```ignore
+    /// let id = spreadsheet.add_threaded_comment("Sheet1", "A1", "Excelize", "Hello")?;
+    /// ```
+    fn add_threaded_comment(
+        &mut self,
+        sheet: &str,
+        cell: &str,
+        author: &str,
+        text: &str,
+    ) -> Result<String, ExcelizeError>;
+    /// add_threaded_comment_reply provides a function to reply to the
+    /// threaded comment by given worksheet name, ID of the thread, author and
+    /// text. The legacy note of the thread will be updated. Returns the ID of
+    /// the reply.
+    fn add_threaded_comment_reply(
+        &mut self,
+        sheet: &str,
+        id: &str,
+        author: &str,
+        text: &str,
+    ) -> Result<String, ExcelizeError>;
+    /// get_threaded_comments provides a function to get the threaded comments
+    /// with the replies of the worksheet by given worksheet name.
+    fn get_threaded_comments(&self, sheet: &str) -> Result<Vec<ThreadedComment>, ExcelizeError>;
+    /// resolve_threaded_comment provides a function to set the resolved state
+    /// of the thread by given worksheet name and ID of the thread.
+    fn resolve_threaded_comment(
+        &mut self,
+        sheet: &str,
+        id: &str,
+        done: bool,
+    ) -> Result<(), ExcelizeError>;
+    /// delete_threaded_comment provides a function to delete the thread with
+    /// the replies and the legacy note of the cell by given worksheet name
+    /// and cell reference.
+    fn delete_threaded_comment(&mut self, sheet: &str, cell: &str) -> Result<(), ExcelizeError>;
+}
+
+impl ThreadedComments for Spreadsheet {
+    fn threaded_comments_reader(&mut self, path: &str) -> Result<(), ExcelizeError> {
+        let comments: xml_threaded_comments::XMLThreadedComments;
+        match self.threaded_comments.get_key_value(path) {
+            Some(_) => Ok(()),
+            None => {
+                if let Some(buf) = self.file.get_key_value(path) {
+                    let s = match std::str::from_utf8(buf.1) {
+                        Ok(v) => v,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    };
+                    match from_str(s) {
+                        Ok(o) => comments = o,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    }
+                    self.threaded_comments.insert(String::from(path), comments);
+                    return Ok(());
+                }
+                Err(ExcelizeError::CommonError(String::from(
+                    "threaded comments is none",
+                )))
+            }
+        }
+    }
+
+    fn persons_reader(&mut self, path: &str) -> Result<(), ExcelizeError> {
+        let persons: xml_threaded_comments::XMLPersonList;
+        match self.persons {
+            Some(_) => Ok(()),
+            None => {
+                if let Some(buf) = self.file.get_key_value(path) {
+                    let s = match std::str::from_utf8(buf.1) {
+                        Ok(v) => v,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    };
+                    match from_str(s) {
+                        Ok(o) => persons = o,
+                        Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
+                    }
+                    self.persons.replace(persons);
+                    return Ok(());
+                }
+                Err(ExcelizeError::CommonError(String::from("persons is none")))
+            }
+        }
+    }
+
+    fn add_threaded_comment(
+        &mut self,
+        sheet: &str,
+        cell: &str,
+        author: &str,
+        text: &str,
+    ) -> Result<String, ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        let cell = coordinates_to_cell_name(col, row)?;
+        check_threaded_comment_text(text)?;
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        if self.get_comments(sheet)?.iter().any(|c| c.cell == cell) {
+            return Err(ExcelizeError::CommonError(format!(
+                "the cell {} already has a comment",
+                cell
+            )));
+        }
+        let person_id = prepare_person(self, author);
+        let path = match get_sheet_threaded_comments_path(self, &sheet_path) {
+            Some(path) => path,
+            None => {
+                let path = format!(
+                    "xl/threadedComments/threadedComment{}.xml",
+                    next_part_index(self, "xl/threadedComments/threadedComment", ".xml")
+                );
+                let target = get_relative_target(&sheet_path, &path);
+                self.add_rels(
+                    &get_rels_path(&sheet_path),
+                    SOURCE_RELATIONSHIP_THREADED_COMMENT,
+                    &target,
+                    None,
+                );
+                if let Some(ref mut types) = self.content_type {
+                    add_content_type_override(types, &path, CONTENT_TYPE_THREADED_COMMENTS);
+                }
+                path
+            }
+        };
+        self.threaded_comments_reader(&path).ok();
+        let id = new_guid();
+        self.threaded_comments
+            .entry(path)
+            .or_default()
+            .threaded_comment
+            .push(xml_threaded_comments::XMLThreadedComment {
+                ref_attr: cell.clone(),
+                dt: Some(now_date_time()),
+                person_id,
+                id: id.clone(),
+                ..Default::default()
+            });
+        set_threaded_comment_text(self, &sheet_path, &id, text);
+        update_threaded_comment_note(self, sheet, &cell)?;
+        Ok(id)
+    }
+
+    fn add_threaded_comment_reply(
+        &mut self,
+        sheet: &str,
+        id: &str,
+        author: &str,
+        text: &str,
+    ) -> Result<String, ExcelizeError> {
+        check_threaded_comment_text(text)?;
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        let cell = match get_sheet_threaded_comments_path(self, &sheet_path)
+            .and_then(|path| self.threaded_comments.get(&path))
+            .and_then(|comments| {
+                comments
+                    .threaded_comment
+                    .iter()
+                    .find(|c| c.id == id && c.parent_id.is_none())
+            }) {
+            Some(thread) => thread.ref_attr.clone(),
+            None => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "threaded comment {} does not exist",
+                    id
+                )))
+            }
+        };
+        let person_id = prepare_person(self, author);
+        let reply_id = new_guid();
+        if let Some(comments) = get_sheet_threaded_comments_path(self, &sheet_path)
+            .and_then(|path| self.threaded_comments.get_mut(&path))
+        {
+            comments
+                .threaded_comment
+                .push(xml_threaded_comments::XMLThreadedComment {
+                    ref_attr: cell.clone(),
+                    dt: Some(now_date_time()),
+                    person_id,
+                    id: reply_id.clone(),
+                    parent_id: Some(String::from(id)),
+                    ..Default::default()
+                });
+        }
+        set_threaded_comment_text(self, &sheet_path, &reply_id, text);
+        update_threaded_comment_note(self, sheet, &cell)?;
+        Ok(reply_id)
+    }
+
+    fn get_threaded_comments(&self, sheet: &str) -> Result<Vec<ThreadedComment>, ExcelizeError> {
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        let comments = match get_sheet_threaded_comments_path(self, &sheet_path)
+            .and_then(|path| self.threaded_comments.get(&path))
+        {
+            Some(comments) => comments,
+            None => return Ok(Vec::new()),
+        };
+        let to_threaded_comment = |c: &xml_threaded_comments::XMLThreadedComment| ThreadedComment {
+            id: c.id.clone(),
+            cell: c.ref_attr.clone(),
+            author: self
+                .persons
+                .as_ref()
+                .and_then(|persons| persons.person.iter().find(|p| p.id == c.person_id))
+                .map(|p| p.display_name.clone())
+                .unwrap_or_default(),
+            date_time: c.dt.clone(),
+            text: c.text.clone(),
+            done: c.done.unwrap_or(false),
+            replies: Vec::new(),
+        };
+        Ok(comments
+            .threaded_comment
+            .iter()
+            .filter(|c| c.parent_id.is_none())
+            .map(|thread| {
+                let mut threaded_comment = to_threaded_comment(thread);
+                threaded_comment.replies = comments
+                    .threaded_comment
+                    .iter()
+                    .filter(|c| c.parent_id.as_deref() == Some(thread.id.as_str()))
+                    .map(to_threaded_comment)
+                    .collect();
+                threaded_comment
+            })
+            .collect())
+    }
+
+    fn resolve_threaded_comment(
+        &mut self,
+        sheet: &str,
+        id: &str,
+        done: bool,
+    ) -> Result<(), ExcelizeError> {
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        match get_sheet_threaded_comments_path(self, &sheet_path)
+            .and_then(|path| self.threaded_comments.get_mut(&path))
+            .and_then(|comments| {
+                comments
+                    .threaded_comment
+                    .iter_mut()
+                    .find(|c| c.id == id && c.parent_id.is_none())
+            }) {
+            Some(thread) => {
+                thread.done = done.then_some(true);
+                Ok(())
+            }
+            None => Err(ExcelizeError::CommonError(format!(
+                "threaded comment {} does not exist",
+                id
+            ))),
+        }
+    }
+
+    fn delete_threaded_comment(&mut self, sheet: &str, cell: &str) -> Result<(), ExcelizeError> {
+        let (col, row) = cell_name_to_coordinates(cell)?;
+        let cell = coordinates_to_cell_name(col, row)?;
+        let sheet_path = self.get_sheet_xml_path(sheet)?;
+        let comments = get_sheet_threaded_comments_path(self, &sheet_path)
+            .and_then(|path| self.threaded_comments.get_mut(&path));
+        let deleted = comments.map_or(false, |comments| {
+            let count = comments.threaded_comment.len();
+            comments.threaded_comment.retain(|c| c.ref_attr != cell);
+            comments.threaded_comment.len() != count
+        });
+        if !deleted {
+            return Err(ExcelizeError::CommonError(format!(
+                "the cell {} doesn't have a threaded comment",
+                cell
+            )));
+        }
+        self.delete_comment(sheet, &cell).ok();
+        Ok(())
+    }
+}
+
+// get_sheet_threaded_comments_path provides a function to get the path of the
+// threaded comments part of the worksheet by given worksheet part path.
+pub(crate) fn get_sheet_threaded_comments_path(
+    spreadsheet: &Spreadsheet,
+    sheet_path: &str,
+) -> Option<String> {
+    spreadsheet
+        .get_rels_by_type(sheet_path, SOURCE_RELATIONSHIP_THREADED_COMMENT)
+        .into_iter()
+        .next()
+        .map(|(_, path)| path)
+}
+
+// get_persons_path provides a function to get the path of the persons part of
+// the workbook.
+pub(crate) fn get_persons_path(spreadsheet: &Spreadsheet) -> Option<String> {
+    spreadsheet
+        .get_rels_by_type("xl/workbook.xml", SOURCE_RELATIONSHIP_PERSON)
+        .into_iter()
+        .next()
+        .map(|(_, path)| path)
+}
+
+// check_threaded_comment_text provides a function to check the length of the
+// threaded comment text.
+fn check_threaded_comment_text(text: &str) -> Result<(), ExcelizeError> {
+    if text.chars().count() > TOTAL_CELL_CHARS {
+        return Err(ExcelizeError::CommonError(format!(
+            "comment text length exceeds the limit {}",
+            TOTAL_CELL_CHARS
+        )));
+    }
+    Ok(())
+}
+
+// set_threaded_comment_text provides a function to set the text of the
+// threaded comment by given worksheet part path and ID of the comment.
+fn set_threaded_comment_text(
+    spreadsheet: &mut Spreadsheet,
+    sheet_path: &str,
+    id: &str,
+    text: &str,
+) {
+    if let Some(comment) = get_sheet_threaded_comments_path(spreadsheet, sheet_path)
+        .and_then(|path| spreadsheet.threaded_comments.get_mut(&path))
+        .and_then(|comments| comments.threaded_comment.iter_mut().find(|c| c.id == id))
+    {
+        comment.text = String::from(text);
+    }
+}
+
+// prepare_person provides a function to get the person ID of the author, the
+// person will be added into the persons part, which will be created if it
+// doesn't exist.
+fn prepare_person(spreadsheet: &mut Spreadsheet, author: &str) -> String {
+    if spreadsheet.persons.is_none() {
+        let path = match get_persons_path(spreadsheet) {
+            Some(path) => path,
+            None => {
+                let target = get_relative_target("xl/workbook.xml", DEFAULT_PERSONS_PATH);
+                spreadsheet.add_rels(
+                    &get_rels_path("xl/workbook.xml"),
+                    SOURCE_RELATIONSHIP_PERSON,
+                    &target,
+                    None,
+                );
+                if let Some(ref mut types) = spreadsheet.content_type {
+                    add_content_type_override(types, DEFAULT_PERSONS_PATH, CONTENT_TYPE_PERSONS);
+                }
+                String::from(DEFAULT_PERSONS_PATH)
+            }
+        };
+        if spreadsheet.persons_reader(&path).is_err() {
+            spreadsheet.persons = Some(xml_threaded_comments::XMLPersonList::default());
+        }
+    }
+    let persons = spreadsheet.persons.get_or_insert_with(Default::default);
+    if let Some(person) = persons.person.iter().find(|p| p.display_name == author) {
+        return person.id.clone();
+    }
+    let id = new_guid();
+    persons.person.push(xml_threaded_comments::XMLPerson {
+        display_name: String::from(author),
+        id: id.clone(),
+        user_id: Some(String::from(author)),
+        provider_id: Some(String::from("None")),
+    });
+    id
+}
+
+// update_threaded_comment_note provides a function to rebuild the legacy note
+// of the thread on the cell, which is shown by the versions of Excel that
+// don't support the threaded comments.
+fn update_threaded_comment_note(
+    spreadsheet: &mut Spreadsheet,
+    sheet: &str,
+    cell: &str,
+) -> Result<(), ExcelizeError> {
+    let thread = match spreadsheet
+        .get_threaded_comments(sheet)?
+        .into_iter()
+        .find(|c| c.cell == cell)
+    {
+        Some(thread) => thread,
+        None => return Ok(()),
+    };
+    let mut text = format!("{}{}", THREADED_COMMENT_NOTE_HEADER, thread.text);
+    for reply in &thread.replies {
+        text.push_str("\nReply:\n    ");
+        text.push_str(&reply.text);
+    }
+    spreadsheet.delete_comment(sheet, cell).ok();
+    spreadsheet.add_comment(
+        sheet,
+        &Comment {
+            author: format!("tc={}", thread.id),
+            cell: String::from(cell),
+            text: text.chars().take(TOTAL_CELL_CHARS).collect(),
+            ..Default::default()
+        },
+    )
+}
+
+// new_guid provides a function to generate a random GUID in the registry
+// format, for example {3F2504E0-4F89-41D3-9A0C-0305E82C3301}.
+pub(crate) fn new_guid() -> String {
+    let mut b = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut b);
+    b[6] = (b[6] & 0x0f) | 0x40;
+    b[8] = (b[8] & 0x3f) | 0x80;
+    let hex: String = b.iter().map(|v| format!("{:02X}", v)).collect();
+    format!(
+        "{{{}-{}-{}-{}-{}}}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+// now_date_time provides a function to get the current UTC date time in the
+// format of the threaded comment timestamp.
+fn now_date_time() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs()) as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    let secs = secs.rem_euclid(86400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.00",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rows;
+
+    #[test]
+    fn test_threaded_comments() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert!(wb.get_threaded_comments("Sheet2").unwrap().is_empty());
+        assert!(wb
+            .add_threaded_comment("Sheet1", "A22", "Excelize", "Hello")
+            .is_err());
+
+        let id = wb
+            .add_threaded_comment("Sheet2", "B2", "Excelize", "Hello")
+            .unwrap();
+        assert!(wb
+            .add_threaded_comment("Sheet2", "B2", "Excelize", "Hello")
+            .is_err());
+        let reply_id = wb
+            .add_threaded_comment_reply("Sheet2", &id, "Reviewer", "Done")
+            .unwrap();
+        assert!(wb
+            .add_threaded_comment_reply("Sheet2", &reply_id, "Reviewer", "Done")
+            .is_err());
+        wb.resolve_threaded_comment("Sheet2", &id, true).unwrap();
+
+        let threads = wb.get_threaded_comments("Sheet2").unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, id);
+        assert_eq!(threads[0].cell, "B2");
+        assert_eq!(threads[0].author, "Excelize");
+        assert_eq!(threads[0].text, "Hello");
+        assert!(threads[0].done);
+        assert_eq!(threads[0].replies.len(), 1);
+        assert_eq!(threads[0].replies[0].author, "Reviewer");
+        assert_eq!(threads[0].replies[0].text, "Done");
+        assert_eq!(wb.persons.as_ref().unwrap().person.len(), 2);
+        assert!(wb
+            .threaded_comments
+            .contains_key("xl/threadedComments/threadedComment1.xml"));
+
+        let notes = wb.get_comments("Sheet2").unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].author, format!("tc={}", id));
+        assert!(notes[0].text.ends_with("Hello\nReply:\n    Done"));
+
+        wb.delete_threaded_comment("Sheet2", "B2").unwrap();
+        assert!(wb.get_threaded_comments("Sheet2").unwrap().is_empty());
+        assert!(wb.get_comments("Sheet2").unwrap().is_empty());
+
+        // the threads are moved with the notes when inserting the rows
+        let id = wb
+            .add_threaded_comment("Sheet2", "B2", "Excelize", "Hello")
+            .unwrap();
+        wb.add_threaded_comment_reply("Sheet2", &id, "Reviewer", "Reply")
+            .unwrap();
+        wb.insert_rows("Sheet2", 1, 2).unwrap();
+        let threads = wb.get_threaded_comments("Sheet2").unwrap();
+        assert_eq!(threads[0].cell, "B4");
+        assert_eq!(threads[0].replies.len(), 1);
+        wb.add_threaded_comment_reply("Sheet2", &id, "Reviewer", "Moved")
+            .unwrap();
+        let notes = wb.get_comments("Sheet2").unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].cell, "B4");
+        assert!(notes[0].text.ends_with("Reply\nReply:\n    Moved"));
+
+        // the threads with the replies and the notes are deleted with the row
+        wb.remove_row("Sheet2", 4).unwrap();
+        assert!(wb.get_threaded_comments("Sheet2").unwrap().is_empty());
+        let comments = &wb.threaded_comments["xl/threadedComments/threadedComment1.xml"];
+        assert!(comments.threaded_comment.is_empty());
+        assert!(wb.get_comments("Sheet2").unwrap().is_empty());
+    }
+}
@@ -7,12 +7,13 @@ use crate::ExcelizeError;
 static _MAX_FONT_FAMILY_LENGTH: u32 = 31;
 static _MAX_FONT_SIZE: u32 = 409;
 static _MAX_FILE_NAME_LENGTH: u32 = 207;
-static _MAX_COLUMN_WIDTH: u32 = 255;
-static _MAX_ROW_HEIGHT: u32 = 409;
-static _TOTAL_ROWS: u32 = 1048576;
-static TOTAL_COLUMNS: u32 = 16384;
-static _TOTAL_SHEET_HYPERLINKS: u32 = 65529;
-static _TOTAL_CELL_CHARS: u32 = 32767;
+pub(crate) static MAX_COLUMN_WIDTH: u32 = 255;
+pub(crate) static MAX_ROW_HEIGHT: u32 = 409;
+pub(crate) static TOTAL_ROWS: u32 = 1048576;
+pub(crate) static TOTAL_COLUMNS: u32 = 16384;
+pub(crate) static TOTAL_SHEET_HYPERLINKS: usize = 65529;
+pub(crate) static TOTAL_CELL_CHARS: usize = 32767;
+pub(crate) static MAX_DATE_SERIAL: f64 = 2958465.0;
 
 // column_number_to_name provides a function to convert the integer to Excel
 // sheet column title.
@@ -39,3 +40,110 @@ pub fn column_number_to_name(col: u32) -> Result<String, ExcelizeError> {
     }
     Ok(column)
 }
+
+// column_name_to_number provides a function to convert Excel sheet column
+// title to int. Column name case insensitive. The function returns an error if
+// column name incorrect.
+pub fn column_name_to_number(name: &str) -> Result<u32, ExcelizeError> {
+    if name.is_empty() || name.len() > 3 {
+        return Err(ExcelizeError::CommonError(format!(
+            "invalid column name {:?}",
+            name
+        )));
+    }
+    let mut col: u32 = 0;
+    for c in name.chars() {
+        if !c.is_ascii_alphabetic() {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid column name {:?}",
+                name
+            )));
+        }
+        col = col * 26 + (c.to_ascii_uppercase() as u32 - 'A' as u32 + 1);
+    }
+    if col > TOTAL_COLUMNS {
+        return Err(ExcelizeError::CommonError(String::from(
+            "column number exceeds maximum limit",
+        )));
+    }
+    Ok(col)
+}
+
+// split_cell_name splits cell name to column name and row number, the dollar
+// signs of the absolute references are ignored.
+pub fn split_cell_name(cell: &str) -> Result<(String, u32), ExcelizeError> {
+    let err = || ExcelizeError::CommonError(format!("invalid cell name {:?}", cell));
+    let name = cell.replace('$', "");
+    let i = match name.find(|c: char| c.is_ascii_digit()) {
+        Some(i) => i,
+        None => return Err(err()),
+    };
+    let (col, row) = name.split_at(i);
+    if col.is_empty() || !col.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(err());
+    }
+    match row.parse::<u32>() {
+        Ok(r) if r > 0 => Ok((col.to_ascii_uppercase(), r)),
+        _ => Err(err()),
+    }
+}
+
+// cell_name_to_coordinates converts alphanumeric cell name to [X, Y]
+// coordinates or returns an error.
+pub fn cell_name_to_coordinates(cell: &str) -> Result<(u32, u32), ExcelizeError> {
+    let (col_name, row) = split_cell_name(cell)?;
+    if row > TOTAL_ROWS {
+        return Err(ExcelizeError::CommonError(String::from(
+            "row number exceeds maximum limit",
+        )));
+    }
+    Ok((column_name_to_number(&col_name)?, row))
+}
+
+// coordinates_to_cell_name converts [X, Y] coordinates to alpha-numeric cell
+// name or returns an error.
+pub fn coordinates_to_cell_name(col: u32, row: u32) -> Result<String, ExcelizeError> {
+    if row < 1 {
+        return Err(ExcelizeError::CommonError(format!(
+            "incorrect row number {}",
+            row
+        )));
+    }
+    if row > TOTAL_ROWS {
+        return Err(ExcelizeError::CommonError(String::from(
+            "row number exceeds maximum limit",
+        )));
+    }
+    Ok(format!("{}{}", column_number_to_name(col)?, row))
+}
+
+// range_ref_to_coordinates provides a function to convert range reference to
+// a pair of coordinates [x1, y1, x2, y2], the coordinates are sorted so that
+// the first pair is the top-left cell of the range. A single cell reference is
+// treated as a range of one cell.
+pub fn range_ref_to_coordinates(range: &str) -> Result<[u32; 4], ExcelizeError> {
+    let mut cells = range.split(':');
+    let first = cells.next().unwrap_or_default();
+    let last = cells.next().unwrap_or(first);
+    if cells.next().is_some() {
+        return Err(ExcelizeError::CommonError(format!(
+            "invalid range reference {:?}",
+            range
+        )));
+    }
+    let (x1, y1) = cell_name_to_coordinates(first)?;
+    let (x2, y2) = cell_name_to_coordinates(last)?;
+    Ok([x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)])
+}
+
+// coordinates_to_range_ref provides a function to convert a pair of
+// coordinates to range reference, a single cell reference will be returned
+// when both pairs point to the same cell.
+pub fn coordinates_to_range_ref(coordinates: &[u32; 4]) -> Result<String, ExcelizeError> {
+    let first = coordinates_to_cell_name(coordinates[0], coordinates[1])?;
+    if coordinates[0] == coordinates[2] && coordinates[1] == coordinates[3] {
+        return Ok(first);
+    }
+    let last = coordinates_to_cell_name(coordinates[2], coordinates[3])?;
+    Ok(format!("{}:{}", first, last))
+}
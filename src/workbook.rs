@@ -2,12 +2,28 @@
 // this source code is governed by a BSD-style license that can be found in
 // the LICENSE file.
 
+use crate::crypt::{
+    gen_iso_passwd_hash, gen_legacy_passwd_hash, verify_iso_passwd_hash, PROTECTION_SPIN_COUNT,
+};
 use crate::{xml_content_types, xml_workbook, ExcelizeError, Spreadsheet};
 use std::str;
 extern crate quick_xml;
 extern crate serde;
 
 use quick_xml::de::from_str;
+
+/// WorkbookProtectionOptions directly maps the settings of workbook
+/// protection. The password will be hashed by the algorithm, which is one of
+/// "SHA-256", "SHA-384" and "SHA-512", with "SHA-512" used by default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkbookProtectionOptions {
+    pub algorithm_name: String,
+    pub password: String,
+    pub lock_structure: bool,
+    pub lock_windows: bool,
+    pub lock_revision: bool,
+}
+
 pub trait Workbook {
     fn get_content_type(&mut self) -> Result<(), ExcelizeError>
     where
@@ -15,6 +31,70 @@ pub trait Workbook {
     fn get_workbook(&mut self) -> Result<(), ExcelizeError>
     where
         Self: std::marker::Sized;
+    /// protect_workbook provides a function to prevent other users from
+    /// viewing hidden worksheets, adding, moving, deleting, or hiding
+    /// worksheets, and renaming worksheets in a workbook. The password is
+    /// hashed with a random salt and the spin count of 100000, and the
+    /// revisions password hash is generated as well when the revisions are
+    /// locked. For example, protect the workbook structure with the password:
+    ///
+    /// ```ignore
+    /// spreadsheet.protect_workbook(&WorkbookProtectionOptions {
+    ///     password: String::from("password"),
+    ///     lock_structure: true,
+    ///     ..Default::default()
+    /// })
+    /// ```
+    fn protect_workbook(&mut self, opts: &WorkbookProtectionOptions) -> Result<(), ExcelizeError>;
+    /// unprotect_workbook provides a function to remove protection for the
+    /// workbook, the password will be verified against the workbook password
+    /// hash if the workbook is protected with password.
+    fn unprotect_workbook(&mut self, password: &str) -> Result<(), ExcelizeError>;
+}
+
+// add_content_type_override provides a function to add the content type of
+// the part by given part name, the existing content type of the part will be
+// replaced.
+pub(crate) fn add_content_type_override(
+    types: &mut xml_content_types::XMLTypes,
+    part_name: &str,
+    content_type: &str,
+) {
+    delete_content_type_override(types, part_name);
+    types.fields.push(xml_content_types::XMLOverride::Override {
+        part_name: format!("/{}", part_name.trim_start_matches('/')),
+        content_type: String::from(content_type),
+    });
+}
+
+// delete_content_type_override provides a function to delete the content type
+// of the part by given part name.
+pub(crate) fn delete_content_type_override(
+    types: &mut xml_content_types::XMLTypes,
+    part_name: &str,
+) {
+    let part_name = format!("/{}", part_name.trim_start_matches('/'));
+    types.fields.retain(|field| {
+        !matches!(field, xml_content_types::XMLOverride::Override { part_name: name, .. } if *name == part_name)
+    });
+}
+
+// add_content_type_default provides a function to add the default content
+// type of the extension if it doesn't exist.
+pub(crate) fn add_content_type_default(
+    types: &mut xml_content_types::XMLTypes,
+    extension: &str,
+    content_type: &str,
+) {
+    let exists = types.fields.iter().any(|field| {
+        matches!(field, xml_content_types::XMLOverride::Default { extension: ext, .. } if ext.eq_ignore_ascii_case(extension))
+    });
+    if !exists {
+        types.fields.push(xml_content_types::XMLOverride::Default {
+            extension: String::from(extension),
+            content_type: String::from(content_type),
+        });
+    }
 }
 
 impl Workbook for Spreadsheet {
@@ -24,7 +104,7 @@ impl Workbook for Spreadsheet {
             Some(_) => Ok(()),
             None => {
                 if let Some(buf) = self.file.get_key_value("[Content_Types].xml") {
-                    let s = match str::from_utf8(&buf.1) {
+                    let s = match str::from_utf8(buf.1) {
                         Ok(v) => v,
                         Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
                     };
@@ -48,7 +128,7 @@ impl Workbook for Spreadsheet {
             Some(_) => Ok(()),
             None => {
                 if let Some(buf) = self.file.get_key_value("xl/workbook.xml") {
-                    let s = match str::from_utf8(&buf.1) {
+                    let s = match str::from_utf8(buf.1) {
                         Ok(v) => v,
                         Err(e) => return Err(ExcelizeError::CommonError(e.to_string())),
                     };
@@ -66,4 +146,120 @@ impl Workbook for Spreadsheet {
             }
         }
     }
+
+    fn protect_workbook(&mut self, opts: &WorkbookProtectionOptions) -> Result<(), ExcelizeError> {
+        self.get_workbook()?;
+        let mut protection = xml_workbook::XMLWorkbookProtection {
+            lock_structure: Some(opts.lock_structure),
+            lock_windows: Some(opts.lock_windows),
+            lock_revision: Some(opts.lock_revision),
+            ..Default::default()
+        };
+        if !opts.password.is_empty() {
+            let algorithm = match opts.algorithm_name.is_empty() {
+                true => "SHA-512",
+                false => &opts.algorithm_name,
+            };
+            let (hash_value, salt_value) =
+                gen_iso_passwd_hash(&opts.password, algorithm, None, PROTECTION_SPIN_COUNT)?;
+            protection.workbook_algorithm_name = Some(String::from(algorithm));
+            protection.workbook_hash_value = Some(hash_value);
+            protection.workbook_salt_value = Some(salt_value);
+            protection.workbook_spin_count = Some(PROTECTION_SPIN_COUNT);
+            if opts.lock_revision {
+                let (hash_value, salt_value) =
+                    gen_iso_passwd_hash(&opts.password, algorithm, None, PROTECTION_SPIN_COUNT)?;
+                protection.revisions_algorithm_name = Some(String::from(algorithm));
+                protection.revisions_hash_value = Some(hash_value);
+                protection.revisions_salt_value = Some(salt_value);
+                protection.revisions_spin_count = Some(PROTECTION_SPIN_COUNT);
+            }
+        }
+        if let Some(ref mut wb) = self.workbook {
+            wb.workbook_protection = Some(protection);
+        }
+        Ok(())
+    }
+
+    fn unprotect_workbook(&mut self, password: &str) -> Result<(), ExcelizeError> {
+        self.get_workbook()?;
+        let wb = match self.workbook {
+            Some(ref mut wb) => wb,
+            None => return Err(ExcelizeError::CommonError(String::from("workbook is none"))),
+        };
+        let protection = match wb.workbook_protection {
+            Some(ref protection) => protection,
+            None => {
+                return Err(ExcelizeError::CommonError(String::from(
+                    "workbook is not protected",
+                )))
+            }
+        };
+        let verified = match (
+            &protection.workbook_algorithm_name,
+            &protection.workbook_hash_value,
+            &protection.workbook_salt_value,
+            &protection.workbook_password,
+        ) {
+            (Some(algorithm), Some(hash_value), Some(salt_value), _) => verify_iso_passwd_hash(
+                password,
+                algorithm,
+                hash_value,
+                salt_value,
+                protection.workbook_spin_count.unwrap_or(0),
+            )?,
+            (_, _, _, Some(legacy)) => {
+                gen_legacy_passwd_hash(password)?.eq_ignore_ascii_case(legacy)
+            }
+            _ => true,
+        };
+        if !verified {
+            return Err(ExcelizeError::CommonError(String::from(
+                "the password is incorrect",
+            )));
+        }
+        wb.workbook_protection = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protect_workbook() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        assert!(wb.unprotect_workbook("").is_err());
+        let opts = WorkbookProtectionOptions {
+            password: String::from("password"),
+            lock_structure: true,
+            lock_revision: true,
+            ..Default::default()
+        };
+        wb.protect_workbook(&opts).unwrap();
+        let protection = wb
+            .workbook
+            .as_ref()
+            .unwrap()
+            .workbook_protection
+            .as_ref()
+            .unwrap();
+        assert_eq!(protection.lock_structure, Some(true));
+        assert_eq!(
+            protection.workbook_algorithm_name.as_deref(),
+            Some("SHA-512")
+        );
+        assert!(protection.revisions_hash_value.is_some());
+        assert!(wb.unprotect_workbook("passwd").is_err());
+        wb.unprotect_workbook("password").unwrap();
+        assert!(wb.workbook.as_ref().unwrap().workbook_protection.is_none());
+
+        wb.protect_workbook(&WorkbookProtectionOptions {
+            lock_windows: true,
+            ..Default::default()
+        })
+        .unwrap();
+        wb.unprotect_workbook("").unwrap();
+    }
 }
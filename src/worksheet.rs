@@ -2,17 +2,125 @@
 // this source code is governed by a BSD-style license that can be found in
 // the LICENSE file.
 
-use crate::{xml_worksheet, ExcelizeError, Spreadsheet};
+use crate::crypt::{
+    gen_iso_passwd_hash, gen_legacy_passwd_hash, verify_iso_passwd_hash, PROTECTION_SPIN_COUNT,
+};
+use crate::{range_ref_to_coordinates, xml_worksheet, ExcelizeError, Spreadsheet};
 use std::str;
 extern crate quick_xml;
 extern crate serde;
 
 use quick_xml::de::from_str;
+
+/// SheetProtectionOptions directly maps the settings of worksheet protection.
+/// The flags specify the actions which are allowed to the users when the
+/// worksheet is protected, and the password will be hashed by the algorithm,
+/// which is one of "SHA-256", "SHA-384" and "SHA-512", with "SHA-512" used by
+/// default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SheetProtectionOptions {
+    pub algorithm_name: String,
+    pub auto_filter: bool,
+    pub delete_columns: bool,
+    pub delete_rows: bool,
+    pub edit_objects: bool,
+    pub edit_scenarios: bool,
+    pub format_cells: bool,
+    pub format_columns: bool,
+    pub format_rows: bool,
+    pub insert_columns: bool,
+    pub insert_hyperlinks: bool,
+    pub insert_rows: bool,
+    pub password: String,
+    pub pivot_tables: bool,
+    pub select_locked_cells: bool,
+    pub select_unlocked_cells: bool,
+    pub sort: bool,
+}
+
+/// ProtectedRange directly maps the settings of a range which is editable
+/// when the worksheet is protected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProtectedRange {
+    pub name: String,
+    pub sqref: String,
+    pub algorithm_name: String,
+}
+
 pub trait Worksheet {
     fn worksheet_reader(&mut self) -> Result<(), ExcelizeError>
     where
         Self: std::marker::Sized;
     fn get_target_by_rid(&self, rid: String) -> Result<String, ExcelizeError>;
+    /// get_sheet_xml_path provides a function to get the path of the
+    /// worksheet part in the package by given worksheet name, for example
+    /// xl/worksheets/sheet1.xml.
+    fn get_sheet_xml_path(&self, sheet: &str) -> Result<String, ExcelizeError>;
+    /// get_sheet_index provides a function to get the zero-based index of the
+    /// worksheet in the workbook by given worksheet name.
+    fn get_sheet_index(&self, sheet: &str) -> Result<usize, ExcelizeError>;
+    /// get_worksheet provides a function to get the worksheet by given
+    /// worksheet name.
+    fn get_worksheet(&self, sheet: &str) -> Result<&xml_worksheet::XMLWorksheet, ExcelizeError>;
+    /// get_worksheet_mut provides a function to get the mutable worksheet by
+    /// given worksheet name.
+    fn get_worksheet_mut(
+        &mut self,
+        sheet: &str,
+    ) -> Result<&mut xml_worksheet::XMLWorksheet, ExcelizeError>;
+    /// protect_sheet provides a function to prevent other users from
+    /// accidentally or deliberately changing, moving, or deleting data in a
+    /// worksheet. The password is hashed with a random salt and the spin count
+    /// of 100000, and the legacy password hash is stored as well for the
+    /// earlier versions of the spreadsheet applications. For example, protect
+    /// Sheet1 with the password and allow selecting the cells:
+    ///
+    /// ```ignore
+    /// spreadsheet.protect_sheet(
+    ///     "Sheet1",
+    ///     &SheetProtectionOptions {
+    ///         password: String::from("password"),
+    ///         select_locked_cells: true,
+    ///         select_unlocked_cells: true,
+    ///         ..Default::default()
+    ///     },
+    /// )
+    /// ```
+    fn protect_sheet(
+        &mut self,
+        sheet: &str,
+        opts: &SheetProtectionOptions,
+    ) -> Result<(), ExcelizeError>;
+    /// unprotect_sheet provides a function to remove protection for a sheet,
+    /// the password will be verified against the password hash of the sheet
+    /// protection if the sheet is protected with password.
+    fn unprotect_sheet(&mut self, sheet: &str, password: &str) -> Result<(), ExcelizeError>;
+    /// get_sheet_protection provides a function to get the worksheet
+    /// protection settings by given worksheet name, the password can't be
+    /// retrieved from the hash so that it's always empty.
+    fn get_sheet_protection(&self, sheet: &str) -> Result<SheetProtectionOptions, ExcelizeError>;
+    /// add_protected_range provides a function to add a range which the users
+    /// are allowed to edit when the worksheet is protected. The sqref is a
+    /// space-separated list of the cell or range references, and the password
+    /// will be hashed by the "SHA-512" algorithm when it's not empty. For
+    /// example, allow editing the range A1:B3 on Sheet1 with a password:
+    ///
+    /// ```ignore
+    /// spreadsheet.add_protected_range("Sheet1", "Inputs", "A1:B3", "password")
+    /// ```
+    fn add_protected_range(
+        &mut self,
+        sheet: &str,
+        name: &str,
+        sqref: &str,
+        password: &str,
+    ) -> Result<(), ExcelizeError>;
+    /// get_protected_ranges provides a function to get the protected ranges
+    /// of the worksheet by given worksheet name.
+    fn get_protected_ranges(&self, sheet: &str) -> Result<Vec<ProtectedRange>, ExcelizeError>;
+    /// delete_protected_range provides a function to delete the protected
+    /// range by given worksheet name and the range name.
+    fn delete_protected_range(&mut self, sheet: &str, name: &str) -> Result<(), ExcelizeError>;
 }
 
 impl Worksheet for Spreadsheet {
@@ -30,7 +138,7 @@ impl Worksheet for Spreadsheet {
                                 let rels_xml = format!("xl/{}", &target.to_string());
                                 match self.file.get_key_value(&rels_xml.to_string()) {
                                     Some(buf) => {
-                                        let s = match str::from_utf8(&buf.1) {
+                                        let s = match str::from_utf8(buf.1) {
                                             Ok(v) => v,
                                             Err(e) => {
                                                 return Err(ExcelizeError::CommonError(
@@ -84,4 +192,332 @@ impl Worksheet for Spreadsheet {
             None => Err(ExcelizeError::CommonError(String::from("target is none"))),
         }
     }
+
+    fn get_sheet_xml_path(&self, sheet: &str) -> Result<String, ExcelizeError> {
+        let rid = self
+            .workbook
+            .as_ref()
+            .and_then(|wb| wb.sheets.sheet.iter().find(|s| s.name == sheet))
+            .map(|s| s.id.clone())
+            .ok_or_else(|| ExcelizeError::CommonError(format!("sheet {} is not exist", sheet)))?;
+        let target = self.get_target_by_rid(rid)?;
+        Ok(match target.strip_prefix('/') {
+            Some(target) => String::from(target),
+            None => format!("xl/{}", target),
+        })
+    }
+
+    fn get_sheet_index(&self, sheet: &str) -> Result<usize, ExcelizeError> {
+        self.workbook
+            .as_ref()
+            .and_then(|wb| wb.sheets.sheet.iter().position(|s| s.name == sheet))
+            .ok_or_else(|| ExcelizeError::CommonError(format!("sheet {} is not exist", sheet)))
+    }
+
+    fn get_worksheet(&self, sheet: &str) -> Result<&xml_worksheet::XMLWorksheet, ExcelizeError> {
+        match self.worksheets.get(sheet) {
+            Some(ws) => Ok(ws),
+            None => Err(ExcelizeError::CommonError(format!(
+                "sheet {} is not exist",
+                sheet
+            ))),
+        }
+    }
+
+    fn get_worksheet_mut(
+        &mut self,
+        sheet: &str,
+    ) -> Result<&mut xml_worksheet::XMLWorksheet, ExcelizeError> {
+        match self.worksheets.get_mut(sheet) {
+            Some(ws) => Ok(ws),
+            None => Err(ExcelizeError::CommonError(format!(
+                "sheet {} is not exist",
+                sheet
+            ))),
+        }
+    }
+
+    fn protect_sheet(
+        &mut self,
+        sheet: &str,
+        opts: &SheetProtectionOptions,
+    ) -> Result<(), ExcelizeError> {
+        let mut protection = xml_worksheet::CTSheetProtection {
+            sheet: Some(true),
+            objects: Some(!opts.edit_objects),
+            scenarios: Some(!opts.edit_scenarios),
+            format_cells: Some(!opts.format_cells),
+            format_columns: Some(!opts.format_columns),
+            format_rows: Some(!opts.format_rows),
+            insert_columns: Some(!opts.insert_columns),
+            insert_rows: Some(!opts.insert_rows),
+            insert_hyperlinks: Some(!opts.insert_hyperlinks),
+            delete_columns: Some(!opts.delete_columns),
+            delete_rows: Some(!opts.delete_rows),
+            select_locked_cells: Some(!opts.select_locked_cells),
+            sort: Some(!opts.sort),
+            auto_filter: Some(!opts.auto_filter),
+            pivot_tables: Some(!opts.pivot_tables),
+            select_unlocked_cells: Some(!opts.select_unlocked_cells),
+            ..Default::default()
+        };
+        if !opts.password.is_empty() {
+            let algorithm = match opts.algorithm_name.is_empty() {
+                true => "SHA-512",
+                false => &opts.algorithm_name,
+            };
+            let (hash_value, salt_value) =
+                gen_iso_passwd_hash(&opts.password, algorithm, None, PROTECTION_SPIN_COUNT)?;
+            protection.password = Some(gen_legacy_passwd_hash(&opts.password)?);
+            protection.algorithm_name = Some(String::from(algorithm));
+            protection.hash_value = Some(hash_value);
+            protection.salt_value = Some(salt_value);
+            protection.spin_count = Some(PROTECTION_SPIN_COUNT);
+        }
+        self.get_worksheet_mut(sheet)?.sheet_protection = Some(protection);
+        Ok(())
+    }
+
+    fn unprotect_sheet(&mut self, sheet: &str, password: &str) -> Result<(), ExcelizeError> {
+        let ws = self.get_worksheet_mut(sheet)?;
+        let protection = match ws.sheet_protection {
+            Some(ref protection) => protection,
+            None => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "sheet {} is not protected",
+                    sheet
+                )))
+            }
+        };
+        let verified = match (
+            &protection.algorithm_name,
+            &protection.hash_value,
+            &protection.salt_value,
+            &protection.password,
+        ) {
+            (Some(algorithm), Some(hash_value), Some(salt_value), _) => verify_iso_passwd_hash(
+                password,
+                algorithm,
+                hash_value,
+                salt_value,
+                protection.spin_count.unwrap_or(0),
+            )?,
+            (_, _, _, Some(legacy)) => {
+                gen_legacy_passwd_hash(password)?.eq_ignore_ascii_case(legacy)
+            }
+            _ => true,
+        };
+        if !verified {
+            return Err(ExcelizeError::CommonError(String::from(
+                "the password is incorrect",
+            )));
+        }
+        ws.sheet_protection = None;
+        Ok(())
+    }
+
+    fn get_sheet_protection(&self, sheet: &str) -> Result<SheetProtectionOptions, ExcelizeError> {
+        let ws = self.get_worksheet(sheet)?;
+        let protection = match ws.sheet_protection {
+            Some(ref protection) => protection,
+            None => {
+                return Err(ExcelizeError::CommonError(format!(
+                    "sheet {} is not protected",
+                    sheet
+                )))
+            }
+        };
+        let allowed = |locked: Option<bool>, default: bool| !locked.unwrap_or(default);
+        Ok(SheetProtectionOptions {
+            algorithm_name: protection.algorithm_name.clone().unwrap_or_default(),
+            auto_filter: allowed(protection.auto_filter, true),
+            delete_columns: allowed(protection.delete_columns, true),
+            delete_rows: allowed(protection.delete_rows, true),
+            edit_objects: allowed(protection.objects, false),
+            edit_scenarios: allowed(protection.scenarios, false),
+            format_cells: allowed(protection.format_cells, true),
+            format_columns: allowed(protection.format_columns, true),
+            format_rows: allowed(protection.format_rows, true),
+            insert_columns: allowed(protection.insert_columns, true),
+            insert_hyperlinks: allowed(protection.insert_hyperlinks, true),
+            insert_rows: allowed(protection.insert_rows, true),
+            password: String::new(),
+            pivot_tables: allowed(protection.pivot_tables, true),
+            select_locked_cells: allowed(protection.select_locked_cells, false),
+            select_unlocked_cells: allowed(protection.select_unlocked_cells, false),
+            sort: allowed(protection.sort, true),
+        })
+    }
+
+    fn add_protected_range(
+        &mut self,
+        sheet: &str,
+        name: &str,
+        sqref: &str,
+        password: &str,
+    ) -> Result<(), ExcelizeError> {
+        if name.is_empty() {
+            return Err(ExcelizeError::CommonError(String::from(
+                "the protected range name can't be empty",
+            )));
+        }
+        let mut refs = Vec::new();
+        for range in sqref.split_whitespace() {
+            refs.push(range_ref_to_coordinates(range)?);
+        }
+        if refs.is_empty() {
+            return Err(ExcelizeError::CommonError(format!(
+                "invalid range reference {:?}",
+                sqref
+            )));
+        }
+        let ws = self.get_worksheet_mut(sheet)?;
+        let ranges = ws.protected_ranges.get_or_insert_with(Default::default);
+        if ranges
+            .protected_range
+            .iter()
+            .any(|r| r.name.eq_ignore_ascii_case(name))
+        {
+            return Err(ExcelizeError::CommonError(format!(
+                "the protected range {} already exists",
+                name
+            )));
+        }
+        let mut range = xml_worksheet::CTProtectedRange {
+            sqref: sqref.split_whitespace().collect::<Vec<&str>>().join(" "),
+            name: String::from(name),
+            ..Default::default()
+        };
+        if !password.is_empty() {
+            let (hash_value, salt_value) =
+                gen_iso_passwd_hash(password, "SHA-512", None, PROTECTION_SPIN_COUNT)?;
+            range.algorithm_name = Some(String::from("SHA-512"));
+            range.hash_value = Some(hash_value);
+            range.salt_value = Some(salt_value);
+            range.spin_count = Some(PROTECTION_SPIN_COUNT);
+        }
+        ranges.protected_range.push(range);
+        Ok(())
+    }
+
+    fn get_protected_ranges(&self, sheet: &str) -> Result<Vec<ProtectedRange>, ExcelizeError> {
+        let ws = self.get_worksheet(sheet)?;
+        Ok(ws
+            .protected_ranges
+            .iter()
+            .flat_map(|ranges| ranges.protected_range.iter())
+            .map(|r| ProtectedRange {
+                name: r.name.clone(),
+                sqref: r.sqref.clone(),
+                algorithm_name: r.algorithm_name.clone().unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    fn delete_protected_range(&mut self, sheet: &str, name: &str) -> Result<(), ExcelizeError> {
+        let ws = self.get_worksheet_mut(sheet)?;
+        let mut deleted = false;
+        if let Some(ref mut ranges) = ws.protected_ranges {
+            let count = ranges.protected_range.len();
+            ranges
+                .protected_range
+                .retain(|r| !r.name.eq_ignore_ascii_case(name));
+            deleted = ranges.protected_range.len() != count;
+            if ranges.protected_range.is_empty() {
+                ws.protected_ranges = None;
+            }
+        }
+        if !deleted {
+            return Err(ExcelizeError::CommonError(format!(
+                "the protected range {} is not exist",
+                name
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Col, Rows};
+
+    #[test]
+    fn test_protect_sheet() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        let opts = SheetProtectionOptions {
+            password: String::from("password"),
+            format_cells: true,
+            select_locked_cells: true,
+            select_unlocked_cells: true,
+            ..Default::default()
+        };
+        wb.protect_sheet("Sheet1", &opts).unwrap();
+        let protection = wb.worksheets["Sheet1"].sheet_protection.as_ref().unwrap();
+        assert_eq!(protection.password.as_deref(), Some("83AF"));
+        assert_eq!(protection.spin_count, Some(PROTECTION_SPIN_COUNT));
+        let got = wb.get_sheet_protection("Sheet1").unwrap();
+        assert_eq!(got.algorithm_name, "SHA-512");
+        assert!(got.format_cells && got.select_locked_cells && !got.format_rows);
+
+        assert!(wb.unprotect_sheet("Sheet1", "Password").is_err());
+        wb.unprotect_sheet("Sheet1", "password").unwrap();
+        assert!(wb.get_sheet_protection("Sheet1").is_err());
+        assert!(wb.unprotect_sheet("Sheet1", "password").is_err());
+
+        wb.protect_sheet("Sheet2", &SheetProtectionOptions::default())
+            .unwrap();
+        wb.unprotect_sheet("Sheet2", "").unwrap();
+        let opts = SheetProtectionOptions {
+            password: "p".repeat(70),
+            ..Default::default()
+        };
+        wb.protect_sheet("Sheet2", &opts).unwrap();
+        wb.unprotect_sheet("Sheet2", &"p".repeat(70)).unwrap();
+        let opts = SheetProtectionOptions {
+            password: String::from("password"),
+            algorithm_name: String::from("MD4"),
+            ..Default::default()
+        };
+        assert!(wb.protect_sheet("Sheet2", &opts).is_err());
+    }
+
+    #[test]
+    fn test_protected_ranges() {
+        let mut wb = Spreadsheet::open_file(String::from("src/test/Book1.xlsx")).unwrap();
+        wb.add_protected_range("Sheet1", "Inputs", "A1:B3  D5", "password")
+            .unwrap();
+        wb.add_protected_range("Sheet1", "Notes", "F1:F10", "")
+            .unwrap();
+        assert!(wb
+            .add_protected_range("Sheet1", "inputs", "C1", "")
+            .is_err());
+        assert!(wb
+            .add_protected_range("Sheet1", "Bad", "A0:B1", "")
+            .is_err());
+        assert!(wb.add_protected_range("Sheet1", "", "A1", "").is_err());
+        assert_eq!(
+            wb.get_protected_ranges("Sheet1").unwrap(),
+            vec![
+                ProtectedRange {
+                    name: String::from("Inputs"),
+                    sqref: String::from("A1:B3 D5"),
+                    algorithm_name: String::from("SHA-512"),
+                },
+                ProtectedRange {
+                    name: String::from("Notes"),
+                    sqref: String::from("F1:F10"),
+                    algorithm_name: String::new(),
+                },
+            ]
+        );
+        wb.insert_rows("Sheet1", 2, 2).unwrap();
+        wb.remove_col("Sheet1", "F").unwrap();
+        let ranges = wb.get_protected_ranges("Sheet1").unwrap();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].sqref, "A1:B5 D7");
+        wb.delete_protected_range("Sheet1", "Inputs").unwrap();
+        assert!(wb.delete_protected_range("Sheet1", "Notes").is_err());
+        assert!(wb.worksheets["Sheet1"].protected_ranges.is_none());
+    }
 }
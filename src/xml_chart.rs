@@ -0,0 +1,361 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::{STXBool, STXDouble, STXInt, STXstring};
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLChartSpace directly maps the chartSpace element from the namespace
+/// http://schemas.openxmlformats.org/drawingml/2006/chart. This element is the
+/// root of the chart part, which specifies a single chart. The elements are
+/// aliased by the local names, since the deserializer matches the elements
+/// without the namespace prefix.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "c:chartSpace")]
+pub struct XMLChartSpace {
+    #[serde(rename = "xmlns:c")]
+    pub xmlns_c: String,
+    #[serde(rename = "xmlns:a")]
+    pub xmlns_a: String,
+    #[serde(rename = "xmlns:r")]
+    pub xmlns_r: String,
+    #[serde(rename = "c:roundedCorners", alias = "roundedCorners")]
+    pub rounded_corners: Option<STXBool>,
+    #[serde(rename = "c:chart", alias = "chart")]
+    pub chart: CTChart,
+}
+
+/// CTChart directly maps the chart element. This element specifies the title,
+/// the plot area and the legend of the chart.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTChart {
+    #[serde(rename = "c:title", alias = "title")]
+    pub title: Option<CTTitle>,
+    #[serde(rename = "c:autoTitleDeleted", alias = "autoTitleDeleted")]
+    pub auto_title_deleted: Option<STXBool>,
+    #[serde(rename = "c:plotArea", alias = "plotArea")]
+    pub plot_area: CTPlotArea,
+    #[serde(rename = "c:legend", alias = "legend")]
+    pub legend: Option<CTLegend>,
+    #[serde(rename = "c:plotVisOnly", alias = "plotVisOnly")]
+    pub plot_vis_only: Option<STXBool>,
+    #[serde(rename = "c:dispBlanksAs", alias = "dispBlanksAs")]
+    pub disp_blanks_as: Option<STXstring>,
+}
+
+/// CTTitle directly maps the title element. This element specifies the title
+/// of the chart or the axis.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTitle {
+    #[serde(rename = "c:tx", alias = "tx")]
+    pub tx: Option<CTTx>,
+    #[serde(rename = "c:overlay", alias = "overlay")]
+    pub overlay: Option<STXBool>,
+}
+
+/// CTTx directly maps the tx element. This element specifies the text of the
+/// title or the series name, which is either a reference to a cell, the rich
+/// text or the literal text.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTx {
+    #[serde(rename = "c:strRef", alias = "strRef")]
+    pub str_ref: Option<CTStrRef>,
+    #[serde(rename = "c:rich", alias = "rich")]
+    pub rich: Option<CTTextBody>,
+    #[serde(rename = "c:v", alias = "v")]
+    pub v: Option<STXText>,
+}
+
+/// CTTextBody directly maps the rich element. This element contains the
+/// paragraphs of the rich text.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTextBody {
+    #[serde(rename = "a:bodyPr", alias = "bodyPr")]
+    pub body_pr: CTTextBodyProperties,
+    #[serde(rename = "a:lstStyle", alias = "lstStyle")]
+    pub lst_style: Option<CTTextListStyle>,
+    #[serde(rename = "a:p", alias = "p", default)]
+    pub p: Vec<CTTextParagraph>,
+}
+
+/// CTTextBodyProperties directly maps the bodyPr element. This element
+/// specifies the properties of the text body.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTextBodyProperties {}
+
+/// CTTextListStyle directly maps the lstStyle element. This element specifies
+/// the list styles of the text body.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTextListStyle {}
+
+/// CTTextParagraph directly maps the p element. This element specifies a
+/// paragraph of the rich text.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTextParagraph {
+    #[serde(rename = "a:r", alias = "r", default)]
+    pub r: Vec<CTRegularTextRun>,
+}
+
+/// CTRegularTextRun directly maps the r element. This element specifies a run
+/// of the text in the paragraph.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTRegularTextRun {
+    #[serde(rename = "a:t", alias = "t")]
+    pub t: STXText,
+}
+
+/// STXText directly maps the element with the string data type as the text
+/// content, such as the t, f and v elements.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct STXText(pub String);
+
+/// CTStrRef directly maps the strRef element. This element specifies a
+/// reference to the string data.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTStrRef {
+    #[serde(rename = "c:f", alias = "f")]
+    pub f: STXText,
+}
+
+/// CTNumRef directly maps the numRef element. This element specifies a
+/// reference to the numeric data.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTNumRef {
+    #[serde(rename = "c:f", alias = "f")]
+    pub f: STXText,
+}
+
+/// CTPlotArea directly maps the plotArea element. This element specifies the
+/// chart groups and the axes of the chart.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPlotArea {
+    #[serde(rename = "c:layout", alias = "layout")]
+    pub layout: Option<CTLayout>,
+    #[serde(rename = "c:areaChart", alias = "areaChart", default)]
+    pub area_chart: Vec<CTChartGroup>,
+    #[serde(rename = "c:barChart", alias = "barChart", default)]
+    pub bar_chart: Vec<CTChartGroup>,
+    #[serde(rename = "c:lineChart", alias = "lineChart", default)]
+    pub line_chart: Vec<CTChartGroup>,
+    #[serde(rename = "c:pieChart", alias = "pieChart", default)]
+    pub pie_chart: Vec<CTChartGroup>,
+    #[serde(rename = "c:scatterChart", alias = "scatterChart", default)]
+    pub scatter_chart: Vec<CTChartGroup>,
+    #[serde(rename = "c:catAx", alias = "catAx", default)]
+    pub cat_ax: Vec<CTAxis>,
+    #[serde(rename = "c:valAx", alias = "valAx", default)]
+    pub val_ax: Vec<CTAxis>,
+}
+
+/// CTLayout directly maps the layout element. This element specifies how the
+/// plot area is placed in the chart, the layout is automatic if it's empty.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTLayout {}
+
+/// CTChartGroup directly maps the areaChart, barChart, lineChart, pieChart and
+/// scatterChart elements. These elements specify a group of series plotted
+/// with the same chart type and the IDs of the axes of the group.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTChartGroup {
+    #[serde(rename = "c:barDir", alias = "barDir")]
+    pub bar_dir: Option<STXstring>,
+    #[serde(rename = "c:grouping", alias = "grouping")]
+    pub grouping: Option<STXstring>,
+    #[serde(rename = "c:scatterStyle", alias = "scatterStyle")]
+    pub scatter_style: Option<STXstring>,
+    #[serde(rename = "c:varyColors", alias = "varyColors")]
+    pub vary_colors: Option<STXBool>,
+    #[serde(rename = "c:ser", alias = "ser", default)]
+    pub ser: Vec<CTSer>,
+    #[serde(rename = "c:dLbls", alias = "dLbls")]
+    pub d_lbls: Option<CTDLbls>,
+    #[serde(rename = "c:gapWidth", alias = "gapWidth")]
+    pub gap_width: Option<STXInt>,
+    #[serde(rename = "c:overlap", alias = "overlap")]
+    pub overlap: Option<STXInt>,
+    #[serde(rename = "c:marker", alias = "marker")]
+    pub marker: Option<STXBool>,
+    #[serde(rename = "c:firstSliceAng", alias = "firstSliceAng")]
+    pub first_slice_ang: Option<STXInt>,
+    #[serde(rename = "c:axId", alias = "axId", default)]
+    pub ax_id: Vec<STXInt>,
+}
+
+/// CTSer directly maps the ser element. This element specifies a series of the
+/// chart group, the category and value data sources are the xVal and yVal
+/// elements in the scatter chart.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTSer {
+    #[serde(rename = "c:idx", alias = "idx")]
+    pub idx: STXInt,
+    #[serde(rename = "c:order", alias = "order")]
+    pub order: STXInt,
+    #[serde(rename = "c:tx", alias = "tx")]
+    pub tx: Option<CTTx>,
+    #[serde(rename = "c:spPr", alias = "spPr")]
+    pub sp_pr: Option<CTShapeProperties>,
+    #[serde(rename = "c:marker", alias = "marker")]
+    pub marker: Option<CTMarker>,
+    #[serde(rename = "c:invertIfNegative", alias = "invertIfNegative")]
+    pub invert_if_negative: Option<STXBool>,
+    #[serde(rename = "c:cat", alias = "cat")]
+    pub cat: Option<CTAxDataSource>,
+    #[serde(rename = "c:val", alias = "val")]
+    pub val: Option<CTNumDataSource>,
+    #[serde(rename = "c:xVal", alias = "xVal")]
+    pub x_val: Option<CTAxDataSource>,
+    #[serde(rename = "c:yVal", alias = "yVal")]
+    pub y_val: Option<CTNumDataSource>,
+    #[serde(rename = "c:smooth", alias = "smooth")]
+    pub smooth: Option<STXBool>,
+}
+
+/// CTShapeProperties directly maps the spPr element. This element specifies
+/// the visual shape properties of the series.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTShapeProperties {
+    #[serde(rename = "a:ln", alias = "ln")]
+    pub ln: Option<CTLineProperties>,
+}
+
+/// CTLineProperties directly maps the ln element. This element specifies the
+/// width in EMUs and the fill of the outline.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTLineProperties {
+    #[serde(rename = "w")]
+    pub w: Option<i32>,
+    #[serde(rename = "a:noFill", alias = "noFill")]
+    pub no_fill: Option<CTNoFillProperties>,
+}
+
+/// CTNoFillProperties directly maps the noFill element. This element specifies
+/// the outline has no fill.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTNoFillProperties {}
+
+/// CTMarker directly maps the marker element of the series. This element
+/// specifies the symbol of the data points.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTMarker {
+    #[serde(rename = "c:symbol", alias = "symbol")]
+    pub symbol: Option<STXstring>,
+}
+
+/// CTAxDataSource directly maps the cat and xVal elements. These elements
+/// specify the reference to the category data or the X values.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTAxDataSource {
+    #[serde(rename = "c:numRef", alias = "numRef")]
+    pub num_ref: Option<CTNumRef>,
+    #[serde(rename = "c:strRef", alias = "strRef")]
+    pub str_ref: Option<CTStrRef>,
+}
+
+/// CTNumDataSource directly maps the val and yVal elements. These elements
+/// specify the reference to the values or the Y values.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTNumDataSource {
+    #[serde(rename = "c:numRef", alias = "numRef")]
+    pub num_ref: Option<CTNumRef>,
+}
+
+/// CTDLbls directly maps the dLbls element. This element specifies which
+/// content is shown in the data labels of the chart group.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDLbls {
+    #[serde(rename = "c:showLegendKey", alias = "showLegendKey")]
+    pub show_legend_key: Option<STXBool>,
+    #[serde(rename = "c:showVal", alias = "showVal")]
+    pub show_val: Option<STXBool>,
+    #[serde(rename = "c:showCatName", alias = "showCatName")]
+    pub show_cat_name: Option<STXBool>,
+    #[serde(rename = "c:showSerName", alias = "showSerName")]
+    pub show_ser_name: Option<STXBool>,
+    #[serde(rename = "c:showPercent", alias = "showPercent")]
+    pub show_percent: Option<STXBool>,
+    #[serde(rename = "c:showBubbleSize", alias = "showBubbleSize")]
+    pub show_bubble_size: Option<STXBool>,
+}
+
+/// CTAxis directly maps the catAx and valAx elements. These elements specify
+/// the category axis and the value axis, the crossBetween element only
+/// applies to the value axis, and the auto, lblAlgn, lblOffset and
+/// noMultiLvlLbl elements only apply to the category axis.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTAxis {
+    #[serde(rename = "c:axId", alias = "axId")]
+    pub ax_id: STXInt,
+    #[serde(rename = "c:scaling", alias = "scaling")]
+    pub scaling: CTScaling,
+    #[serde(rename = "c:delete", alias = "delete")]
+    pub delete: Option<STXBool>,
+    #[serde(rename = "c:axPos", alias = "axPos")]
+    pub ax_pos: STXstring,
+    #[serde(rename = "c:majorGridlines", alias = "majorGridlines")]
+    pub major_gridlines: Option<CTChartLines>,
+    #[serde(rename = "c:minorGridlines", alias = "minorGridlines")]
+    pub minor_gridlines: Option<CTChartLines>,
+    #[serde(rename = "c:title", alias = "title")]
+    pub title: Option<CTTitle>,
+    #[serde(rename = "c:numFmt", alias = "numFmt")]
+    pub num_fmt: Option<CTChartNumFmt>,
+    #[serde(rename = "c:majorTickMark", alias = "majorTickMark")]
+    pub major_tick_mark: Option<STXstring>,
+    #[serde(rename = "c:minorTickMark", alias = "minorTickMark")]
+    pub minor_tick_mark: Option<STXstring>,
+    #[serde(rename = "c:tickLblPos", alias = "tickLblPos")]
+    pub tick_lbl_pos: Option<STXstring>,
+    #[serde(rename = "c:crossAx", alias = "crossAx")]
+    pub cross_ax: STXInt,
+    #[serde(rename = "c:crosses", alias = "crosses")]
+    pub crosses: Option<STXstring>,
+    #[serde(rename = "c:crossBetween", alias = "crossBetween")]
+    pub cross_between: Option<STXstring>,
+    #[serde(rename = "c:auto", alias = "auto")]
+    pub auto: Option<STXBool>,
+    #[serde(rename = "c:lblAlgn", alias = "lblAlgn")]
+    pub lbl_algn: Option<STXstring>,
+    #[serde(rename = "c:lblOffset", alias = "lblOffset")]
+    pub lbl_offset: Option<STXInt>,
+    #[serde(rename = "c:noMultiLvlLbl", alias = "noMultiLvlLbl")]
+    pub no_multi_lvl_lbl: Option<STXBool>,
+}
+
+/// CTScaling directly maps the scaling element. This element specifies the
+/// orientation and the bounds of the axis.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTScaling {
+    #[serde(rename = "c:orientation", alias = "orientation")]
+    pub orientation: Option<STXstring>,
+    #[serde(rename = "c:max", alias = "max")]
+    pub max: Option<STXDouble>,
+    #[serde(rename = "c:min", alias = "min")]
+    pub min: Option<STXDouble>,
+}
+
+/// CTChartLines directly maps the majorGridlines and minorGridlines elements.
+/// These elements specify the gridlines of the axis are shown.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTChartLines {}
+
+/// CTChartNumFmt directly maps the numFmt element of the axis. This element
+/// specifies the number format of the axis labels, the format of the source
+/// data is used if the source linked attribute is true.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTChartNumFmt {
+    #[serde(rename = "formatCode")]
+    pub format_code: String,
+    #[serde(rename = "sourceLinked")]
+    pub source_linked: Option<bool>,
+}
+
+/// CTLegend directly maps the legend element. This element specifies the
+/// position of the legend of the chart.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTLegend {
+    #[serde(rename = "c:legendPos", alias = "legendPos")]
+    pub legend_pos: Option<STXstring>,
+    #[serde(rename = "c:overlay", alias = "overlay")]
+    pub overlay: Option<STXBool>,
+}
@@ -0,0 +1,54 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::CTRst;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLComments directly maps the comments element from the namespace
+/// http://schemas.openxmlformats.org/spreadsheetml/2006/main. A comment is a
+/// rich text note that is attached to and associated with a cell, separate
+/// from other cell content. Comment content is stored separate from the cell,
+/// and is displayed in a drawing object (like a text box) that is separate
+/// from, but associated with, a cell. Comments are used as reminders, such as
+/// noting how a complex formula works, or to provide feedback to other users.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "comments")]
+pub struct XMLComments {
+    #[serde(rename = "authors")]
+    pub authors: XMLAuthors,
+    #[serde(rename = "commentList")]
+    pub comment_list: XMLCommentList,
+}
+
+/// XMLAuthors directly maps the authors element. This element holds a string
+/// representing the name of a single author of comments. Every comment shall
+/// have an author. The maximum length of the author string is an
+/// implementation detail, but a good guideline is 255 chars.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLAuthors {
+    #[serde(rename = "author")]
+    pub author: Vec<String>,
+}
+
+/// XMLCommentList directly maps the commentList element. This element is a
+/// container that holds a list of comments for the sheet.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLCommentList {
+    #[serde(rename = "comment")]
+    pub comment: Vec<XMLComment>,
+}
+
+/// XMLComment directly maps the comment element. This element represents a
+/// single user entered comment. Each comment shall have an author and can
+/// optionally contain richly formatted text.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLComment {
+    #[serde(rename = "ref")]
+    pub ref_attr: String,
+    #[serde(rename = "authorId")]
+    pub author_id: u32,
+    #[serde(rename = "text")]
+    pub text: CTRst,
+}
@@ -12,7 +12,7 @@ use serde::Serialize;
 #[serde(rename = "Types")]
 pub struct XMLTypes {
     #[serde(rename = "$value")]
-    fields: Vec<XMLOverride>,
+    pub fields: Vec<XMLOverride>,
 }
 
 /// CTMaps directly maps the elements in the namespace
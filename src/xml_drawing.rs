@@ -0,0 +1,164 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLWsDr directly maps the wsDr element from the namespace
+/// http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing. This
+/// element is the root of the drawing part of the worksheet, which contains
+/// the anchors of the shapes, the pictures and the charts.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "xdr:wsDr")]
+pub struct XMLWsDr {
+    #[serde(rename = "xmlns:xdr")]
+    pub xmlns_xdr: String,
+    #[serde(rename = "xmlns:a")]
+    pub xmlns_a: String,
+    #[serde(rename = "xdr:twoCellAnchor", alias = "twoCellAnchor", default)]
+    pub two_cell_anchor: Vec<CTTwoCellAnchor>,
+}
+
+/// CTTwoCellAnchor directly maps the twoCellAnchor element. This element
+/// specifies the object is anchored by the top-left and the bottom-right
+/// corners to the cells of the worksheet.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "xdr:twoCellAnchor")]
+pub struct CTTwoCellAnchor {
+    #[serde(rename = "editAs")]
+    pub edit_as: Option<String>,
+    #[serde(rename = "xdr:from", alias = "from")]
+    pub from: CTDrawingMarker,
+    #[serde(rename = "xdr:to", alias = "to")]
+    pub to: CTDrawingMarker,
+    #[serde(rename = "xdr:graphicFrame", alias = "graphicFrame")]
+    pub graphic_frame: Option<CTGraphicalObjectFrame>,
+    #[serde(rename = "xdr:clientData", alias = "clientData")]
+    pub client_data: CTAnchorClientData,
+}
+
+/// CTDrawingMarker directly maps the from and to elements. These elements
+/// specify the zero-based column and row index of the cell and the offsets in
+/// EMUs within the cell.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDrawingMarker {
+    #[serde(rename = "xdr:col", alias = "col")]
+    pub col: STXCoordinate,
+    #[serde(rename = "xdr:colOff", alias = "colOff")]
+    pub col_off: STXCoordinate,
+    #[serde(rename = "xdr:row", alias = "row")]
+    pub row: STXCoordinate,
+    #[serde(rename = "xdr:rowOff", alias = "rowOff")]
+    pub row_off: STXCoordinate,
+}
+
+/// STXCoordinate directly maps the element with the coordinate as the text
+/// content, such as the col, colOff, row and rowOff elements.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct STXCoordinate(pub i64);
+
+/// CTGraphicalObjectFrame directly maps the graphicFrame element. This
+/// element specifies the frame of the graphical object such as the chart.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTGraphicalObjectFrame {
+    #[serde(rename = "macro")]
+    pub macro_attr: Option<String>,
+    #[serde(rename = "xdr:nvGraphicFramePr", alias = "nvGraphicFramePr")]
+    pub nv_graphic_frame_pr: CTGraphicalObjectFrameNonVisual,
+    #[serde(rename = "xdr:xfrm", alias = "xfrm")]
+    pub xfrm: CTTransform2D,
+    #[serde(rename = "a:graphic", alias = "graphic")]
+    pub graphic: CTGraphicalObject,
+}
+
+/// CTGraphicalObjectFrameNonVisual directly maps the nvGraphicFramePr
+/// element. This element specifies the non-visual properties of the frame.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTGraphicalObjectFrameNonVisual {
+    #[serde(rename = "xdr:cNvPr", alias = "cNvPr")]
+    pub c_nv_pr: CTNonVisualDrawingProps,
+    #[serde(rename = "xdr:cNvGraphicFramePr", alias = "cNvGraphicFramePr")]
+    pub c_nv_graphic_frame_pr: CTNonVisualGraphicFrameProperties,
+}
+
+/// CTNonVisualDrawingProps directly maps the cNvPr element. This element
+/// specifies the unique ID and the name of the object in the drawing.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTNonVisualDrawingProps {
+    #[serde(rename = "id")]
+    pub id: u32,
+    #[serde(rename = "name")]
+    pub name: String,
+}
+
+/// CTNonVisualGraphicFrameProperties directly maps the cNvGraphicFramePr
+/// element. This element specifies the locking properties of the frame.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTNonVisualGraphicFrameProperties {}
+
+/// CTTransform2D directly maps the xfrm element. This element specifies the
+/// offset and the extents of the frame in EMUs.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTransform2D {
+    #[serde(rename = "a:off", alias = "off")]
+    pub off: CTPoint2D,
+    #[serde(rename = "a:ext", alias = "ext")]
+    pub ext: CTPositiveSize2D,
+}
+
+/// CTPoint2D directly maps the off element. This element specifies the
+/// location of the frame.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPoint2D {
+    #[serde(rename = "x")]
+    pub x: i64,
+    #[serde(rename = "y")]
+    pub y: i64,
+}
+
+/// CTPositiveSize2D directly maps the ext element. This element specifies the
+/// size of the frame.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPositiveSize2D {
+    #[serde(rename = "cx")]
+    pub cx: i64,
+    #[serde(rename = "cy")]
+    pub cy: i64,
+}
+
+/// CTGraphicalObject directly maps the graphic element. This element contains
+/// the data of the graphical object.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTGraphicalObject {
+    #[serde(rename = "a:graphicData", alias = "graphicData")]
+    pub graphic_data: CTGraphicalObjectData,
+}
+
+/// CTGraphicalObjectData directly maps the graphicData element. This element
+/// specifies the type of the graphical object by the URI, and refers to the
+/// chart part if the object is a chart.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTGraphicalObjectData {
+    #[serde(rename = "uri")]
+    pub uri: String,
+    #[serde(rename = "c:chart", alias = "chart")]
+    pub chart: Option<CTChartRelId>,
+}
+
+/// CTChartRelId directly maps the chart element of the graphic data. This
+/// element specifies the relationship ID of the chart part.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTChartRelId {
+    #[serde(rename = "xmlns:c")]
+    pub xmlns_c: String,
+    #[serde(rename = "xmlns:r")]
+    pub xmlns_r: String,
+    #[serde(rename = "r:id")]
+    pub id: String,
+}
+
+/// CTAnchorClientData directly maps the clientData element. This element
+/// specifies the client data of the anchor.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTAnchorClientData {}
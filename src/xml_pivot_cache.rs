@@ -0,0 +1,164 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLPivotCacheDefinition directly maps the pivotCacheDefinition element
+/// from the namespace http://schemas.openxmlformats.org/spreadsheetml/2006/main.
+/// This element represents the pivotCacheDefinition part, which defines each
+/// field in the source data, including the name of the field, the data type
+/// and the unique items of the field.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "pivotCacheDefinition")]
+pub struct XMLPivotCacheDefinition {
+    #[serde(rename = "r:id")]
+    pub rid: Option<String>,
+    #[serde(rename = "refreshOnLoad")]
+    pub refresh_on_load: Option<bool>,
+    #[serde(rename = "createdVersion")]
+    pub created_version: Option<u32>,
+    #[serde(rename = "refreshedVersion")]
+    pub refreshed_version: Option<u32>,
+    #[serde(rename = "minRefreshableVersion")]
+    pub min_refreshable_version: Option<u32>,
+    #[serde(rename = "recordCount")]
+    pub record_count: Option<u32>,
+    #[serde(rename = "cacheSource")]
+    pub cache_source: CTCacheSource,
+    #[serde(rename = "cacheFields")]
+    pub cache_fields: CTCacheFields,
+}
+
+/// CTCacheSource directly maps the cacheSource element. This element describes
+/// the data source of the pivot cache, such as the worksheet range.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCacheSource {
+    #[serde(rename = "type")]
+    pub type_attr: String,
+    #[serde(rename = "worksheetSource")]
+    pub worksheet_source: Option<CTWorksheetSource>,
+}
+
+/// CTWorksheetSource directly maps the worksheetSource element. This element
+/// specifies the range reference and the worksheet name, or the defined name
+/// of the source data.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTWorksheetSource {
+    #[serde(rename = "ref")]
+    pub ref_attr: Option<String>,
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+    #[serde(rename = "sheet")]
+    pub sheet: Option<String>,
+}
+
+/// CTCacheFields directly maps the cacheFields element. This element
+/// represents the collection of field definitions in the source data.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCacheFields {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "cacheField", default)]
+    pub cache_field: Vec<CTCacheField>,
+}
+
+/// CTCacheField directly maps the cacheField element. This element represents
+/// a single field in the pivot cache, the shared items hold the unique values
+/// of the field which are referenced by index from the cache records.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCacheField {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "numFmtId")]
+    pub num_fmt_id: Option<u32>,
+    #[serde(rename = "sharedItems")]
+    pub shared_items: Option<CTSharedItems>,
+}
+
+/// CTSharedItems directly maps the sharedItems element. This element
+/// represents the collection of unique items of the field, and the attributes
+/// describe the data types of the values in the field.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTSharedItems {
+    #[serde(rename = "containsSemiMixedTypes")]
+    pub contains_semi_mixed_types: Option<bool>,
+    #[serde(rename = "containsString")]
+    pub contains_string: Option<bool>,
+    #[serde(rename = "containsNumber")]
+    pub contains_number: Option<bool>,
+    #[serde(rename = "containsInteger")]
+    pub contains_integer: Option<bool>,
+    #[serde(rename = "containsBlank")]
+    pub contains_blank: Option<bool>,
+    #[serde(rename = "minValue")]
+    pub min_value: Option<f64>,
+    #[serde(rename = "maxValue")]
+    pub max_value: Option<f64>,
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "$value", default)]
+    pub items: Vec<CTCacheValue>,
+}
+
+/// CTCacheValue directly maps the value elements of the shared items and the
+/// cache records, which are the string, number, boolean, error, date time,
+/// missing value and the index of the shared item.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub enum CTCacheValue {
+    #[serde(rename = "s")]
+    S {
+        #[serde(rename = "v")]
+        v: String,
+    },
+    #[serde(rename = "n")]
+    N {
+        #[serde(rename = "v")]
+        v: f64,
+    },
+    #[serde(rename = "b")]
+    B {
+        #[serde(rename = "v")]
+        v: bool,
+    },
+    #[serde(rename = "e")]
+    E {
+        #[serde(rename = "v")]
+        v: String,
+    },
+    #[serde(rename = "d")]
+    D {
+        #[serde(rename = "v")]
+        v: String,
+    },
+    #[serde(rename = "m")]
+    M {},
+    #[serde(rename = "x")]
+    X {
+        #[serde(rename = "v")]
+        v: u32,
+    },
+}
+
+/// XMLPivotCacheRecords directly maps the pivotCacheRecords element from the
+/// namespace http://schemas.openxmlformats.org/spreadsheetml/2006/main. This
+/// element represents the pivotCacheRecords part, which holds the underlying
+/// data of the pivot cache.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "pivotCacheRecords")]
+pub struct XMLPivotCacheRecords {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "r", default)]
+    pub r: Vec<CTRecord>,
+}
+
+/// CTRecord directly maps the r element. This element represents a single
+/// record of the source data in the pivot cache, the values are in the order
+/// of the cache fields.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTRecord {
+    #[serde(rename = "$value", default)]
+    pub values: Vec<CTCacheValue>,
+}
@@ -0,0 +1,249 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLPivotTableDefinition directly maps the pivotTableDefinition element
+/// from the namespace http://schemas.openxmlformats.org/spreadsheetml/2006/main.
+/// This element represents the pivotTable part, which defines the location of
+/// the pivot table, the pivot cache it's based on and the layout of the row,
+/// column, page and data fields.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "pivotTableDefinition")]
+pub struct XMLPivotTableDefinition {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "cacheId")]
+    pub cache_id: u32,
+    #[serde(rename = "dataCaption")]
+    pub data_caption: String,
+    #[serde(rename = "dataOnRows")]
+    pub data_on_rows: Option<bool>,
+    #[serde(rename = "applyNumberFormats")]
+    pub apply_number_formats: Option<bool>,
+    #[serde(rename = "applyBorderFormats")]
+    pub apply_border_formats: Option<bool>,
+    #[serde(rename = "applyFontFormats")]
+    pub apply_font_formats: Option<bool>,
+    #[serde(rename = "applyPatternFormats")]
+    pub apply_pattern_formats: Option<bool>,
+    #[serde(rename = "applyAlignmentFormats")]
+    pub apply_alignment_formats: Option<bool>,
+    #[serde(rename = "applyWidthHeightFormats")]
+    pub apply_width_height_formats: Option<bool>,
+    #[serde(rename = "showDrill")]
+    pub show_drill: Option<bool>,
+    #[serde(rename = "useAutoFormatting")]
+    pub use_auto_formatting: Option<bool>,
+    #[serde(rename = "pageOverThenDown")]
+    pub page_over_then_down: Option<bool>,
+    #[serde(rename = "mergeItem")]
+    pub merge_item: Option<bool>,
+    #[serde(rename = "itemPrintTitles")]
+    pub item_print_titles: Option<bool>,
+    #[serde(rename = "createdVersion")]
+    pub created_version: Option<u32>,
+    #[serde(rename = "indent")]
+    pub indent: Option<u32>,
+    #[serde(rename = "outline")]
+    pub outline: Option<bool>,
+    #[serde(rename = "outlineData")]
+    pub outline_data: Option<bool>,
+    #[serde(rename = "multipleFieldFilters")]
+    pub multiple_field_filters: Option<bool>,
+    #[serde(rename = "rowGrandTotals")]
+    pub row_grand_totals: Option<bool>,
+    #[serde(rename = "colGrandTotals")]
+    pub col_grand_totals: Option<bool>,
+    #[serde(rename = "compact")]
+    pub compact: Option<bool>,
+    #[serde(rename = "compactData")]
+    pub compact_data: Option<bool>,
+    #[serde(rename = "showError")]
+    pub show_error: Option<bool>,
+    #[serde(rename = "location")]
+    pub location: CTLocation,
+    #[serde(rename = "pivotFields")]
+    pub pivot_fields: Option<CTPivotFields>,
+    #[serde(rename = "rowFields")]
+    pub row_fields: Option<CTFields>,
+    #[serde(rename = "colFields")]
+    pub col_fields: Option<CTFields>,
+    #[serde(rename = "pageFields")]
+    pub page_fields: Option<CTPageFields>,
+    #[serde(rename = "dataFields")]
+    pub data_fields: Option<CTDataFields>,
+    #[serde(rename = "pivotTableStyleInfo")]
+    pub pivot_table_style_info: Option<CTPivotTableStyleInfo>,
+}
+
+/// CTLocation directly maps the location element. This element represents
+/// the location of the pivot table in the worksheet, the first header row,
+/// data row and data column are relative to the top-left cell of the range.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTLocation {
+    #[serde(rename = "ref")]
+    pub ref_attr: String,
+    #[serde(rename = "firstHeaderRow")]
+    pub first_header_row: u32,
+    #[serde(rename = "firstDataRow")]
+    pub first_data_row: u32,
+    #[serde(rename = "firstDataCol")]
+    pub first_data_col: u32,
+    #[serde(rename = "rowPageCount")]
+    pub row_page_count: Option<u32>,
+    #[serde(rename = "colPageCount")]
+    pub col_page_count: Option<u32>,
+}
+
+/// CTPivotFields directly maps the pivotFields element. This element
+/// represents the collection of fields that appear on the pivot table, in the
+/// order of the cache fields.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPivotFields {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "pivotField", default)]
+    pub pivot_field: Vec<CTPivotField>,
+}
+
+/// CTPivotField directly maps the pivotField element. This element represents
+/// a single field in the pivot table, the axis specifies the region of the
+/// pivot table the field is displayed in.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPivotField {
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+    #[serde(rename = "axis")]
+    pub axis: Option<String>,
+    #[serde(rename = "dataField")]
+    pub data_field: Option<bool>,
+    #[serde(rename = "compact")]
+    pub compact: Option<bool>,
+    #[serde(rename = "outline")]
+    pub outline: Option<bool>,
+    #[serde(rename = "showAll")]
+    pub show_all: Option<bool>,
+    #[serde(rename = "defaultSubtotal")]
+    pub default_subtotal: Option<bool>,
+    #[serde(rename = "items")]
+    pub items: Option<CTItems>,
+}
+
+/// CTItems directly maps the items element. This element represents the
+/// collection of items in the pivot field.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTItems {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "item", default)]
+    pub item: Vec<CTItem>,
+}
+
+/// CTItem directly maps the item element. This element represents a single
+/// item in the pivot field, the x attribute is the index of the shared item
+/// of the cache field, and the t attribute specifies the type of the subtotal
+/// item.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTItem {
+    #[serde(rename = "x")]
+    pub x: Option<u32>,
+    #[serde(rename = "t")]
+    pub t: Option<String>,
+    #[serde(rename = "h")]
+    pub h: Option<bool>,
+}
+
+/// CTFields directly maps the rowFields and colFields elements. These
+/// elements represent the collection of the row or column fields of the pivot
+/// table, the field index -2 represents the values of the data fields.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTFields {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "field", default)]
+    pub field: Vec<CTField>,
+}
+
+/// CTField directly maps the field element. This element represents a field
+/// of the row or column axis by the index of the pivot field.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTField {
+    #[serde(rename = "x")]
+    pub x: i32,
+}
+
+/// CTPageFields directly maps the pageFields element. This element represents
+/// the collection of the fields on the page or report filter area of the
+/// pivot table.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPageFields {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "pageField", default)]
+    pub page_field: Vec<CTPageField>,
+}
+
+/// CTPageField directly maps the pageField element. This element represents a
+/// field on the page or report filter area of the pivot table.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPageField {
+    #[serde(rename = "fld")]
+    pub fld: i32,
+    #[serde(rename = "item")]
+    pub item: Option<u32>,
+    #[serde(rename = "hier")]
+    pub hier: Option<i32>,
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+}
+
+/// CTDataFields directly maps the dataFields element. This element represents
+/// the collection of the fields on the values area of the pivot table.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDataFields {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "dataField", default)]
+    pub data_field: Vec<CTDataField>,
+}
+
+/// CTDataField directly maps the dataField element. This element represents a
+/// field on the values area of the pivot table, the subtotal specifies the
+/// aggregate function of the field.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDataField {
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+    #[serde(rename = "fld")]
+    pub fld: u32,
+    #[serde(rename = "subtotal")]
+    pub subtotal: Option<String>,
+    #[serde(rename = "baseField")]
+    pub base_field: Option<i32>,
+    #[serde(rename = "baseItem")]
+    pub base_item: Option<u32>,
+    #[serde(rename = "numFmtId")]
+    pub num_fmt_id: Option<u32>,
+}
+
+/// CTPivotTableStyleInfo directly maps the pivotTableStyleInfo element. This
+/// element specifies the style of the pivot table, and which portions of the
+/// pivot table have the style applied.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPivotTableStyleInfo {
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+    #[serde(rename = "showRowHeaders")]
+    pub show_row_headers: Option<bool>,
+    #[serde(rename = "showColHeaders")]
+    pub show_col_headers: Option<bool>,
+    #[serde(rename = "showRowStripes")]
+    pub show_row_stripes: Option<bool>,
+    #[serde(rename = "showColStripes")]
+    pub show_col_stripes: Option<bool>,
+    #[serde(rename = "showLastColumn")]
+    pub show_last_column: Option<bool>,
+}
@@ -23,4 +23,6 @@ pub struct XMLRelationship {
     pub rel_type: String,
     #[serde(rename = "Target")]
     pub target: String,
+    #[serde(rename = "TargetMode")]
+    pub target_mode: Option<String>,
 }
@@ -2,6 +2,7 @@
 // this source code is governed by a BSD-style license that can be found in
 // the LICENSE file.
 
+use crate::CTColor;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -32,7 +33,7 @@ pub struct CTSST {
 /// the cell is more complex - i.e., has formatting applied at the character
 /// level - then the string item shall consist of multiple rich text runs which
 /// collectively are used to express the string.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename = "si")]
 pub struct CTRst {
     #[serde(rename = "t")]
@@ -49,7 +50,7 @@ pub struct CTRst {
 /// hint for this String Item (si). Phonetic hints are used to give information
 /// about the pronunciation of an East Asian language. The hints are displayed
 /// as text within the spreadsheet cells across the top portion of the cell.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename = "rPh")]
 pub struct CTPhoneticRun {}
 
@@ -60,11 +61,11 @@ pub struct CTPhoneticRun {}
 /// cells across the top portion of the cell. Since the phonetic hints are text,
 /// every phonetic hint is expressed as a phonetic run (rPh), and these
 /// properties specify how to display that phonetic run.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename = "phoneticPr")]
 pub struct CTPhoneticPr {}
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename = "r")]
 pub struct RElt {
     #[serde(rename = "t")]
@@ -78,7 +79,7 @@ pub struct RElt {
 /// applied to the text. These properties are defined as direct formatting, since
 /// they are directly applied to the run and supersede any formatting from
 /// styles.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename = "rPr")]
 pub struct RPrElt {
     #[serde(rename = "rFont")]
@@ -101,11 +102,17 @@ pub struct RPrElt {
     pub condense: Option<STXBool>,
     #[serde(rename = "extend")]
     pub extend: Option<STXBool>,
+    #[serde(rename = "color")]
+    pub color: Option<CTColor>,
+    #[serde(rename = "sz")]
+    pub sz: Option<STXDouble>,
+    #[serde(rename = "u")]
+    pub u: Option<STXUnderline>,
 }
 
 /// STXstring directly maps the val element with string data type as an
 /// attribute
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct STXstring {
     #[serde(rename = "val")]
     pub val: String,
@@ -113,7 +120,7 @@ pub struct STXstring {
 
 /// STXInt directly maps the val element with integer data type as an
 /// attribute
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct STXInt {
     #[serde(rename = "val")]
     pub val: i32,
@@ -121,8 +128,24 @@ pub struct STXInt {
 
 /// STXBool directly maps the val element with boolean data type as an
 /// attribute
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct STXBool {
     #[serde(rename = "val")]
     pub val: Option<bool>,
 }
+
+/// STXDouble directly maps the val element with float data type as an
+/// attribute
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct STXDouble {
+    #[serde(rename = "val")]
+    pub val: f64,
+}
+
+/// STXUnderline directly maps the u element, the underline style is single
+/// when the val attribute is omitted.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct STXUnderline {
+    #[serde(rename = "val")]
+    pub val: Option<String>,
+}
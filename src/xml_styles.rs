@@ -0,0 +1,212 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::CTColor;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLStyleSheet directly maps the styleSheet element in the namespace
+/// http://schemas.openxmlformats.org/spreadsheetml/2006/main - currently I have
+/// not checked it for completeness - it does as much as I need.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "styleSheet")]
+pub struct XMLStyleSheet {
+    #[serde(rename = "fonts")]
+    pub fonts: Option<CTFonts>,
+    #[serde(rename = "fills")]
+    pub fills: Option<CTFills>,
+    #[serde(rename = "cellXfs")]
+    pub cell_xfs: Option<CTCellXfs>,
+    #[serde(rename = "dxfs")]
+    pub dxfs: Option<CTDxfs>,
+}
+
+/// CTFonts directly maps the fonts element. This element contains all font
+/// definitions for this workbook.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTFonts {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "font")]
+    pub font: Vec<CTFont>,
+}
+
+/// CTFont directly maps the font element. This element defines the
+/// properties for one of the fonts used in this workbook.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTFont {
+    #[serde(rename = "b")]
+    pub b: Option<CTBooleanProperty>,
+    #[serde(rename = "i")]
+    pub i: Option<CTBooleanProperty>,
+    #[serde(rename = "strike")]
+    pub strike: Option<CTBooleanProperty>,
+    #[serde(rename = "color")]
+    pub color: Option<CTColor>,
+    #[serde(rename = "sz")]
+    pub sz: Option<CTFontSize>,
+    #[serde(rename = "name")]
+    pub name: Option<CTFontName>,
+}
+
+/// CTBooleanProperty directly maps the element which specifies a boolean
+/// property, such as the b, i and strike elements. The property is enabled
+/// when the val attribute is omitted.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTBooleanProperty {
+    #[serde(rename = "val")]
+    pub val: Option<bool>,
+}
+
+/// CTFontSize directly maps the sz element. This element represents the point
+/// size (1/72 of an inch) of the Latin and East Asian text.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTFontSize {
+    #[serde(rename = "val")]
+    pub val: f64,
+}
+
+/// CTFontName directly maps the name element. This element specifies the font
+/// face name.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTFontName {
+    #[serde(rename = "val")]
+    pub val: String,
+}
+
+/// CTFills directly maps the fills element. This element defines the cell
+/// fills portion of the Styles part, consisting of a sequence of fill records.
+/// A cell fill consists of a background color, foreground color, and pattern
+/// to be applied across the cell.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTFills {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "fill")]
+    pub fill: Vec<CTFill>,
+}
+
+/// CTFill directly maps the fill element. This element specifies fill
+/// formatting.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTFill {
+    #[serde(rename = "patternFill")]
+    pub pattern_fill: Option<CTPatternFill>,
+}
+
+/// CTPatternFill directly maps the patternFill element in the namespace
+/// http://schemas.openxmlformats.org/spreadsheetml/2006/main - This element is
+/// used to specify cell fill information for pattern and solid color cell
+/// fills. For solid cell fills (no pattern), fgColor is used. For cell fills
+/// with patterns specified, then the cell fill color is specified by the
+/// bgColor element.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPatternFill {
+    #[serde(rename = "patternType")]
+    pub pattern_type: Option<String>,
+    #[serde(rename = "fgColor")]
+    pub fg_color: Option<CTColor>,
+    #[serde(rename = "bgColor")]
+    pub bg_color: Option<CTColor>,
+}
+
+/// CTCellXfs directly maps the cellXfs element. This element contains the
+/// master formatting records (xf) which define the formatting applied to cells
+/// in this workbook.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCellXfs {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "xf")]
+    pub xf: Vec<CTXf>,
+}
+
+/// CTXf directly maps the xf element. A single xf element describes all of the
+/// formatting for a cell.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTXf {
+    #[serde(rename = "numFmtId")]
+    pub num_fmt_id: Option<u32>,
+    #[serde(rename = "fontId")]
+    pub font_id: Option<u32>,
+    #[serde(rename = "fillId")]
+    pub fill_id: Option<u32>,
+    #[serde(rename = "borderId")]
+    pub border_id: Option<u32>,
+    #[serde(rename = "xfId")]
+    pub xf_id: Option<u32>,
+    #[serde(rename = "applyNumberFormat")]
+    pub apply_number_format: Option<bool>,
+    #[serde(rename = "applyFont")]
+    pub apply_font: Option<bool>,
+    #[serde(rename = "applyFill")]
+    pub apply_fill: Option<bool>,
+    #[serde(rename = "applyBorder")]
+    pub apply_border: Option<bool>,
+    #[serde(rename = "applyAlignment")]
+    pub apply_alignment: Option<bool>,
+    #[serde(rename = "applyProtection")]
+    pub apply_protection: Option<bool>,
+}
+
+/// CTDxfs directly maps the dxfs element. This element contains the master
+/// differential formatting records (dxf's) which define formatting for all
+/// non-cell formatting in this workbook.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDxfs {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "dxf")]
+    pub dxf: Vec<CTDxf>,
+}
+
+/// CTDxf directly maps the dxf element. A single dxf record, expressing
+/// incremental formatting to be applied, such as the conditional formatting,
+/// the table styles and the sort conditions.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDxf {
+    #[serde(rename = "font")]
+    pub font: Option<CTFont>,
+    #[serde(rename = "numFmt")]
+    pub num_fmt: Option<CTNumFmt>,
+    #[serde(rename = "fill")]
+    pub fill: Option<CTFill>,
+    #[serde(rename = "border")]
+    pub border: Option<CTBorder>,
+}
+
+/// CTNumFmt directly maps the numFmt element. This element specifies number
+/// format properties which indicate how to format and render the numeric
+/// value of a cell.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTNumFmt {
+    #[serde(rename = "numFmtId")]
+    pub num_fmt_id: u32,
+    #[serde(rename = "formatCode")]
+    pub format_code: String,
+}
+
+/// CTBorder directly maps the border element. This element contains the
+/// border formatting of the cell edges.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTBorder {
+    #[serde(rename = "left")]
+    pub left: Option<CTBorderPr>,
+    #[serde(rename = "right")]
+    pub right: Option<CTBorderPr>,
+    #[serde(rename = "top")]
+    pub top: Option<CTBorderPr>,
+    #[serde(rename = "bottom")]
+    pub bottom: Option<CTBorderPr>,
+}
+
+/// CTBorderPr directly maps the element of the border edge, such as the left,
+/// right, top and bottom elements.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTBorderPr {
+    #[serde(rename = "style")]
+    pub style: Option<String>,
+    #[serde(rename = "color")]
+    pub color: Option<CTColor>,
+}
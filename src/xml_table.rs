@@ -0,0 +1,82 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use crate::CTAutoFilter;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLTable directly maps the table element from the namespace
+/// http://schemas.openxmlformats.org/spreadsheetml/2006/main. A table helps
+/// organize and provide structure to lists of information in a worksheet.
+/// Tables have clearly labeled columns, rows, and data regions. Tables make it
+/// easier for users to sort, analyze, format, manage, add, and delete
+/// information.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "table")]
+pub struct XMLTable {
+    #[serde(rename = "id")]
+    pub id: u32,
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(rename = "ref")]
+    pub ref_attr: String,
+    #[serde(rename = "headerRowCount")]
+    pub header_row_count: Option<u32>,
+    #[serde(rename = "totalsRowCount")]
+    pub totals_row_count: Option<u32>,
+    #[serde(rename = "totalsRowShown")]
+    pub totals_row_shown: Option<bool>,
+    #[serde(rename = "autoFilter")]
+    pub auto_filter: Option<CTAutoFilter>,
+    #[serde(rename = "tableColumns")]
+    pub table_columns: CTTableColumns,
+    #[serde(rename = "tableStyleInfo")]
+    pub table_style_info: Option<CTTableStyleInfo>,
+}
+
+/// CTTableColumns directly maps the tableColumns element. An element
+/// representing the collection of all table columns for this table.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTableColumns {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "tableColumn", default)]
+    pub table_column: Vec<CTTableColumn>,
+}
+
+/// CTTableColumn directly maps the tableColumn element. An element
+/// representing a single column for this table, the name of the column is the
+/// text of the header cell.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTableColumn {
+    #[serde(rename = "id")]
+    pub id: u32,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "totalsRowFunction")]
+    pub totals_row_function: Option<String>,
+    #[serde(rename = "totalsRowLabel")]
+    pub totals_row_label: Option<String>,
+    #[serde(rename = "dataDxfId")]
+    pub data_dxf_id: Option<u32>,
+}
+
+/// CTTableStyleInfo directly maps the tableStyleInfo element. This element
+/// describes which style is used to display this table, and specifies which
+/// portions of the table have the style applied.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTableStyleInfo {
+    #[serde(rename = "name")]
+    pub name: Option<String>,
+    #[serde(rename = "showFirstColumn")]
+    pub show_first_column: Option<bool>,
+    #[serde(rename = "showLastColumn")]
+    pub show_last_column: Option<bool>,
+    #[serde(rename = "showRowStripes")]
+    pub show_row_stripes: Option<bool>,
+    #[serde(rename = "showColumnStripes")]
+    pub show_column_stripes: Option<bool>,
+}
@@ -0,0 +1,64 @@
+// Copyright 2021 - 2024 The excelize Authors. All rights reserved. Use of
+// this source code is governed by a BSD-style license that can be found in
+// the LICENSE file.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// XMLThreadedComments directly maps the ThreadedComments element from the
+/// namespace http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments.
+/// This element is the root of the threaded comments part of the worksheet,
+/// a threaded comment is a conversation of the top comment and the replies
+/// attached to a cell.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "ThreadedComments")]
+pub struct XMLThreadedComments {
+    #[serde(rename = "threadedComment", default)]
+    pub threaded_comment: Vec<XMLThreadedComment>,
+}
+
+/// XMLThreadedComment directly maps the threadedComment element. This element
+/// represents a single comment of the thread, the replies of the thread refer
+/// to the top comment by the parent ID.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLThreadedComment {
+    #[serde(rename = "ref")]
+    pub ref_attr: String,
+    #[serde(rename = "dT")]
+    pub dt: Option<String>,
+    #[serde(rename = "personId")]
+    pub person_id: String,
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "parentId")]
+    pub parent_id: Option<String>,
+    #[serde(rename = "done")]
+    pub done: Option<bool>,
+    #[serde(rename = "text", default)]
+    pub text: String,
+}
+
+/// XMLPersonList directly maps the personList element from the namespace
+/// http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments.
+/// This element is the root of the persons part of the workbook, which holds
+/// the authors of the threaded comments.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "personList")]
+pub struct XMLPersonList {
+    #[serde(rename = "person", default)]
+    pub person: Vec<XMLPerson>,
+}
+
+/// XMLPerson directly maps the person element. This element specifies an
+/// author of the threaded comments.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLPerson {
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "userId")]
+    pub user_id: Option<String>,
+    #[serde(rename = "providerId")]
+    pub provider_id: Option<String>,
+}
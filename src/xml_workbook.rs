@@ -13,8 +13,14 @@ use serde::Serialize;
 pub struct XMLWorkbook {
     #[serde(rename = "fileVersion")]
     pub file_version: XMLFileVersion,
+    #[serde(rename = "workbookProtection")]
+    pub workbook_protection: Option<XMLWorkbookProtection>,
     #[serde(rename = "sheets")]
     pub sheets: XMLSheets,
+    #[serde(rename = "definedNames")]
+    pub defined_names: Option<XMLDefinedNames>,
+    #[serde(rename = "pivotCaches")]
+    pub pivot_caches: Option<XMLPivotCaches>,
 }
 
 /// XMLFileVersion directly maps the fileVersion element. This element defines
@@ -33,6 +39,40 @@ pub struct XMLFileVersion {
     pub rup_build: i32,
 }
 
+/// XMLWorkbookProtection directly maps the workbookProtection element. This
+/// element specifies options for protecting data in the workbook. Applications
+/// might use workbook protection to prevent anyone from accidentally changing,
+/// moving, or deleting important data.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLWorkbookProtection {
+    #[serde(rename = "workbookAlgorithmName")]
+    pub workbook_algorithm_name: Option<String>,
+    #[serde(rename = "workbookHashValue")]
+    pub workbook_hash_value: Option<String>,
+    #[serde(rename = "workbookSaltValue")]
+    pub workbook_salt_value: Option<String>,
+    #[serde(rename = "workbookSpinCount")]
+    pub workbook_spin_count: Option<u32>,
+    #[serde(rename = "revisionsAlgorithmName")]
+    pub revisions_algorithm_name: Option<String>,
+    #[serde(rename = "revisionsHashValue")]
+    pub revisions_hash_value: Option<String>,
+    #[serde(rename = "revisionsSaltValue")]
+    pub revisions_salt_value: Option<String>,
+    #[serde(rename = "revisionsSpinCount")]
+    pub revisions_spin_count: Option<u32>,
+    #[serde(rename = "workbookPassword")]
+    pub workbook_password: Option<String>,
+    #[serde(rename = "revisionsPassword")]
+    pub revisions_password: Option<String>,
+    #[serde(rename = "lockStructure")]
+    pub lock_structure: Option<bool>,
+    #[serde(rename = "lockWindows")]
+    pub lock_windows: Option<bool>,
+    #[serde(rename = "lockRevision")]
+    pub lock_revision: Option<bool>,
+}
+
 /// XMLSheets directly maps the sheets element from the namespace
 /// http://schemas.openxmlformats.org/spreadsheetml/2006/main.
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
@@ -52,3 +92,52 @@ pub struct XMLSheet {
     #[serde(rename = "r:id")]
     pub id: String,
 }
+
+/// XMLDefinedNames directly maps the definedNames element. This element
+/// defines the collection of defined names for this workbook. Defined names are
+/// descriptive names to represent cells, ranges of cells, formulas, or constant
+/// values. Defined names can be used to represent a range on any worksheet.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct XMLDefinedNames {
+    #[serde(rename = "definedName")]
+    pub defined_name: Vec<XMLDefinedName>,
+}
+
+/// XMLDefinedName directly maps the definedName element from the namespace
+/// http://schemas.openxmlformats.org/spreadsheetml/2006/main. This element
+/// defines a defined name within this workbook. A defined name is descriptive
+/// text that is used to represents a cell, range of cells, formula, or
+/// constant value.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct XMLDefinedName {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "comment")]
+    pub comment: Option<String>,
+    #[serde(rename = "localSheetId")]
+    pub local_sheet_id: Option<u32>,
+    #[serde(rename = "hidden")]
+    pub hidden: Option<bool>,
+    #[serde(rename = "$value")]
+    pub data: String,
+}
+
+/// XMLPivotCaches directly maps the pivotCaches element. This element
+/// enumerates the pivot cache definition parts used by the pivot tables on
+/// the worksheets of the workbook.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLPivotCaches {
+    #[serde(rename = "pivotCache", default)]
+    pub pivot_cache: Vec<XMLPivotCache>,
+}
+
+/// XMLPivotCache directly maps the pivotCache element. This element specifies
+/// the cache ID of the pivot cache which is referenced by the pivot tables,
+/// and the relationship Id references the pivot cache definition part.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct XMLPivotCache {
+    #[serde(rename = "cacheId")]
+    pub cache_id: u32,
+    #[serde(rename = "r:id")]
+    pub rid: String,
+}
@@ -24,10 +24,28 @@ pub struct XMLWorksheet {
     pub sheet_data: CTSheetData,
     #[serde(rename = "sheetProtection")]
     pub sheet_protection: Option<CTSheetProtection>,
+    #[serde(rename = "protectedRanges")]
+    pub protected_ranges: Option<CTProtectedRanges>,
     #[serde(rename = "autoFilter")]
     pub auto_filter: Option<CTAutoFilter>,
     #[serde(rename = "sortState")]
     pub sort_state: Option<CTSortState>,
+    #[serde(rename = "mergeCells")]
+    pub merge_cells: Option<CTMergeCells>,
+    #[serde(rename = "conditionalFormatting")]
+    pub conditional_formatting: Option<Vec<CTConditionalFormatting>>,
+    #[serde(rename = "dataValidations")]
+    pub data_validations: Option<CTDataValidations>,
+    #[serde(rename = "hyperlinks")]
+    pub hyperlinks: Option<CTHyperlinks>,
+    #[serde(rename = "drawing")]
+    pub drawing: Option<CTDrawing>,
+    #[serde(rename = "legacyDrawing")]
+    pub legacy_drawing: Option<CTLegacyDrawing>,
+    #[serde(rename = "tableParts")]
+    pub table_parts: Option<CTTableParts>,
+    #[serde(rename = "extLst")]
+    pub ext_lst: Option<CTWorksheetExtLst>,
 }
 
 /// CTCols defines column width and column formatting for one or more columns
@@ -40,7 +58,7 @@ pub struct CTCols {
 
 /// CTCol directly maps the col (Column Width & Formatting). Defines column
 /// width and column formatting for one or more columns of the worksheet.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTCol {
     #[serde(rename = "min")]
     pub min: u32,
@@ -95,18 +113,88 @@ pub struct CTSheetViews {
 /// the others are discarded. When multiple windows are viewing the same sheet,
 /// multiple sheetView elements (with corresponding workbookView entries) are
 /// saved.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTSheetView {
+    #[serde(rename = "windowProtection")]
+    pub window_protection: Option<bool>,
+    #[serde(rename = "showFormulas")]
+    pub show_formulas: Option<bool>,
+    #[serde(rename = "showGridLines")]
+    pub show_grid_lines: Option<bool>,
+    #[serde(rename = "showRowColHeaders")]
+    pub show_row_col_headers: Option<bool>,
+    #[serde(rename = "showZeros")]
+    pub show_zeros: Option<bool>,
+    #[serde(rename = "rightToLeft")]
+    pub right_to_left: Option<bool>,
     #[serde(rename = "tabSelected")]
     pub tab_selected: Option<bool>,
+    #[serde(rename = "showRuler")]
+    pub show_ruler: Option<bool>,
+    #[serde(rename = "showWhiteSpace")]
+    pub show_white_space: Option<bool>,
+    #[serde(rename = "showOutlineSymbols")]
+    pub show_outline_symbols: Option<bool>,
+    #[serde(rename = "defaultGridColor")]
+    pub default_grid_color: Option<bool>,
+    #[serde(rename = "view")]
+    pub view: Option<String>,
+    #[serde(rename = "topLeftCell")]
+    pub top_left_cell: Option<String>,
+    #[serde(rename = "colorId")]
+    pub color_id: Option<u32>,
+    #[serde(rename = "zoomScale")]
+    pub zoom_scale: Option<u32>,
+    #[serde(rename = "zoomScaleNormal")]
+    pub zoom_scale_normal: Option<u32>,
+    #[serde(rename = "zoomScalePageLayoutView")]
+    pub zoom_scale_page_layout_view: Option<u32>,
+    #[serde(rename = "zoomScaleSheetLayoutView")]
+    pub zoom_scale_sheet_layout_view: Option<u32>,
     #[serde(rename = "workbookViewId")]
     pub workbook_view_id: i32,
+    #[serde(rename = "pane")]
+    pub pane: Option<CTPane>,
+    #[serde(rename = "selection")]
+    pub selection: Option<Vec<CTSelection>>,
+}
+
+/// CTPane directly maps the pane element in the namespace
+/// http://schemas.openxmlformats.org/spreadsheetml/2006/main. Worksheet view
+/// pane.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTPane {
+    #[serde(rename = "xSplit")]
+    pub x_split: Option<f64>,
+    #[serde(rename = "ySplit")]
+    pub y_split: Option<f64>,
+    #[serde(rename = "topLeftCell")]
+    pub top_left_cell: Option<String>,
+    #[serde(rename = "activePane")]
+    pub active_pane: Option<String>,
+    #[serde(rename = "state")]
+    pub state: Option<String>,
+}
+
+/// CTSelection directly maps the selection element in the namespace
+/// http://schemas.openxmlformats.org/spreadsheetml/2006/main. Worksheet view
+/// selection.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTSelection {
+    #[serde(rename = "pane")]
+    pub pane: Option<String>,
+    #[serde(rename = "activeCell")]
+    pub active_cell: Option<String>,
+    #[serde(rename = "activeCellId")]
+    pub active_cell_id: Option<u32>,
+    #[serde(rename = "sqref")]
+    pub sqref: Option<String>,
 }
 
 /// CTSheetFormatPr directly maps the sheetFormatPr element in the namespace
 /// http://schemas.openxmlformats.org/spreadsheetml/2006/main. This element
 /// specifies the sheet formatting properties.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename = "sheetFormatPr")]
 pub struct CTSheetFormatPr {
     #[serde(rename = "baseColWidth")]
@@ -142,7 +230,7 @@ pub struct CTSheetData {
 /// CTRow directly maps the row element. The element expresses information
 /// about an entire row of a worksheet, and contains all cell definitions for a
 /// particular row in the worksheet.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 #[serde(rename = "row")]
 pub struct CTRow {
     pub r: Option<u32>,
@@ -182,16 +270,35 @@ pub struct CTRow {
 pub struct CTCell {
     #[serde(rename = "r")]
     pub r: String,
+    #[serde(rename = "s")]
+    pub s: Option<u32>,
     #[serde(rename = "t")]
     pub t: Option<String>,
+    #[serde(rename = "f")]
+    pub f: Option<CTCellFormula>,
     #[serde(rename = "v")]
     pub v: Option<String>,
 }
 
+/// CTCellFormula directly maps the f element. Formula for the cell. The
+/// formula expression is contained in the character node of this element.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename = "f")]
+pub struct CTCellFormula {
+    #[serde(rename = "t")]
+    pub t: Option<String>,
+    #[serde(rename = "ref")]
+    pub ref_attr: Option<String>,
+    #[serde(rename = "si")]
+    pub si: Option<u32>,
+    #[serde(rename = "$value")]
+    pub content: Option<String>,
+}
+
 /// CTSheetPr directly maps the sheetPr element in the namespace
 /// http://schemas.openxmlformats.org/spreadsheetml/2006/main - Sheet-level
 /// properties.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTSheetPr {
     #[serde(rename = "syncHorizontal")]
     pub sync_horizontal: Option<bool>,
@@ -225,7 +332,7 @@ pub struct CTSheetPr {
 /// to make a patterned cell fill. Background color of the cell fill pattern.
 /// Cell fill patterns operate with two colors: a background color and a
 /// foreground color. These combine together to make a patterned cell fill.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTColor {
     #[serde(rename = "auto")]
     pub auto: Option<bool>,
@@ -241,7 +348,7 @@ pub struct CTColor {
 
 /// CTOutlinePr maps to the outlinePr element. SummaryBelow allows you to
 /// adjust the direction of grouper controls.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTOutlinePr {
     #[serde(rename = "applyStyles")]
     pub apply_styles: Option<bool>,
@@ -262,10 +369,44 @@ pub struct CTPageSetUpPr {
     pub fit_to_page: Option<bool>,
 }
 
+/// CTProtectedRanges directly maps the protectedRanges element. This
+/// collection represents the ranges on a protected sheet which can be edited
+/// by the users, optionally with a password.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTProtectedRanges {
+    #[serde(rename = "protectedRange")]
+    pub protected_range: Vec<CTProtectedRange>,
+}
+
+/// CTProtectedRange directly maps the protectedRange element. This element
+/// specifies a range which is unlocked when the sheet is protected, the
+/// password hash is stored in the same way as the sheet protection.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTProtectedRange {
+    #[serde(rename = "password")]
+    pub password: Option<String>,
+    #[serde(rename = "sqref")]
+    pub sqref: String,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "securityDescriptor")]
+    pub security_descriptor: Option<String>,
+    #[serde(rename = "algorithmName")]
+    pub algorithm_name: Option<String>,
+    #[serde(rename = "hashValue")]
+    pub hash_value: Option<String>,
+    #[serde(rename = "saltValue")]
+    pub salt_value: Option<String>,
+    #[serde(rename = "spinCount")]
+    pub spin_count: Option<u32>,
+}
+
 /// CTSheetProtection collection expresses the sheet protection options to
 /// enforce when the sheet is protected.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTSheetProtection {
+    #[serde(rename = "password")]
+    pub password: Option<String>,
     #[serde(rename = "algorithmName")]
     pub algorithm_name: Option<String>,
     #[serde(rename = "hashValue")]
@@ -311,11 +452,11 @@ pub struct CTSheetProtection {
 /// CTAutoFilter temporarily hides rows based on a filter criteria, which is
 /// applied column by column to a table of data in the worksheet. This collection
 /// expresses AutoFilter settings.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTAutoFilter {
     #[serde(rename = "ref")]
     pub ref_attr: Option<String>,
-    #[serde(rename = "filterColumn")]
+    #[serde(rename = "filterColumn", default)]
     pub filter_column: Vec<CTFilterColumn>,
     #[serde(rename = "sortState")]
     pub sort_state: Option<CTSortState>,
@@ -326,7 +467,7 @@ pub struct CTAutoFilter {
 /// specifies filter information that has been applied to this column. If a
 /// column in the AutoFilter range has no criteria specified, then there is no
 /// corresponding filterColumn collection expressed for that column.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTFilterColumn {
     #[serde(rename = "colId")]
     pub col_id: u32,
@@ -380,16 +521,18 @@ pub struct CTIconFilter {
 /// or with the current system date (e.g., show values for "today"). For any
 /// cells whose values do not meet the specified criteria, the corresponding rows
 /// shall be hidden from view when the filter is applied.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTDynamicFilter {
     #[serde(rename = "type")]
     pub type_attr: String,
     #[serde(rename = "val")]
     pub val: Option<f64>,
     #[serde(rename = "valIso")]
-    pub val_iso: Option<u8>,
+    pub val_iso: Option<String>,
+    #[serde(rename = "maxVal")]
+    pub max_val: Option<f64>,
     #[serde(rename = "maxValIso")]
-    pub max_val_iso: Option<u8>,
+    pub max_val_iso: Option<String>,
 }
 
 /// CTCustomFilters directly maps the customFilters element. When there is more
@@ -481,7 +624,7 @@ pub struct CTDateGroupItem {
 
 /// CTSortState directly maps the sortState element. This collection
 /// preserves the AutoFilter sort state.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTSortState {
     #[serde(rename = "columnSort")]
     pub column_sort: Option<bool>,
@@ -497,7 +640,10 @@ pub struct CTSortState {
     pub ext_lst: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+/// CTSortCondition directly maps the sortCondition element. This collection
+/// specifies a sort condition to apply to the sort state, the sort key column
+/// or row is specified by the ref attribute.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
 pub struct CTSortCondition {
     #[serde(rename = "descending")]
     pub descending: Option<bool>,
@@ -514,3 +660,357 @@ pub struct CTSortCondition {
     #[serde(rename = "iconId")]
     pub icon_id: Option<u32>,
 }
+
+/// CTMergeCells directly maps the mergeCells element. This collection
+/// expresses all the merged cells in the sheet.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct CTMergeCells {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "mergeCell")]
+    pub merge_cell: Vec<CTMergeCell>,
+}
+
+/// CTMergeCell directly maps the mergeCell element. A single merged cell.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct CTMergeCell {
+    #[serde(rename = "ref")]
+    pub ref_attr: String,
+}
+
+/// CTHyperlinks directly maps the hyperlinks element. This collection
+/// expresses all the hyperlinks in the sheet.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct CTHyperlinks {
+    #[serde(rename = "hyperlink")]
+    pub hyperlink: Vec<CTHyperlink>,
+}
+
+/// CTHyperlink directly maps the hyperlink element. A hyperlink can be stored
+/// in a package as a relationship. Hyperlinks shall be identified by containing
+/// a target which specifies the destination of the given hyperlink.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTHyperlink {
+    #[serde(rename = "ref")]
+    pub ref_attr: String,
+    #[serde(rename = "r:id")]
+    pub rid: Option<String>,
+    #[serde(rename = "location")]
+    pub location: Option<String>,
+    #[serde(rename = "tooltip")]
+    pub tooltip: Option<String>,
+    #[serde(rename = "display")]
+    pub display: Option<String>,
+}
+
+/// CTDataValidations directly maps the dataValidations element. This
+/// collection expresses all data validation information for cells in a sheet
+/// which have data validation features applied.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDataValidations {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "dataValidation")]
+    pub data_validation: Vec<CTDataValidation>,
+}
+
+/// CTDataValidation directly maps the single item of data validation defined
+/// on a range of the worksheet.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDataValidation {
+    #[serde(rename = "type")]
+    pub type_attr: Option<String>,
+    #[serde(rename = "errorStyle")]
+    pub error_style: Option<String>,
+    #[serde(rename = "imeMode")]
+    pub ime_mode: Option<String>,
+    #[serde(rename = "operator")]
+    pub operator: Option<String>,
+    #[serde(rename = "allowBlank")]
+    pub allow_blank: Option<bool>,
+    #[serde(rename = "showDropDown")]
+    pub show_drop_down: Option<bool>,
+    #[serde(rename = "showInputMessage")]
+    pub show_input_message: Option<bool>,
+    #[serde(rename = "showErrorMessage")]
+    pub show_error_message: Option<bool>,
+    #[serde(rename = "errorTitle")]
+    pub error_title: Option<String>,
+    #[serde(rename = "error")]
+    pub error: Option<String>,
+    #[serde(rename = "promptTitle")]
+    pub prompt_title: Option<String>,
+    #[serde(rename = "prompt")]
+    pub prompt: Option<String>,
+    #[serde(rename = "sqref")]
+    pub sqref: String,
+    #[serde(rename = "formula1")]
+    pub formula1: Option<String>,
+    #[serde(rename = "formula2")]
+    pub formula2: Option<String>,
+}
+
+/// CTConditionalFormatting directly maps the conditionalFormatting element. A
+/// conditional format is a format, such as cell shading or font color, that a
+/// spreadsheet application can automatically apply to cells if a specified
+/// condition is true.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTConditionalFormatting {
+    #[serde(rename = "pivot")]
+    pub pivot: Option<bool>,
+    #[serde(rename = "sqref")]
+    pub sqref: String,
+    #[serde(rename = "cfRule")]
+    pub cf_rule: Vec<CTCfRule>,
+}
+
+/// CTCfRule directly maps the cfRule element. This collection represents a
+/// description of a conditional formatting rule.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCfRule {
+    #[serde(rename = "type")]
+    pub type_attr: Option<String>,
+    #[serde(rename = "dxfId")]
+    pub dxf_id: Option<u32>,
+    #[serde(rename = "priority")]
+    pub priority: i32,
+    #[serde(rename = "stopIfTrue")]
+    pub stop_if_true: Option<bool>,
+    #[serde(rename = "aboveAverage")]
+    pub above_average: Option<bool>,
+    #[serde(rename = "percent")]
+    pub percent: Option<bool>,
+    #[serde(rename = "bottom")]
+    pub bottom: Option<bool>,
+    #[serde(rename = "operator")]
+    pub operator: Option<String>,
+    #[serde(rename = "text")]
+    pub text: Option<String>,
+    #[serde(rename = "timePeriod")]
+    pub time_period: Option<String>,
+    #[serde(rename = "rank")]
+    pub rank: Option<u32>,
+    #[serde(rename = "stdDev")]
+    pub std_dev: Option<i32>,
+    #[serde(rename = "equalAverage")]
+    pub equal_average: Option<bool>,
+    #[serde(rename = "formula")]
+    pub formula: Option<Vec<String>>,
+    #[serde(rename = "colorScale")]
+    pub color_scale: Option<CTColorScale>,
+    #[serde(rename = "dataBar")]
+    pub data_bar: Option<CTDataBar>,
+    #[serde(rename = "iconSet")]
+    pub icon_set: Option<CTIconSet>,
+    #[serde(rename = "extLst")]
+    pub ext_lst: Option<CTCfRuleExtLst>,
+}
+
+/// CTColorScale directly maps the colorScale element. This element describes
+/// a gradated color scale in this conditional formatting rule, the colors are
+/// corresponding to the conditional format value objects.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTColorScale {
+    #[serde(rename = "cfvo", default)]
+    pub cfvo: Vec<CTCfvo>,
+    #[serde(rename = "color", default)]
+    pub color: Vec<CTColor>,
+}
+
+/// CTDataBar directly maps the dataBar element. This element describes a data
+/// bar conditional formatting rule, the length of the bar is determined by the
+/// minimum and maximum conditional format value objects.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDataBar {
+    #[serde(rename = "minLength")]
+    pub min_length: Option<u32>,
+    #[serde(rename = "maxLength")]
+    pub max_length: Option<u32>,
+    #[serde(rename = "showValue")]
+    pub show_value: Option<bool>,
+    #[serde(rename = "cfvo", default)]
+    pub cfvo: Vec<CTCfvo>,
+    #[serde(rename = "color")]
+    pub color: Option<CTColor>,
+}
+
+/// CTIconSet directly maps the iconSet element. This element describes an
+/// icon set conditional formatting rule, the icons are assigned by the
+/// thresholds of the conditional format value objects.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTIconSet {
+    #[serde(rename = "iconSet")]
+    pub icon_set: Option<String>,
+    #[serde(rename = "showValue")]
+    pub show_value: Option<bool>,
+    #[serde(rename = "percent")]
+    pub percent: Option<bool>,
+    #[serde(rename = "reverse")]
+    pub reverse: Option<bool>,
+    #[serde(rename = "cfvo", default)]
+    pub cfvo: Vec<CTCfvo>,
+}
+
+/// CTCfvo directly maps the cfvo element. This element expresses a
+/// conditional format value object, such as the minimum, maximum, number,
+/// percent, percentile or formula, which is used by the color scales, data
+/// bars and icon sets.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCfvo {
+    #[serde(rename = "type")]
+    pub type_attr: String,
+    #[serde(rename = "val")]
+    pub val: Option<String>,
+    #[serde(rename = "gte")]
+    pub gte: Option<bool>,
+}
+
+/// CTCfRuleExtLst directly maps the extLst element of the conditional
+/// formatting rule, which links the rule to the x14 extension rule by ID.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCfRuleExtLst {
+    #[serde(rename = "ext", default)]
+    pub ext: Vec<CTCfRuleExt>,
+}
+
+/// CTCfRuleExt directly maps the ext element of the conditional formatting
+/// rule.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTCfRuleExt {
+    #[serde(rename = "uri")]
+    pub uri: String,
+    #[serde(rename = "id")]
+    pub id: Option<String>,
+}
+
+/// CTDrawing directly maps the drawing element. This element indicates that
+/// the sheet contains drawing components built on the drawingML platform. The
+/// relationship Id references the part containing the drawingML definitions.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTDrawing {
+    #[serde(rename = "r:id")]
+    pub rid: String,
+}
+
+/// CTLegacyDrawing directly maps the legacyDrawing element. This element
+/// indicates that the sheet contains drawing components built on the VML
+/// platform. The relationship Id references the VML drawing part.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTLegacyDrawing {
+    #[serde(rename = "r:id")]
+    pub rid: String,
+}
+
+/// CTTableParts directly maps the tableParts element. This element is the
+/// collection of the references to the table parts of the worksheet.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTableParts {
+    #[serde(rename = "count")]
+    pub count: Option<u32>,
+    #[serde(rename = "tablePart", default)]
+    pub table_part: Vec<CTTablePart>,
+}
+
+/// CTTablePart directly maps the tablePart element. The relationship Id
+/// references the table part of the worksheet.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTTablePart {
+    #[serde(rename = "r:id")]
+    pub rid: String,
+}
+
+/// CTWorksheetExtLst directly maps the extLst element of the worksheet. This
+/// element holds the future feature data of the worksheet, such as the x14
+/// conditional formatting.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTWorksheetExtLst {
+    #[serde(rename = "ext", default)]
+    pub ext: Vec<CTWorksheetExt>,
+}
+
+/// CTWorksheetExt directly maps the ext element of the worksheet.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTWorksheetExt {
+    #[serde(rename = "uri")]
+    pub uri: String,
+    #[serde(rename = "conditionalFormattings")]
+    pub conditional_formattings: Option<CTX14ConditionalFormattings>,
+}
+
+/// CTX14ConditionalFormattings directly maps the conditionalFormattings
+/// element in the namespace
+/// http://schemas.microsoft.com/office/spreadsheetml/2009/9/main.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTX14ConditionalFormattings {
+    #[serde(rename = "conditionalFormatting", default)]
+    pub conditional_formatting: Vec<CTX14ConditionalFormatting>,
+}
+
+/// CTX14ConditionalFormatting directly maps the x14 conditionalFormatting
+/// element. This element specifies the extended conditional formatting rules
+/// of the range.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTX14ConditionalFormatting {
+    #[serde(rename = "cfRule", default)]
+    pub cf_rule: Vec<CTX14CfRule>,
+    #[serde(rename = "sqref")]
+    pub sqref: String,
+}
+
+/// CTX14CfRule directly maps the x14 cfRule element. The ID of the rule is
+/// referenced by the extLst of the conditional formatting rule in the
+/// worksheet.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTX14CfRule {
+    #[serde(rename = "type")]
+    pub type_attr: Option<String>,
+    #[serde(rename = "priority")]
+    pub priority: Option<i32>,
+    #[serde(rename = "id")]
+    pub id: Option<String>,
+    #[serde(rename = "dataBar")]
+    pub data_bar: Option<CTX14DataBar>,
+}
+
+/// CTX14DataBar directly maps the x14 dataBar element. This element describes
+/// the modern data bar, such as the solid fill, the border, the direction and
+/// the negative value settings.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTX14DataBar {
+    #[serde(rename = "minLength")]
+    pub min_length: Option<u32>,
+    #[serde(rename = "maxLength")]
+    pub max_length: Option<u32>,
+    #[serde(rename = "border")]
+    pub border: Option<bool>,
+    #[serde(rename = "gradient")]
+    pub gradient: Option<bool>,
+    #[serde(rename = "direction")]
+    pub direction: Option<String>,
+    #[serde(rename = "negativeBarColorSameAsPositive")]
+    pub negative_bar_color_same_as_positive: Option<bool>,
+    #[serde(rename = "axisPosition")]
+    pub axis_position: Option<String>,
+    #[serde(rename = "cfvo", default)]
+    pub cfvo: Vec<CTX14Cfvo>,
+    #[serde(rename = "fillColor")]
+    pub fill_color: Option<CTColor>,
+    #[serde(rename = "borderColor")]
+    pub border_color: Option<CTColor>,
+    #[serde(rename = "negativeFillColor")]
+    pub negative_fill_color: Option<CTColor>,
+    #[serde(rename = "axisColor")]
+    pub axis_color: Option<CTColor>,
+}
+
+/// CTX14Cfvo directly maps the x14 cfvo element, the value of the object is
+/// stored in the formula element.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CTX14Cfvo {
+    #[serde(rename = "type")]
+    pub type_attr: String,
+    #[serde(rename = "gte")]
+    pub gte: Option<bool>,
+    #[serde(rename = "f")]
+    pub f: Option<String>,
+}